@@ -1,13 +1,14 @@
-use std::{f32::consts::TAU, sync::Arc};
+use std::{f32::consts::TAU, io::Cursor, sync::Arc};
 
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use kira::{
-	dsp::Frame,
+	dsp::{flush_denormal, Frame},
 	manager::{
 		backend::mock::{MockBackend, MockBackendSettings},
 		AudioManager, AudioManagerSettings, Capacities,
 	},
 	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	track::{effect::reverb::ReverbBuilder, TrackBuilder},
 	LoopBehavior,
 };
 
@@ -28,6 +29,50 @@ fn create_test_sound(num_samples: usize) -> StaticSoundData {
 	}
 }
 
+/// Creates the bytes of a 16-bit stereo WAV file containing
+/// a sine wave.
+fn create_test_wav_file(num_frames: u32) -> Vec<u8> {
+	const SAMPLE_RATE: u32 = 48_000;
+	const NUM_CHANNELS: u16 = 2;
+	const BYTES_PER_SAMPLE: u16 = 2;
+	let block_align = NUM_CHANNELS * BYTES_PER_SAMPLE;
+	let data_size = num_frames * block_align as u32;
+	let mut bytes = Vec::with_capacity(44 + data_size as usize);
+	bytes.extend_from_slice(b"RIFF");
+	bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+	bytes.extend_from_slice(b"WAVE");
+	bytes.extend_from_slice(b"fmt ");
+	bytes.extend_from_slice(&16u32.to_le_bytes());
+	bytes.extend_from_slice(&1u16.to_le_bytes());
+	bytes.extend_from_slice(&NUM_CHANNELS.to_le_bytes());
+	bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+	bytes.extend_from_slice(&(SAMPLE_RATE * block_align as u32).to_le_bytes());
+	bytes.extend_from_slice(&block_align.to_le_bytes());
+	bytes.extend_from_slice(&(BYTES_PER_SAMPLE * 8).to_le_bytes());
+	bytes.extend_from_slice(b"data");
+	bytes.extend_from_slice(&data_size.to_le_bytes());
+	let mut phase = 0.0f32;
+	for _ in 0..num_frames {
+		let sample = ((phase * TAU).sin() * i16::MAX as f32) as i16;
+		for _ in 0..NUM_CHANNELS {
+			bytes.extend_from_slice(&sample.to_le_bytes());
+		}
+		phase += 440.0 / SAMPLE_RATE as f32;
+	}
+	bytes
+}
+
+fn loading(c: &mut Criterion) {
+	// loading a multi-minute file into memory
+	c.bench_function("load 3 minute wav file", |b| {
+		let bytes = create_test_wav_file(48_000 * 60 * 3);
+		b.iter(|| {
+			StaticSoundData::from_cursor(Cursor::new(bytes.clone()), StaticSoundSettings::new())
+				.unwrap()
+		});
+	});
+}
+
 fn sounds(c: &mut Criterion) {
 	// a simple test case where many sounds are being played at once
 	c.bench_function("simple", |b| {
@@ -87,5 +132,89 @@ fn sounds(c: &mut Criterion) {
 	});
 }
 
-criterion_group!(benches, sounds);
+fn effects(c: &mut Criterion) {
+	// a single short sound played through a long reverb. after
+	// the sound finishes, the reverb tail decays towards silence,
+	// which would produce subnormal floats if they weren't flushed
+	c.bench_function("decaying reverb tail", |b| {
+		const SAMPLE_RATE: u32 = 48_000;
+		let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+			main_track_builder: {
+				let mut builder = TrackBuilder::new();
+				builder.add_effect(ReverbBuilder::new().feedback(0.99).mix(1.0));
+				builder
+			},
+			backend_settings: MockBackendSettings {
+				sample_rate: SAMPLE_RATE,
+			},
+			..Default::default()
+		})
+		.unwrap();
+		let sound_data = create_test_sound(SAMPLE_RATE as usize / 10);
+		manager
+			.play(StaticSoundData {
+				settings: StaticSoundSettings::new(),
+				..sound_data
+			})
+			.unwrap();
+		manager.backend_mut().on_start_processing();
+		// let the sound finish and the reverb tail decay
+		for _ in 0..SAMPLE_RATE * 60 {
+			manager.backend_mut().process();
+		}
+		b.iter(|| manager.backend_mut().process());
+	});
+
+	// kira always flushes denormals, so to show the difference it
+	// makes, compare a comb filter like the one the reverb uses with
+	// and without flushing. the input is so quiet that the filter's
+	// state stays subnormal, just like the end of a reverb tail
+	let mut group = c.benchmark_group("comb filter with a subnormal tail");
+	group.bench_function("without flushing", |b| {
+		let mut comb = CombFilter::new();
+		b.iter(|| comb.process_block(|sample| sample))
+	});
+	group.bench_function("with flushing", |b| {
+		let mut comb = CombFilter::new();
+		b.iter(|| comb.process_block(flush_denormal))
+	});
+	group.finish();
+}
+
+/// A simplified version of the comb filter used by the reverb effect.
+struct CombFilter {
+	filter_store: f32,
+	buffer: Vec<f32>,
+	current_index: usize,
+}
+
+impl CombFilter {
+	const FEEDBACK: f32 = 0.84;
+	const DAMP: f32 = 0.25;
+	const INPUT: f32 = 1.0e-40;
+
+	fn new() -> Self {
+		Self {
+			filter_store: 0.0,
+			buffer: vec![0.0; 1116],
+			current_index: 0,
+		}
+	}
+
+	fn process_block(&mut self, flush: impl Fn(f32) -> f32) -> f32 {
+		let mut sum = 0.0;
+		for _ in 0..1024 {
+			let output = self.buffer[self.current_index];
+			self.filter_store = flush(output * (1.0 - Self::DAMP) + self.filter_store * Self::DAMP);
+			self.buffer[self.current_index] =
+				flush(black_box(Self::INPUT) + self.filter_store * Self::FEEDBACK);
+			self.current_index += 1;
+			self.current_index %= self.buffer.len();
+			sum += output;
+		}
+		sum
+	}
+}
+
+criterion_group!(benches, sounds, effects, loading);
 criterion_main!(benches);
@@ -2,12 +2,14 @@
 
 pub(crate) mod clocks;
 mod handle;
+mod sync;
 mod time;
 
 #[cfg(test)]
 mod test;
 
 pub use handle::*;
+pub use sync::*;
 pub use time::*;
 
 use std::sync::{
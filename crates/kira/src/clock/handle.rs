@@ -25,6 +25,14 @@ impl ClockHandle {
 		self.id
 	}
 
+	/// Returns `false` if the clock has been marked for removal.
+	///
+	/// This is only advisory: the clock may be marked for removal
+	/// right after this returns `true`.
+	pub fn is_alive(&self) -> bool {
+		!self.shared.is_marked_for_removal()
+	}
+
 	/// Returns `true` if the clock is currently ticking
 	/// and `false` if not.
 	pub fn ticking(&self) -> bool {
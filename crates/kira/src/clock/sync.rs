@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod test;
+
+use super::{ClockId, ClockTime};
+
+/// Tracks how far a clock has progressed between ticks, which
+/// lets effects and other code on the audio thread move in time
+/// with a clock.
+///
+/// To use a [`ClockSync`] in an [`Effect`](crate::track::effect::Effect),
+/// pass every clock tick from [`Effect::on_clock_tick`](crate::track::effect::Effect::on_clock_tick)
+/// to [`on_clock_tick`](Self::on_clock_tick), call [`update`](Self::update)
+/// with the `dt` passed to [`Effect::process`](crate::track::effect::Effect::process),
+/// and read the [`phase`](Self::phase) to drive the modulation.
+///
+/// Effects aren't told how fast a clock is ticking, so the time
+/// between ticks is measured from the last two ticks. Until the
+/// clock has ticked twice, the phase only moves forward when the
+/// clock ticks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClockSync {
+	clock: ClockId,
+	ticks_per_cycle: u64,
+	last_tick: Option<u64>,
+	time_since_tick: f64,
+	tick_duration: Option<f64>,
+}
+
+impl ClockSync {
+	/// Creates a new [`ClockSync`] that follows the given clock,
+	/// with one cycle per tick.
+	pub fn new(clock: ClockId) -> Self {
+		Self {
+			clock,
+			ticks_per_cycle: 1,
+			last_tick: None,
+			time_since_tick: 0.0,
+			tick_duration: None,
+		}
+	}
+
+	/// Sets how many ticks of the clock make up one cycle.
+	///
+	/// For example, with a clock that ticks every sixteenth note,
+	/// a value of `4` makes the phase go from `0.0` to `1.0` once
+	/// per beat.
+	pub fn ticks_per_cycle(self, ticks_per_cycle: u64) -> Self {
+		Self {
+			ticks_per_cycle: ticks_per_cycle.max(1),
+			..self
+		}
+	}
+
+	/// Returns the clock this [`ClockSync`] follows.
+	pub fn clock(&self) -> ClockId {
+		self.clock
+	}
+
+	/// Returns how far through the current cycle the clock is,
+	/// from `0.0` to `1.0`.
+	///
+	/// The phase is `0.0` on the ticks that start each cycle and
+	/// moves smoothly towards `1.0` between ticks. If the clock
+	/// slows down or stops, the phase waits at the next tick
+	/// instead of moving past it.
+	pub fn phase(&self) -> f64 {
+		let last_tick = match self.last_tick {
+			Some(last_tick) => last_tick,
+			None => return 0.0,
+		};
+		let tick_fraction = match self.tick_duration {
+			Some(tick_duration) if tick_duration > 0.0 => {
+				(self.time_since_tick / tick_duration).min(1.0)
+			}
+			_ => 0.0,
+		};
+		((last_tick % self.ticks_per_cycle) as f64 + tick_fraction) / self.ticks_per_cycle as f64
+	}
+
+	/// Advances the time since the last tick by `dt` seconds.
+	pub fn update(&mut self, dt: f64) {
+		self.time_since_tick += dt;
+	}
+
+	/// Called whenever a clock ticks. Ticks from other clocks
+	/// are ignored.
+	pub fn on_clock_tick(&mut self, time: ClockTime) {
+		if time.clock != self.clock {
+			return;
+		}
+		if let Some(last_tick) = self.last_tick {
+			// the clock can be reset to an earlier time, in which
+			// case the time between ticks can't be measured
+			if time.ticks > last_tick {
+				self.tick_duration = Some(self.time_since_tick / (time.ticks - last_tick) as f64);
+			}
+		}
+		self.last_tick = Some(time.ticks);
+		self.time_since_tick = 0.0;
+	}
+}
@@ -0,0 +1,72 @@
+use atomic_arena::Arena;
+
+use crate::clock::{ClockId, ClockTime};
+
+use super::ClockSync;
+
+/// Tests that the phase of a `ClockSync` moves smoothly between
+/// ticks and resets at each tick.
+#[test]
+#[allow(clippy::float_cmp)]
+fn phase_resets_at_each_tick() {
+	let mut dummy_arena = Arena::new(1);
+	let clock = ClockId(dummy_arena.insert(()).unwrap());
+	let mut sync = ClockSync::new(clock);
+	assert_eq!(sync.phase(), 0.0);
+	sync.on_clock_tick(ClockTime { clock, ticks: 0 });
+	sync.update(1.0);
+	sync.on_clock_tick(ClockTime { clock, ticks: 1 });
+	for tick in 2..5 {
+		assert_eq!(sync.phase(), 0.0);
+		for i in 1..4 {
+			sync.update(0.25);
+			assert_eq!(sync.phase(), i as f64 * 0.25);
+		}
+		sync.update(0.25);
+		sync.on_clock_tick(ClockTime { clock, ticks: tick });
+	}
+	assert_eq!(sync.phase(), 0.0);
+	// if the clock stops, the phase waits at the next tick
+	sync.update(10.0);
+	assert_eq!(sync.phase(), 1.0);
+}
+
+/// Tests that a `ClockSync` with multiple ticks per cycle
+/// spreads its phase across those ticks.
+#[test]
+#[allow(clippy::float_cmp)]
+fn multiple_ticks_per_cycle() {
+	let mut dummy_arena = Arena::new(1);
+	let clock = ClockId(dummy_arena.insert(()).unwrap());
+	let mut sync = ClockSync::new(clock).ticks_per_cycle(4);
+	for ticks in 0..8 {
+		sync.on_clock_tick(ClockTime { clock, ticks });
+		if ticks > 0 {
+			assert_eq!(sync.phase(), (ticks % 4) as f64 / 4.0);
+			sync.update(0.5);
+			assert_eq!(sync.phase(), (ticks % 4) as f64 / 4.0 + 0.125);
+			sync.update(0.5);
+		} else {
+			sync.update(1.0);
+		}
+	}
+}
+
+/// Tests that a `ClockSync` ignores ticks from other clocks.
+#[test]
+#[allow(clippy::float_cmp)]
+fn ignores_other_clocks() {
+	let mut dummy_arena = Arena::new(2);
+	let clock = ClockId(dummy_arena.insert(()).unwrap());
+	let other_clock = ClockId(dummy_arena.insert(()).unwrap());
+	let mut sync = ClockSync::new(clock);
+	sync.on_clock_tick(ClockTime { clock, ticks: 0 });
+	sync.update(1.0);
+	sync.on_clock_tick(ClockTime { clock, ticks: 1 });
+	sync.update(0.5);
+	sync.on_clock_tick(ClockTime {
+		clock: other_clock,
+		ticks: 2,
+	});
+	assert_eq!(sync.phase(), 0.5);
+}
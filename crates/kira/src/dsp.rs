@@ -1,12 +1,32 @@
 //! Tools for manipulating audio signals.
 
+mod dc_blocker;
+mod dither;
 mod frame;
+mod lfo;
+mod muffle;
+mod panning_law;
+mod random;
+mod resampler;
 
+pub(crate) use dc_blocker::DcBlocker;
+pub use dither::*;
 pub use frame::*;
+pub use lfo::LfoShape;
+pub(crate) use lfo::{Lfo, LfoSettings};
+pub(crate) use muffle::Muffle;
+pub use panning_law::*;
+pub use random::*;
+pub use resampler::*;
 
 /// Given a previous frame, a current frame, the two next frames,
 /// and a position `x` from 0.0 to 1.0 between the current frame
 /// and next frame, get an approximated frame.
+///
+/// This is the interpolation used to play sounds with
+/// [`ResampleQuality::Cubic`](crate::sound::static_sound::ResampleQuality::Cubic).
+/// To interpolate a stream of frames without keeping track of the
+/// surrounding frames yourself, use a [`Resampler`].
 // This is the 4-point, 3rd-order Hermite interpolation x-form
 // algorithm from "Polynomial Interpolators for High-Quality
 // Resampling of Oversampled Audio" by Olli Niemitalo, p. 43:
@@ -0,0 +1,43 @@
+use std::f64::consts::TAU;
+
+use super::Frame;
+
+/// The cutoff frequency of the DC blocker (in hertz).
+const CUTOFF: f64 = 5.0;
+
+/// A one-pole high-pass filter that removes DC offset
+/// from a signal.
+pub(crate) struct DcBlocker {
+	coefficient: f32,
+	previous_input: Frame,
+	previous_output: Frame,
+}
+
+impl DcBlocker {
+	pub fn new(sample_rate: u32) -> Self {
+		Self {
+			coefficient: Self::coefficient(sample_rate),
+			previous_input: Frame::ZERO,
+			previous_output: Frame::ZERO,
+		}
+	}
+
+	fn coefficient(sample_rate: u32) -> f32 {
+		(-TAU * CUTOFF / sample_rate as f64).exp() as f32
+	}
+
+	pub fn on_change_sample_rate(&mut self, sample_rate: u32) {
+		self.coefficient = Self::coefficient(sample_rate);
+	}
+
+	pub fn process(&mut self, input: Frame) -> Frame {
+		let output = (input - self.previous_input + self.previous_output * self.coefficient)
+			.flush_denormals();
+		// the output decays toward zero after the input goes
+		// silent, so flush denormals to keep it from slowing
+		// down processing
+		self.previous_input = input.flush_denormals();
+		self.previous_output = output;
+		output
+	}
+}
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod test;
+
+use super::Random;
+
+/// Noise that can be added to audio when converting it to a
+/// lower bit depth to hide quantization distortion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Dither {
+	/// Samples are rounded to the nearest value without
+	/// adding any noise.
+	///
+	/// This adds distortion that follows the signal, which
+	/// is most noticeable on quiet passages.
+	#[default]
+	None,
+	/// Noise with a triangular probability distribution
+	/// spanning two quantization steps is added before rounding.
+	///
+	/// This replaces quantization distortion with a constant,
+	/// very quiet noise floor.
+	Triangular,
+}
+
+impl Dither {
+	/// Converts a sample from `-1.0` to `1.0` to a 16-bit integer sample.
+	///
+	/// Samples are scaled by 32768, the inverse of how 16-bit
+	/// samples are loaded, so `-1.0` maps to [`i16::MIN`]. Samples
+	/// that end up outside of the 16-bit range, including `1.0`,
+	/// will be clipped.
+	pub fn f32_to_i16(self, sample: f32, random: &mut Random) -> i16 {
+		let mut scaled = sample * 32768.0;
+		if let Dither::Triangular = self {
+			// the sum of two uniform random values has a
+			// triangular distribution
+			scaled += (random.next_f32() + random.next_f32()) / 2.0;
+		}
+		scaled.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16
+	}
+}
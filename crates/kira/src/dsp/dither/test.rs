@@ -0,0 +1,50 @@
+use crate::dsp::Random;
+
+use super::Dither;
+
+/// Tests that samples are scaled and clipped to the 16-bit range.
+#[test]
+fn converts_samples() {
+	let mut random = Random::new(1);
+	assert_eq!(Dither::None.f32_to_i16(0.0, &mut random), 0);
+	assert_eq!(Dither::None.f32_to_i16(0.5, &mut random), 16384);
+	assert_eq!(Dither::None.f32_to_i16(-0.5, &mut random), -16384);
+	assert_eq!(Dither::None.f32_to_i16(1.0, &mut random), i16::MAX);
+	assert_eq!(Dither::None.f32_to_i16(-1.0, &mut random), i16::MIN);
+	assert_eq!(Dither::None.f32_to_i16(2.0, &mut random), i16::MAX);
+	assert_eq!(Dither::None.f32_to_i16(-2.0, &mut random), i16::MIN);
+	// 16-bit samples survive being loaded and converted back
+	for sample in [i16::MIN, -1, 0, 1, i16::MAX] {
+		assert_eq!(
+			Dither::None.f32_to_i16(sample as f32 / 32768.0, &mut random),
+			sample
+		);
+	}
+}
+
+/// Tests that triangular dithering removes the bias in the
+/// quantization error for signals quieter than one step.
+#[test]
+fn triangular_dither_removes_quantization_bias() {
+	const NUM_SAMPLES: usize = 10_000;
+	let mut random = Random::new(1);
+	// a constant signal of 0.3 quantization steps
+	let sample = 0.3 / 32768.0;
+
+	// without dithering, the signal is lost completely, so the
+	// error is always the same
+	let undithered: Vec<i16> = (0..NUM_SAMPLES)
+		.map(|_| Dither::None.f32_to_i16(sample, &mut random))
+		.collect();
+	assert!(undithered.iter().all(|sample| *sample == 0));
+
+	// with dithering, the error is spread out as noise, and the
+	// signal is preserved on average
+	let dithered: Vec<i16> = (0..NUM_SAMPLES)
+		.map(|_| Dither::Triangular.f32_to_i16(sample, &mut random))
+		.collect();
+	let mean = dithered.iter().map(|sample| *sample as f64).sum::<f64>() / NUM_SAMPLES as f64;
+	assert!((mean - 0.3).abs() < 0.05);
+	assert!(dithered.iter().any(|sample| *sample != 0));
+	assert!(dithered.iter().all(|sample| (-1..=2).contains(sample)));
+}
@@ -1,8 +1,13 @@
+#[cfg(test)]
+mod test;
+
 use std::{
 	f32::consts::SQRT_2,
 	ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
+use super::PanningLaw;
+
 /// A stereo audio sample.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Frame {
@@ -45,6 +50,77 @@ impl Frame {
 		}
 		Self::new(self.left * (1.0 - x).sqrt(), self.right * x.sqrt()) * SQRT_2
 	}
+
+	/// Pans a frame to the left or right using the given
+	/// [`PanningLaw`].
+	///
+	/// An `x` of 0 represents a hard left panning, an `x` of 1
+	/// represents a hard right panning. [`panned`](Self::panned)
+	/// is equivalent to using [`PanningLaw::ConstantPower`].
+	pub fn panned_with_law(self, x: f32, law: PanningLaw) -> Self {
+		if x == 0.5 {
+			return self;
+		}
+		let (left, right) = law.gains(x);
+		Self::new(self.left * left, self.right * right)
+	}
+
+	/// Replaces subnormal (denormal) samples with `0.0`.
+	///
+	/// Decaying signals in feedback loops (like reverb tails)
+	/// eventually become so quiet that they're represented by
+	/// subnormal floats, which are very slow to process on many
+	/// CPUs. Since subnormal samples are far below audible levels,
+	/// they can safely be replaced with silence.
+	pub fn flush_denormals(self) -> Self {
+		Self::new(flush_denormal(self.left), flush_denormal(self.right))
+	}
+
+	/// Returns `true` if neither sample is NaN or infinite.
+	pub fn is_finite(self) -> bool {
+		self.left.is_finite() && self.right.is_finite()
+	}
+
+	/// Returns a frame with the absolute value of each sample.
+	pub fn abs(self) -> Self {
+		Self::new(self.left.abs(), self.right.abs())
+	}
+
+	/// Restricts each sample to be between `min` and `max`.
+	pub fn clamp(self, min: f32, max: f32) -> Self {
+		Self::new(self.left.clamp(min, max), self.right.clamp(min, max))
+	}
+
+	/// Converts interleaved stereo samples (left, right, left, right...)
+	/// into frames.
+	///
+	/// If the slice has an odd number of samples, the last
+	/// sample is ignored.
+	pub fn from_stereo_slice(samples: &[f32]) -> Vec<Self> {
+		samples
+			.chunks_exact(2)
+			.map(|samples| Self::new(samples[0], samples[1]))
+			.collect()
+	}
+
+	/// Converts frames into interleaved stereo samples
+	/// (left, right, left, right...).
+	pub fn to_interleaved(frames: &[Self]) -> Vec<f32> {
+		frames
+			.iter()
+			.flat_map(|frame| [frame.left, frame.right])
+			.collect()
+	}
+}
+
+/// Returns `0.0` if the sample is subnormal (denormal),
+/// or the sample itself otherwise.
+pub fn flush_denormal(sample: f32) -> f32 {
+	if sample.is_subnormal() {
+		0.0
+	} else {
+		sample
+	}
 }
 
 impl Add for Frame {
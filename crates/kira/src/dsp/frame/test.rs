@@ -0,0 +1,50 @@
+use super::{Frame, PanningLaw};
+
+#[test]
+fn abs() {
+	assert_eq!(Frame::new(-0.5, 0.25).abs(), Frame::new(0.5, 0.25));
+}
+
+#[test]
+fn clamp() {
+	assert_eq!(
+		Frame::new(-2.0, 0.5).clamp(-1.0, 1.0),
+		Frame::new(-1.0, 0.5)
+	);
+	assert_eq!(Frame::new(0.5, 3.0).clamp(-1.0, 1.0), Frame::new(0.5, 1.0));
+}
+
+#[test]
+fn interleaving_round_trip() {
+	let frames = vec![
+		Frame::new(0.1, 0.2),
+		Frame::new(-0.3, 0.4),
+		Frame::new(0.5, -0.6),
+	];
+	let interleaved = Frame::to_interleaved(&frames);
+	assert_eq!(interleaved, vec![0.1, 0.2, -0.3, 0.4, 0.5, -0.6]);
+	assert_eq!(Frame::from_stereo_slice(&interleaved), frames);
+}
+
+#[test]
+fn from_stereo_slice_ignores_unpaired_sample() {
+	assert_eq!(
+		Frame::from_stereo_slice(&[0.1, 0.2, 0.3]),
+		vec![Frame::new(0.1, 0.2)]
+	);
+}
+
+#[test]
+fn panned_with_law() {
+	let frame = Frame::from_mono(1.0);
+	for x in [0.0, 0.25, 0.5, 0.75, 1.0] {
+		let a = frame.panned(x);
+		let b = frame.panned_with_law(x, PanningLaw::ConstantPower);
+		assert!((a.left - b.left).abs() < 1.0e-6);
+		assert!((a.right - b.right).abs() < 1.0e-6);
+	}
+	assert_eq!(
+		frame.panned_with_law(0.25, PanningLaw::Linear),
+		Frame::new(1.5, 0.5)
+	);
+}
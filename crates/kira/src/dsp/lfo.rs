@@ -0,0 +1,87 @@
+use std::f64::consts::TAU;
+
+/// The shape of a low frequency oscillator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LfoShape {
+	/// A smooth sine wave.
+	Sine,
+	/// A wave that rises and falls linearly.
+	Triangle,
+	/// A wave that alternates between its highest and lowest
+	/// values.
+	Square,
+}
+
+/// The rate, depth and shape of an LFO that modulates
+/// a parameter.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct LfoSettings {
+	/// The frequency of the oscillator (in hertz).
+	pub rate: f64,
+	/// How far the oscillator moves the parameter from
+	/// its base value.
+	pub depth: f64,
+	/// The shape of the oscillator.
+	pub shape: LfoShape,
+}
+
+impl LfoSettings {
+	/// Returns the amount to add to the parameter's base value.
+	pub fn offset(&self, lfo: &Lfo) -> f64 {
+		self.depth * lfo.value(self.shape)
+	}
+}
+
+/// A phase-continuous low frequency oscillator.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct Lfo {
+	/// The current phase of the oscillator (from 0.0 to 1.0).
+	phase: f64,
+}
+
+impl Lfo {
+	pub fn new() -> Self {
+		Self { phase: 0.0 }
+	}
+
+	/// Returns the current value of the oscillator in the
+	/// -1.0 to 1.0 range.
+	pub fn value(&self, shape: LfoShape) -> f64 {
+		match shape {
+			LfoShape::Sine => (self.phase * TAU).sin(),
+			LfoShape::Triangle => {
+				if self.phase < 0.25 {
+					self.phase * 4.0
+				} else if self.phase < 0.75 {
+					2.0 - self.phase * 4.0
+				} else {
+					self.phase * 4.0 - 4.0
+				}
+			}
+			LfoShape::Square => {
+				if self.phase < 0.5 {
+					1.0
+				} else {
+					-1.0
+				}
+			}
+		}
+	}
+
+	/// Advances the oscillator by `dt` seconds at the given
+	/// frequency (in hertz).
+	pub fn update(&mut self, frequency: f64, dt: f64) {
+		self.phase += frequency * dt;
+		self.phase = self.phase.rem_euclid(1.0);
+	}
+
+	/// Sets the phase of the oscillator (from 0.0 to 1.0).
+	pub fn set_phase(&mut self, phase: f64) {
+		self.phase = phase.rem_euclid(1.0);
+	}
+
+	pub fn reset(&mut self) {
+		self.phase = 0.0;
+	}
+}
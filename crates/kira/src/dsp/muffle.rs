@@ -0,0 +1,68 @@
+use std::f64::consts::TAU;
+
+use crate::{
+	clock::ClockTime,
+	tween::{Tween, Tweener},
+};
+
+use super::Frame;
+
+/// The cutoff frequency of the low-pass filter when the
+/// muffle amount is 0.0 (in hertz).
+const MAX_CUTOFF: f64 = 20_000.0;
+/// The cutoff frequency of the low-pass filter when the
+/// muffle amount is 1.0 (in hertz).
+const MIN_CUTOFF: f64 = 300.0;
+/// How much the volume is reduced when the muffle amount
+/// is 1.0 (in decibels).
+const MAX_VOLUME_REDUCTION: f64 = 6.0;
+
+/// A low-pass filter and volume duck controlled by a single
+/// amount from 0.0 (no effect) to 1.0 (fully muffled).
+pub(crate) struct Muffle {
+	amount: Tweener,
+	previous_output: Frame,
+}
+
+impl Muffle {
+	pub fn new() -> Self {
+		Self {
+			amount: Tweener::new(0.0),
+			previous_output: Frame::ZERO,
+		}
+	}
+
+	pub fn set_amount(&mut self, amount: f64, tween: Tween) {
+		// a NaN amount would poison the filter state, so
+		// treat it as no muffling
+		let amount = if amount.is_nan() {
+			0.0
+		} else {
+			amount.clamp(0.0, 1.0)
+		};
+		self.amount.set(amount, tween);
+	}
+
+	pub fn on_clock_tick(&mut self, time: ClockTime) {
+		self.amount.on_clock_tick(time);
+	}
+
+	pub fn process(&mut self, input: Frame, dt: f64) -> Frame {
+		self.amount.update(dt);
+		let amount = self.amount.value();
+		if amount <= 0.0 {
+			// keep the filter state in sync with the input so
+			// turning the muffle on doesn't cause a click
+			self.previous_output = input;
+			return input;
+		}
+		// interpolate the cutoff logarithmically so the amount
+		// sounds roughly linear
+		let cutoff = MAX_CUTOFF * (MIN_CUTOFF / MAX_CUTOFF).powf(amount);
+		let coefficient = (1.0 - (-TAU * cutoff * dt).exp()) as f32;
+		let output = self.previous_output + (input - self.previous_output) * coefficient;
+		self.previous_output = output;
+		let gain = 10.0f64.powf(-MAX_VOLUME_REDUCTION * amount / 20.0) as f32;
+		output * gain
+	}
+}
@@ -0,0 +1,39 @@
+/// How the volume of the left and right channels changes
+/// as audio is panned.
+///
+/// All panning laws leave centered audio unchanged. They differ
+/// in how loud audio gets as it's panned to one side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PanningLaw {
+	/// The volume of each channel changes linearly, so each
+	/// channel is 6dB quieter at the center than when panned
+	/// hard to that side.
+	///
+	/// Audio panned to the center will sound quieter
+	/// than audio panned hard left or right.
+	Linear,
+	/// The volume of each channel follows a square root curve,
+	/// so each channel is 3dB quieter at the center than when
+	/// panned hard to that side, and the total power stays the
+	/// same at any panning.
+	///
+	/// This is the panning law used by [`Frame::panned`](super::Frame::panned).
+	ConstantPower,
+	/// A compromise between the linear and constant power
+	/// panning laws, where each channel is 4.5dB quieter at
+	/// the center than when panned hard to that side.
+	Compromise,
+}
+
+impl PanningLaw {
+	/// Returns the amplitudes of the left and right channels
+	/// for a panning value.
+	pub(crate) fn gains(self, x: f32) -> (f32, f32) {
+		match self {
+			PanningLaw::Linear => (2.0 * (1.0 - x), 2.0 * x),
+			PanningLaw::ConstantPower => ((2.0 * (1.0 - x)).sqrt(), (2.0 * x).sqrt()),
+			PanningLaw::Compromise => ((2.0 * (1.0 - x)).powf(0.75), (2.0 * x).powf(0.75)),
+		}
+	}
+}
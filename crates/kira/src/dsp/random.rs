@@ -0,0 +1,70 @@
+use std::{
+	collections::hash_map::RandomState,
+	hash::{BuildHasher, Hasher},
+};
+
+/// A small, fast pseudo-random number generator for use
+/// on the audio thread.
+///
+/// Two [`Random`]s created with the same seed will always
+/// produce the same sequence of numbers, which makes effects
+/// that use randomness (like noise or dithering) reproducible.
+// This is the xorshift64* algorithm from "An experimental
+// exploration of Marsaglia's xorshift generators, scrambled"
+// by Sebastiano Vigna: https://arxiv.org/abs/1402.6246
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Random {
+	state: u64,
+}
+
+impl Random {
+	/// Creates a new [`Random`] with the given seed.
+	pub fn new(seed: u64) -> Self {
+		Self {
+			// xorshift gets stuck on a state of 0, so a seed
+			// of 0 is mapped to a different (arbitrary) state
+			state: if seed == 0 {
+				0x9e37_79b9_7f4a_7c15
+			} else {
+				seed
+			},
+		}
+	}
+
+	/// Creates a new [`Random`] with a seed chosen by the
+	/// operating system.
+	pub fn from_entropy() -> Self {
+		Self::new(RandomState::new().build_hasher().finish())
+	}
+
+	/// Returns a random `u64`.
+	pub fn next_u64(&mut self) -> u64 {
+		self.state ^= self.state >> 12;
+		self.state ^= self.state << 25;
+		self.state ^= self.state >> 27;
+		self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+	}
+
+	/// Returns a random `f64` from `0.0` (inclusive)
+	/// to `1.0` (exclusive).
+	pub fn next_f64(&mut self) -> f64 {
+		// the upper 53 bits fit exactly in an f64's mantissa
+		(self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+	}
+
+	/// Returns a random `f64` from `min` (inclusive)
+	/// to `max` (exclusive).
+	pub fn range(&mut self, min: f64, max: f64) -> f64 {
+		min + (max - min) * self.next_f64()
+	}
+
+	/// Returns a random `f32` from `-1.0` (inclusive)
+	/// to `1.0` (exclusive).
+	///
+	/// This is useful for generating white noise.
+	pub fn next_f32(&mut self) -> f32 {
+		// the upper 24 bits fit exactly in an f32's mantissa
+		let fraction = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+		fraction * 2.0 - 1.0
+	}
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod test;
+
+use super::{interpolate_frame, Frame};
+
+/// Resamples a stream of frames using 4-point cubic interpolation.
+///
+/// The resampler keeps track of the last four frames pushed to it.
+/// [`get`](Resampler::get) interpolates between the second oldest
+/// frame (the "current" frame) and the frame after it, so the output
+/// lags two frames behind the input. To make the first frame of a
+/// signal the current frame, push the first three frames before
+/// reading any output.
+///
+/// This is the same interpolation Kira uses to play sounds, so audio
+/// resampled offline will sound the same as audio resampled
+/// during playback.
+///
+/// # Example
+///
+/// Playing a short buffer at half speed:
+///
+/// ```
+/// use kira::dsp::{Frame, Resampler};
+///
+/// let input: Vec<Frame> = (0..8).map(|i| Frame::from_mono(i as f32)).collect();
+/// let mut input_frames = input.iter().copied().chain(std::iter::repeat(Frame::ZERO));
+/// let mut resampler = Resampler::new();
+/// for _ in 0..3 {
+/// 	resampler.push_frame(input_frames.next().unwrap());
+/// }
+/// let mut output = vec![];
+/// let mut fraction = 0.0;
+/// for _ in 0..input.len() * 2 {
+/// 	output.push(resampler.get(fraction));
+/// 	fraction += 0.5;
+/// 	while fraction >= 1.0 {
+/// 		fraction -= 1.0;
+/// 		resampler.push_frame(input_frames.next().unwrap());
+/// 	}
+/// }
+/// assert_eq!(output[2], input[1]);
+/// assert_eq!(output[3], Frame::from_mono(1.5));
+/// assert_eq!(output[4], input[2]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Resampler {
+	/// The previous, current, and next two frames.
+	frames: [Frame; 4],
+}
+
+impl Resampler {
+	/// Creates a new [`Resampler`] with a history of silent frames.
+	pub fn new() -> Self {
+		Self {
+			frames: [Frame::ZERO; 4],
+		}
+	}
+
+	/// Adds a frame to the end of the resampler's history,
+	/// discarding the oldest frame.
+	pub fn push_frame(&mut self, frame: Frame) {
+		self.frames.rotate_left(1);
+		self.frames[3] = frame;
+	}
+
+	/// Returns an interpolated frame at a position between the
+	/// current frame (`fraction = 0.0`) and the next frame
+	/// (`fraction = 1.0`).
+	pub fn get(&self, fraction: f32) -> Frame {
+		interpolate_frame(
+			self.frames[0],
+			self.frames[1],
+			self.frames[2],
+			self.frames[3],
+			fraction,
+		)
+	}
+}
+
+impl Default for Resampler {
+	fn default() -> Self {
+		Self::new()
+	}
+}
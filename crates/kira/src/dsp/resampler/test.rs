@@ -0,0 +1,21 @@
+use crate::dsp::Frame;
+
+use super::Resampler;
+
+/// Tests that the resampler reproduces a linear ramp exactly
+/// at any fractional position.
+#[test]
+#[allow(clippy::float_cmp)]
+fn linear_ramp() {
+	let mut resampler = Resampler::new();
+	for i in 0..4 {
+		resampler.push_frame(Frame::from_mono(i as f32));
+	}
+	// the current frame is the second oldest frame
+	assert_eq!(resampler.get(0.0), Frame::from_mono(1.0));
+	assert_eq!(resampler.get(0.25), Frame::from_mono(1.25));
+	assert_eq!(resampler.get(0.5), Frame::from_mono(1.5));
+	assert_eq!(resampler.get(1.0), Frame::from_mono(2.0));
+	resampler.push_frame(Frame::from_mono(4.0));
+	assert_eq!(resampler.get(0.5), Frame::from_mono(2.5));
+}
@@ -11,6 +11,12 @@ pub enum CommandError {
 	CommandQueueFull,
 	/// Could not add a sound because a thread panicked while using the command queue.
 	MutexPoisoned,
+	/// Could not send a command because the resource it controls
+	/// has been removed.
+	///
+	/// Unlike [`CommandQueueFull`](Self::CommandQueueFull), retrying
+	/// the command will never succeed.
+	ResourceRemoved,
 }
 
 impl Display for CommandError {
@@ -22,6 +28,9 @@ impl Display for CommandError {
 			CommandError::MutexPoisoned => {
 				"Could not add a sound because a thread panicked while using the command queue."
 			}
+			CommandError::ResourceRemoved => {
+				"Could not send a command because the resource it controls has been removed."
+			}
 		})
 	}
 }
@@ -9,6 +9,13 @@ audio events.
 
 The [book](https://tesselode.github.io/kira/) has tutorials
 on how to use Kira.
+
+## Platform support
+
+Kira requires the standard library. The [`AudioManager`](manager::AudioManager)
+and the cpal backend use threads and atomics from `std`, and streaming sounds
+decode audio on a separate thread. The audio file loaders (the `mp3`, `ogg`,
+`flac`, and `wav` features) read from files and use `std::io`.
 */
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -26,6 +33,7 @@ pub mod dsp;
 mod error;
 mod loop_behavior;
 pub mod manager;
+pub mod modulator;
 mod playback_rate;
 pub mod sound;
 mod start_time;
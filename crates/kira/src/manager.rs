@@ -3,21 +3,28 @@
 pub mod backend;
 pub(crate) mod command;
 pub mod error;
+mod routing_graph;
 mod settings;
 
+#[cfg(test)]
+mod test;
+
+pub use routing_graph::*;
 pub use settings::*;
 
-use std::{collections::HashSet, sync::Arc};
+use std::{sync::Arc, time::Duration};
 
+use atomic_arena::Arena;
 use ringbuf::RingBuffer;
 
 use crate::{
 	clock::{Clock, ClockHandle, ClockId},
 	error::CommandError,
+	modulator::{ModulatorBuilder, ModulatorId},
 	sound::SoundData,
-	track::{SubTrackId, Track, TrackBuilder, TrackHandle, TrackId},
+	track::{GroupHandle, SubTrackId, SubTrackRegistry, Track, TrackBuilder, TrackHandle, TrackId},
 	tween::Tween,
-	ClockSpeed,
+	ClockSpeed, Volume,
 };
 
 use self::{
@@ -28,8 +35,13 @@ use self::{
 		},
 		Backend, DefaultBackend, Renderer, RendererShared,
 	},
-	command::{producer::CommandProducer, ClockCommand, Command, MixerCommand, SoundCommand},
-	error::{AddClockError, AddSubTrackError, PlaySoundError},
+	command::{
+		producer::CommandProducer, ClockCommand, Command, MixerCommand, ModulatorCommand,
+		SoundCommand,
+	},
+	error::{
+		AddClockError, AddModulatorError, AddSubTrackError, PlaySoundError, ReserveSoundsError,
+	},
 };
 
 /// The playback state for all audio.
@@ -56,6 +68,10 @@ impl MainPlaybackState {
 	}
 }
 
+/// How much longer than the shutdown fade to keep the
+/// audio output running for the audio thread to finish it.
+const SHUTDOWN_FADE_MARGIN: Duration = Duration::from_millis(20);
+
 /// Controls audio from gameplay code.
 pub struct AudioManager<B: Backend = DefaultBackend> {
 	backend: B,
@@ -63,6 +79,10 @@ pub struct AudioManager<B: Backend = DefaultBackend> {
 	command_producer: CommandProducer,
 	resource_controllers: ResourceControllers,
 	unused_resource_consumers: UnusedResourceConsumers,
+	start_stop_fade_duration: Duration,
+	/// The sub-tracks that haven't been removed, for building
+	/// routing snapshots and checking new routes.
+	sub_tracks: SubTrackRegistry,
 }
 
 impl<B: Backend> AudioManager<B> {
@@ -76,10 +96,17 @@ impl<B: Backend> AudioManager<B> {
 		let (resources, resource_controllers) = create_resources(
 			settings.capacities,
 			settings.main_track_builder,
+			settings.random_seed,
 			unused_resource_producers,
 			sample_rate,
 		);
-		let renderer = Renderer::new(sample_rate, resources, command_consumer);
+		let renderer = Renderer::new(
+			sample_rate,
+			resources,
+			command_consumer,
+			settings.start_stop_fade_duration,
+			settings.dc_blocker,
+		);
 		let renderer_shared = renderer.shared();
 		backend.start(renderer)?;
 		Ok(Self {
@@ -88,15 +115,35 @@ impl<B: Backend> AudioManager<B> {
 			command_producer: CommandProducer::new(command_producer),
 			resource_controllers,
 			unused_resource_consumers,
+			start_stop_fade_duration: settings.start_stop_fade_duration,
+			sub_tracks: SubTrackRegistry::default(),
 		})
 	}
 
 	/// Plays a sound.
+	///
+	/// Dropping the returned handle does not stop the sound.
+	/// The sound keeps playing until it finishes, and then
+	/// its resources are reclaimed.
 	pub fn play<D: SoundData>(
 		&mut self,
 		sound_data: D,
 	) -> Result<D::Handle, PlaySoundError<D::Error>> {
 		while self.unused_resource_consumers.sound.pop().is_some() {}
+		self.remove_unused_sound_arenas();
+		// while sounds are being moved to a larger arena, they still
+		// need space in the new arena, so we can't give that space
+		// to new sounds yet
+		if let Some(previous_sound_controller) =
+			&self.resource_controllers.previous_sound_controller
+		{
+			if self.resource_controllers.sound_controller.len()
+				+ previous_sound_controller.capacity()
+				>= self.resource_controllers.sound_controller.capacity()
+			{
+				return Err(PlaySoundError::SoundLimitReached);
+			}
+		}
 		let key = self
 			.resource_controllers
 			.sound_controller
@@ -110,6 +157,53 @@ impl<B: Backend> AudioManager<B> {
 		Ok(handle)
 	}
 
+	/// Plays a sound without returning a handle to it.
+	///
+	/// This is useful for short "fire-and-forget" sounds, like
+	/// footsteps or impacts, that never need to be controlled after
+	/// they start. The sound is removed when it finishes playing.
+	pub fn play_detached<D: SoundData>(
+		&mut self,
+		sound_data: D,
+	) -> Result<(), PlaySoundError<D::Error>> {
+		self.play(sound_data).map(|_| ())
+	}
+
+	/// Makes sure there's space for at least `additional` more sounds
+	/// to play at the same time as the sounds that are currently playing.
+	///
+	/// This allocates a larger list of sounds on the current thread and
+	/// sends it to the audio thread, which moves the existing sounds into
+	/// it the next time it processes commands. Moving the sounds may cause
+	/// a one-time hitch in the audio. Some of the new space may not be
+	/// usable until the audio thread has finished moving the sounds.
+	///
+	/// Only one reservation can be in progress at a time. If there's
+	/// already enough space, this does nothing.
+	pub fn reserve_sounds(&mut self, additional: usize) -> Result<(), ReserveSoundsError> {
+		self.remove_unused_sound_arenas();
+		if self
+			.resource_controllers
+			.previous_sound_controller
+			.is_some()
+		{
+			return Err(ReserveSoundsError::ReservationInProgress);
+		}
+		let capacity = self.num_sounds() + additional;
+		if capacity <= self.sound_capacity() {
+			return Ok(());
+		}
+		let arena = Arena::new(capacity);
+		let sound_controller = arena.controller();
+		self.command_producer
+			.push(Command::Sound(SoundCommand::Grow(arena)))?;
+		self.resource_controllers.previous_sound_controller = Some(std::mem::replace(
+			&mut self.resource_controllers.sound_controller,
+			sound_controller,
+		));
+		Ok(())
+	}
+
 	/// Creates a mixer sub-track.
 	pub fn add_sub_track(
 		&mut self,
@@ -122,21 +216,41 @@ impl<B: Backend> AudioManager<B> {
 				.try_reserve()
 				.map_err(|_| AddSubTrackError::SubTrackLimitReached)?,
 		);
-		let existing_routes = builder.routes.0.keys().copied().collect();
+		let effect_names = builder.effect_names.clone();
 		let sub_track = Track::new(builder);
 		let handle = TrackHandle {
 			id: TrackId::Sub(id),
-			shared: Some(sub_track.shared()),
+			shared: sub_track.shared(),
 			command_producer: self.command_producer.clone(),
-			existing_routes,
+			effect_names,
+			sub_tracks: self.sub_tracks.clone(),
 		};
+		let shared = sub_track.shared();
 		self.command_producer
 			.push(Command::Mixer(MixerCommand::AddSubTrack(id, sub_track)))?;
+		self.sub_tracks.add(id, shared, handle.effect_names.clone());
 		Ok(handle)
 	}
 
+	/// Creates a group for a category of sounds, like music
+	/// or sound effects, with the given initial volume.
+	///
+	/// Groups are mixer sub-tracks, so this fails if the maximum
+	/// number of sub-tracks has been reached.
+	pub fn add_group(
+		&mut self,
+		volume: impl Into<Volume>,
+	) -> Result<GroupHandle, AddSubTrackError> {
+		Ok(GroupHandle {
+			track: self.add_sub_track(TrackBuilder::new().volume(volume))?,
+		})
+	}
+
 	/// Creates a clock.
 	pub fn add_clock(&mut self, speed: ClockSpeed) -> Result<ClockHandle, AddClockError> {
+		if self.clock_capacity() == 0 {
+			return Err(AddClockError::ClocksDisabled);
+		}
 		while self.unused_resource_consumers.clock.pop().is_some() {}
 		let id = ClockId(
 			self.resource_controllers
@@ -155,6 +269,27 @@ impl<B: Backend> AudioManager<B> {
 		Ok(handle)
 	}
 
+	/// Creates a modulator.
+	pub fn add_modulator<Builder: ModulatorBuilder>(
+		&mut self,
+		builder: Builder,
+	) -> Result<Builder::Handle, AddModulatorError> {
+		if self.modulator_capacity() == 0 {
+			return Err(AddModulatorError::ModulatorsDisabled);
+		}
+		while self.unused_resource_consumers.modulator.pop().is_some() {}
+		let id = ModulatorId::new(
+			self.resource_controllers
+				.modulator_controller
+				.try_reserve()
+				.map_err(|_| AddModulatorError::ModulatorLimitReached)?,
+		);
+		let (modulator, handle) = builder.build(id.clone());
+		self.command_producer
+			.push(Command::Modulator(ModulatorCommand::Add(id, modulator)))?;
+		Ok(handle)
+	}
+
 	/// Fades out and pauses all audio.
 	pub fn pause(&mut self, fade_out_tween: Tween) -> Result<(), CommandError> {
 		self.command_producer.push(Command::Pause(fade_out_tween))
@@ -165,13 +300,27 @@ impl<B: Backend> AudioManager<B> {
 		self.command_producer.push(Command::Resume(fade_out_tween))
 	}
 
+	/// Muffles all audio, as if the listener were underwater or
+	/// stunned.
+	///
+	/// `amount` ranges from 0.0 (no muffling) to 1.0 (fully muffled).
+	/// Higher amounts apply a lower cutoff frequency to a low-pass
+	/// filter on the main output and reduce the volume slightly.
+	/// Amounts outside of this range are clamped, and `NaN` is
+	/// treated as 0.0.
+	pub fn set_muffle(&mut self, amount: f64, tween: Tween) -> Result<(), CommandError> {
+		self.command_producer
+			.push(Command::SetMuffle(amount, tween))
+	}
+
 	/// Returns a handle to the main mixer track.
 	pub fn main_track(&self) -> TrackHandle {
 		TrackHandle {
 			id: TrackId::Main,
-			shared: None,
+			shared: self.resource_controllers.main_track_shared.clone(),
 			command_producer: self.command_producer.clone(),
-			existing_routes: HashSet::new(),
+			effect_names: self.resource_controllers.main_track_effect_names.clone(),
+			sub_tracks: self.sub_tracks.clone(),
 		}
 	}
 
@@ -195,9 +344,19 @@ impl<B: Backend> AudioManager<B> {
 		self.resource_controllers.clock_controller.capacity()
 	}
 
+	/// Returns the number of modulators that can exist at a time.
+	pub fn modulator_capacity(&self) -> usize {
+		self.resource_controllers.modulator_controller.capacity()
+	}
+
 	/// Returns the number of sounds that are currently loaded.
 	pub fn num_sounds(&self) -> usize {
 		self.resource_controllers.sound_controller.len()
+			+ self
+				.resource_controllers
+				.previous_sound_controller
+				.as_ref()
+				.map_or(0, |controller| controller.len())
 	}
 
 	/// Returns the number of mixer sub-tracks that currently exist.
@@ -210,8 +369,86 @@ impl<B: Backend> AudioManager<B> {
 		self.resource_controllers.clock_controller.len()
 	}
 
+	/// Returns the number of modulators that currently exist.
+	pub fn num_modulators(&self) -> usize {
+		self.resource_controllers.modulator_controller.len()
+	}
+
+	/// Returns the time between audio being produced and that audio
+	/// reaching the speakers, or `None` if the backend can't report it.
+	///
+	/// This is the latency of the backend and audio driver. It doesn't
+	/// include the latency added by resampling individual sounds.
+	pub fn output_latency(&self) -> Option<Duration> {
+		self.backend.output_latency()
+	}
+
+	/// Returns a snapshot of the mixer tracks, their routes and
+	/// effects, and how many sounds are playing on each one.
+	///
+	/// The volumes and sound counts are updated once per batch of
+	/// samples on the audio thread, so they may be slightly behind.
+	/// Tracks that have been stopped or whose handles have been
+	/// dropped aren't included.
+	pub fn routing_snapshot(&self) -> RoutingGraph {
+		let main_track_shared = &self.resource_controllers.main_track_shared;
+		let mut tracks = vec![TrackSnapshot {
+			id: TrackId::Main,
+			volume: main_track_shared.volume(),
+			routes: vec![],
+			effect_names: self.resource_controllers.main_track_effect_names.clone(),
+			num_sounds: main_track_shared.num_sounds(),
+		}];
+		tracks.extend(
+			self.sub_tracks
+				.tracks()
+				.iter()
+				.map(|(id, shared, effect_names)| TrackSnapshot {
+					id: TrackId::Sub(*id),
+					volume: shared.volume(),
+					routes: shared.route_volumes(),
+					effect_names: effect_names.clone(),
+					num_sounds: shared.num_sounds(),
+				}),
+		);
+		RoutingGraph { tracks }
+	}
+
 	/// Returns a mutable reference to this manager's backend.
 	pub fn backend_mut(&mut self) -> &mut B {
 		&mut self.backend
 	}
+
+	fn remove_unused_sound_arenas(&mut self) {
+		// once the old sound arena is sent back, all of the sounds
+		// have been moved to the new arena
+		while self.unused_resource_consumers.sound_arena.pop().is_some() {
+			self.resource_controllers.previous_sound_controller = None;
+		}
+	}
+}
+
+impl<B: Backend> Drop for AudioManager<B> {
+	/// Starts fading out the audio output before the backend is shut
+	/// down (see [`AudioManagerSettings::start_stop_fade_duration`]).
+	///
+	/// This doesn't block. Backends that render audio on another
+	/// thread keep the output running until the fade is finished
+	/// (see [`Backend::finish_fade_out`]).
+	fn drop(&mut self) {
+		if self.start_stop_fade_duration.is_zero() || self.state() != MainPlaybackState::Playing {
+			return;
+		}
+		let fade_out_tween = Tween {
+			duration: self.start_stop_fade_duration,
+			..Default::default()
+		};
+		if self.pause(fade_out_tween).is_err() {
+			return;
+		}
+		// the audio thread only receives the command at the start
+		// of its next batch of samples, so give it a little extra time
+		self.backend
+			.finish_fade_out(self.start_stop_fade_duration + SHUTDOWN_FADE_MARGIN);
+	}
 }
@@ -6,6 +6,8 @@ pub mod mock;
 mod renderer;
 pub(crate) mod resources;
 
+use std::time::Duration;
+
 pub use renderer::*;
 
 #[cfg(feature = "cpal")]
@@ -34,4 +36,23 @@ pub trait Backend: Sized {
 
 	/// Sends the renderer to the backend to start audio playback.
 	fn start(&mut self, renderer: Renderer) -> Result<(), Self::Error>;
+
+	/// Returns the time between the [`Renderer`] producing a frame
+	/// of audio and that audio reaching the speakers, or `None` if
+	/// the backend can't report it.
+	fn output_latency(&self) -> Option<Duration> {
+		None
+	}
+
+	/// Called when the [`AudioManager`](crate::manager::AudioManager)
+	/// is dropped after it tells the [`Renderer`] to fade out.
+	///
+	/// Backends that render audio on another thread can keep the
+	/// output running after they're dropped until the renderer is
+	/// paused or `max_duration` has passed, so the audio doesn't cut
+	/// off abruptly. This must not block the calling thread.
+	///
+	/// By default, this does nothing, and the audio output stops
+	/// as soon as the backend is dropped.
+	fn finish_fade_out(&mut self, _max_duration: Duration) {}
 }
@@ -3,7 +3,11 @@
 #![cfg_attr(docsrs, doc(cfg(feature = "cpal")))]
 
 mod error;
+mod event;
+mod settings;
 pub use error::*;
+pub use event::*;
+pub use settings::*;
 
 #[cfg(target_arch = "wasm32")]
 mod wasm;
@@ -14,3 +18,86 @@ pub use wasm::CpalBackend;
 mod desktop;
 #[cfg(not(target_arch = "wasm32"))]
 pub use desktop::CpalBackend;
+
+use std::{
+	sync::atomic::{AtomicU64, Ordering},
+	time::Duration,
+};
+
+use cpal::{
+	traits::{DeviceTrait, HostTrait},
+	BufferSize, Device, OutputCallbackInfo, SampleRate, StreamConfig,
+};
+
+/// Stored in place of the output latency before it's been measured.
+const OUTPUT_LATENCY_UNKNOWN: u64 = u64::MAX;
+
+impl CpalBackend {
+	/// Returns the names of the available audio output devices.
+	///
+	/// These names can be used for [`CpalBackendSettings::device_name`].
+	pub fn output_device_names() -> Result<Vec<String>, Error> {
+		Ok(cpal::default_host()
+			.output_devices()?
+			.filter_map(|device| device.name().ok())
+			.collect())
+	}
+}
+
+/// Finds the output device and stream configuration requested
+/// by the backend settings.
+fn device_and_config(settings: &CpalBackendSettings) -> Result<(Device, StreamConfig), Error> {
+	let host = cpal::default_host();
+	let device = match &settings.device_name {
+		Some(device_name) => host
+			.output_devices()?
+			.find(|device| matches!(device.name(), Ok(name) if &name == device_name))
+			.ok_or_else(|| Error::DeviceNotFound(device_name.clone()))?,
+		None => host
+			.default_output_device()
+			.ok_or(Error::NoDefaultOutputDevice)?,
+	};
+	let mut config = device.default_output_config()?.config();
+	if let Some(sample_rate) = settings.sample_rate {
+		let supported = device.supported_output_configs()?.any(|range| {
+			range.channels() == config.channels
+				&& range.min_sample_rate().0 <= sample_rate
+				&& sample_rate <= range.max_sample_rate().0
+		});
+		if !supported {
+			return Err(Error::UnsupportedSampleRate(sample_rate));
+		}
+		config.sample_rate = SampleRate(sample_rate);
+	}
+	if let Some(buffer_size) = settings.buffer_size {
+		config.buffer_size = BufferSize::Fixed(buffer_size);
+	}
+	Ok((device, config))
+}
+
+/// Measures the time between a data callback and the audio it
+/// produces being played and stores it (in nanoseconds).
+///
+/// If the host doesn't report when the audio will be played, the
+/// length of the buffer is used instead.
+fn store_output_latency(
+	output_latency: &AtomicU64,
+	info: &OutputCallbackInfo,
+	buffer_frames: usize,
+	sample_rate: u32,
+) {
+	let timestamp = info.timestamp();
+	let latency = timestamp
+		.playback
+		.duration_since(&timestamp.callback)
+		.filter(|latency| !latency.is_zero())
+		.unwrap_or_else(|| Duration::from_secs_f64(buffer_frames as f64 / sample_rate as f64));
+	output_latency.store(latency.as_nanos() as u64, Ordering::SeqCst);
+}
+
+fn load_output_latency(output_latency: &AtomicU64) -> Option<Duration> {
+	match output_latency.load(Ordering::SeqCst) {
+		OUTPUT_LATENCY_UNKNOWN => None,
+		nanos => Some(Duration::from_nanos(nanos)),
+	}
+}
@@ -2,19 +2,19 @@ mod stream_manager;
 
 use stream_manager::{StreamManager, StreamManagerController};
 
+use std::time::Duration;
+
 use crate::manager::backend::{Backend, Renderer};
-use cpal::{
-	traits::{DeviceTrait, HostTrait},
-	Device, StreamConfig,
-};
+use cpal::{Device, StreamConfig};
 
-use super::Error;
+use super::{device_and_config, load_output_latency, CpalBackendEvent, CpalBackendSettings, Error};
 
 enum State {
 	Empty,
 	Uninitialized {
 		device: Device,
 		config: StreamConfig,
+		settings: CpalBackendSettings,
 	},
 	Initialized {
 		stream_manager_controller: StreamManagerController,
@@ -27,21 +27,39 @@ pub struct CpalBackend {
 	state: State,
 }
 
+impl CpalBackend {
+	/// Returns the next event that happened to the audio stream,
+	/// like the output device being disconnected.
+	///
+	/// Returns `None` if there are no new events or the backend
+	/// hasn't been started yet.
+	pub fn pop_event(&mut self) -> Option<CpalBackendEvent> {
+		if let State::Initialized {
+			stream_manager_controller,
+		} = &mut self.state
+		{
+			stream_manager_controller.pop_event()
+		} else {
+			None
+		}
+	}
+}
+
 impl Backend for CpalBackend {
-	type Settings = ();
+	type Settings = CpalBackendSettings;
 
 	type Error = Error;
 
-	fn setup(_settings: Self::Settings) -> Result<(Self, u32), Self::Error> {
-		let host = cpal::default_host();
-		let device = host
-			.default_output_device()
-			.ok_or(Error::NoDefaultOutputDevice)?;
-		let config = device.default_output_config()?.config();
+	fn setup(settings: Self::Settings) -> Result<(Self, u32), Self::Error> {
+		let (device, config) = device_and_config(&settings)?;
 		let sample_rate = config.sample_rate.0;
 		Ok((
 			Self {
-				state: State::Uninitialized { device, config },
+				state: State::Uninitialized {
+					device,
+					config,
+					settings,
+				},
 			},
 			sample_rate,
 		))
@@ -49,15 +67,40 @@ impl Backend for CpalBackend {
 
 	fn start(&mut self, renderer: Renderer) -> Result<(), Self::Error> {
 		let state = std::mem::replace(&mut self.state, State::Empty);
-		if let State::Uninitialized { device, config } = state {
+		if let State::Uninitialized {
+			device,
+			config,
+			settings,
+		} = state
+		{
 			self.state = State::Initialized {
-				stream_manager_controller: StreamManager::start(renderer, device, config),
+				stream_manager_controller: StreamManager::start(renderer, device, config, settings),
 			};
 		} else {
 			panic!("Cannot initialize the backend multiple times")
 		}
 		Ok(())
 	}
+
+	fn output_latency(&self) -> Option<Duration> {
+		if let State::Initialized {
+			stream_manager_controller,
+		} = &self.state
+		{
+			load_output_latency(stream_manager_controller.output_latency())
+		} else {
+			None
+		}
+	}
+
+	fn finish_fade_out(&mut self, max_duration: Duration) {
+		if let State::Initialized {
+			stream_manager_controller,
+		} = &self.state
+		{
+			stream_manager_controller.set_fade_out_duration(max_duration);
+		}
+	}
 }
 
 impl Drop for CpalBackend {
@@ -2,24 +2,32 @@ mod renderer_wrapper;
 
 use std::{
 	sync::{
-		atomic::{AtomicBool, Ordering},
+		atomic::{AtomicBool, AtomicU64, Ordering},
 		Arc,
 	},
-	time::Duration,
+	time::{Duration, Instant},
 };
 
-use crate::manager::backend::Renderer;
+use crate::manager::{
+	backend::{Renderer, RendererShared},
+	MainPlaybackState,
+};
 use cpal::{
-	traits::{DeviceTrait, HostTrait, StreamTrait},
+	traits::{DeviceTrait, StreamTrait},
 	Device, Stream, StreamConfig, StreamError,
 };
-use ringbuf::{Consumer, RingBuffer};
+use ringbuf::{Consumer, Producer, RingBuffer};
 
-use super::super::Error;
+use super::super::{
+	device_and_config, store_output_latency, CpalBackendEvent, CpalBackendSettings, Error,
+	OUTPUT_LATENCY_UNKNOWN,
+};
 
 use self::renderer_wrapper::RendererWrapper;
 
 const CHECK_STREAM_INTERVAL: Duration = Duration::from_millis(500);
+const EVENT_CAPACITY: usize = 16;
+const CHECK_FADE_OUT_INTERVAL: Duration = Duration::from_millis(1);
 
 #[allow(clippy::large_enum_variant)]
 enum State {
@@ -36,12 +44,30 @@ enum State {
 
 pub(super) struct StreamManagerController {
 	should_drop: Arc<AtomicBool>,
+	event_consumer: Consumer<CpalBackendEvent>,
+	output_latency: Arc<AtomicU64>,
+	/// The longest time (in nanoseconds) to keep the stream
+	/// running after stopping while the renderer fades out.
+	fade_out_duration: Arc<AtomicU64>,
 }
 
 impl StreamManagerController {
 	pub fn stop(&self) {
 		self.should_drop.store(true, Ordering::SeqCst);
 	}
+
+	pub fn set_fade_out_duration(&self, fade_out_duration: Duration) {
+		self.fade_out_duration
+			.store(fade_out_duration.as_nanos() as u64, Ordering::SeqCst);
+	}
+
+	pub fn pop_event(&mut self) -> Option<CpalBackendEvent> {
+		self.event_consumer.pop()
+	}
+
+	pub fn output_latency(&self) -> &AtomicU64 {
+		&self.output_latency
+	}
 }
 
 /// Starts a cpal stream and restarts it if needed
@@ -50,6 +76,10 @@ pub(super) struct StreamManager {
 	state: State,
 	device_name: String,
 	sample_rate: u32,
+	settings: CpalBackendSettings,
+	event_producer: Producer<CpalBackendEvent>,
+	output_latency: Arc<AtomicU64>,
+	renderer_shared: Arc<RendererShared>,
 }
 
 impl StreamManager {
@@ -57,16 +87,28 @@ impl StreamManager {
 		renderer: Renderer,
 		device: Device,
 		config: StreamConfig,
+		settings: CpalBackendSettings,
 	) -> StreamManagerController {
 		let should_drop = Arc::new(AtomicBool::new(false));
 		let should_drop_clone = should_drop.clone();
+		let (event_producer, event_consumer) = RingBuffer::new(EVENT_CAPACITY).split();
+		let output_latency = Arc::new(AtomicU64::new(OUTPUT_LATENCY_UNKNOWN));
+		let output_latency_clone = output_latency.clone();
+		let fade_out_duration = Arc::new(AtomicU64::new(0));
+		let fade_out_duration_clone = fade_out_duration.clone();
 		std::thread::spawn(move || {
 			let mut stream_manager = StreamManager {
+				renderer_shared: renderer.shared(),
 				state: State::Idle { renderer },
 				device_name: device_name(&device),
 				sample_rate: config.sample_rate.0,
+				settings,
+				event_producer,
+				output_latency,
 			};
-			stream_manager.start_stream(&device, &config).unwrap();
+			// if the stream can't be started, we'll keep trying
+			// in check_stream
+			stream_manager.start_stream(&device, &config).ok();
 			loop {
 				std::thread::sleep(CHECK_STREAM_INTERVAL);
 				if should_drop.load(Ordering::SeqCst) {
@@ -74,39 +116,87 @@ impl StreamManager {
 				}
 				stream_manager.check_stream();
 			}
+			stream_manager.finish_fade_out(Duration::from_nanos(
+				fade_out_duration.load(Ordering::SeqCst),
+			));
 		});
 		StreamManagerController {
 			should_drop: should_drop_clone,
+			event_consumer,
+			output_latency: output_latency_clone,
+			fade_out_duration: fade_out_duration_clone,
 		}
 	}
 
-	/// Restarts the stream if the audio device gets disconnected.
-	fn check_stream(&mut self) {
-		if let State::Running {
-			stream_error_consumer,
-			..
-		} = &mut self.state
+	/// Keeps the stream running until the renderer has faded
+	/// out, or until `max_duration` has passed.
+	fn finish_fade_out(&self, max_duration: Duration) {
+		if !matches!(self.state, State::Running { .. }) {
+			return;
+		}
+		let deadline = Instant::now() + max_duration;
+		while self.renderer_shared.state() != MainPlaybackState::Paused && Instant::now() < deadline
 		{
-			// check for device disconnection
-			if let Some(StreamError::DeviceNotAvailable) = stream_error_consumer.pop() {
-				self.stop_stream();
-				if let Ok((device, config)) = default_device_and_config() {
-					// TODO: gracefully handle errors that occur in this function
-					self.start_stream(&device, &config).unwrap();
-				}
-			}
-			// check for device changes
-			if let Ok((device, config)) = default_device_and_config() {
-				let device_name = device_name(&device);
-				let sample_rate = config.sample_rate.0;
-				if device_name != self.device_name || sample_rate != self.sample_rate {
+			std::thread::sleep(CHECK_FADE_OUT_INTERVAL);
+		}
+	}
+
+	/// Restarts the stream if the audio device gets disconnected
+	/// or the default device changes.
+	fn check_stream(&mut self) {
+		match &mut self.state {
+			State::Running {
+				stream_error_consumer,
+				..
+			} => {
+				// check for device disconnection
+				if let Some(StreamError::DeviceNotAvailable) = stream_error_consumer.pop() {
 					self.stop_stream();
-					self.start_stream(&device, &config).unwrap();
+					self.push_event(CpalBackendEvent::DeviceDisconnected);
+					self.restart_stream();
+					return;
+				}
+				// check for device changes
+				if let Ok((device, config)) = self.device_and_config() {
+					let device_name = device_name(&device);
+					let sample_rate = config.sample_rate.0;
+					if device_name != self.device_name || sample_rate != self.sample_rate {
+						self.stop_stream();
+						if self.start_stream(&device, &config).is_ok() {
+							self.push_stream_restarted_event();
+						}
+					}
 				}
 			}
+			// a previous attempt to start the stream failed,
+			// so try again
+			State::Idle { .. } => self.restart_stream(),
+			State::Empty => panic!("The stream manager should never be left in the empty state"),
 		}
 	}
 
+	/// Tries to start a stream on the best available device.
+	fn restart_stream(&mut self) {
+		if let Ok((device, config)) = self.device_and_config() {
+			if self.start_stream(&device, &config).is_ok() {
+				self.push_stream_restarted_event();
+			}
+		}
+	}
+
+	/// Returns the device and configuration the stream should use.
+	///
+	/// If the device requested in the settings isn't available
+	/// anymore, this falls back to the default device.
+	fn device_and_config(&self) -> Result<(Device, StreamConfig), Error> {
+		device_and_config(&self.settings).or_else(|_| {
+			device_and_config(&CpalBackendSettings {
+				buffer_size: self.settings.buffer_size,
+				..Default::default()
+			})
+		})
+	}
+
 	fn start_stream(&mut self, device: &Device, config: &StreamConfig) -> Result<(), Error> {
 		let mut renderer =
 			if let State::Idle { renderer } = std::mem::replace(&mut self.state, State::Empty) {
@@ -121,30 +211,54 @@ impl StreamManager {
 		}
 		self.device_name = device_name;
 		self.sample_rate = sample_rate;
-		let (mut renderer_wrapper, renderer_consumer) = RendererWrapper::new(renderer);
+		let (mut renderer_wrapper, mut renderer_consumer) = RendererWrapper::new(renderer);
 		let (mut stream_error_producer, stream_error_consumer) = RingBuffer::new(1).split();
 		let channels = config.channels;
-		let stream = device.build_output_stream(
-			config,
-			move |data: &mut [f32], _| {
-				renderer_wrapper.on_start_processing();
-				for frame in data.chunks_exact_mut(channels as usize) {
-					let out = renderer_wrapper.process();
-					if channels == 1 {
-						frame[0] = (out.left + out.right) / 2.0;
-					} else {
-						frame[0] = out.left;
-						frame[1] = out.right;
+		let output_latency = self.output_latency.clone();
+		let stream = device
+			.build_output_stream(
+				config,
+				move |data: &mut [f32], info| {
+					store_output_latency(
+						&output_latency,
+						info,
+						data.len() / channels as usize,
+						sample_rate,
+					);
+					renderer_wrapper.on_start_processing();
+					for frame in data.chunks_exact_mut(channels as usize) {
+						let out = renderer_wrapper.process();
+						if channels == 1 {
+							frame[0] = (out.left + out.right) / 2.0;
+						} else {
+							frame[0] = out.left;
+							frame[1] = out.right;
+						}
 					}
-				}
-			},
-			move |error| {
-				stream_error_producer
-					.push(error)
-					.expect("Stream error producer is full");
-			},
-		)?;
-		stream.play()?;
+				},
+				move |error| {
+					// if an error is already waiting to be handled,
+					// the stream is going to be restarted anyway
+					stream_error_producer.push(error).ok();
+				},
+			)
+			.map_err(Error::from)
+			.and_then(|stream| {
+				stream.play()?;
+				Ok(stream)
+			});
+		let stream = match stream {
+			Ok(stream) => stream,
+			Err(error) => {
+				// the renderer is sent back once the stream
+				// callback is dropped
+				let renderer = renderer_consumer
+					.pop()
+					.expect("Could not retrieve the renderer after failing to start a stream");
+				self.state = State::Idle { renderer };
+				return Err(error);
+			}
+		};
 		self.state = State::Running {
 			stream,
 			stream_error_consumer,
@@ -169,15 +283,19 @@ impl StreamManager {
 			panic!("Trying to stop the stream when it's not running")
 		}
 	}
-}
 
-fn default_device_and_config() -> Result<(Device, StreamConfig), Error> {
-	let host = cpal::default_host();
-	let device = host
-		.default_output_device()
-		.ok_or(Error::NoDefaultOutputDevice)?;
-	let config = device.default_output_config()?.config();
-	Ok((device, config))
+	fn push_stream_restarted_event(&mut self) {
+		self.push_event(CpalBackendEvent::StreamRestarted {
+			device_name: self.device_name.clone(),
+			sample_rate: self.sample_rate,
+		});
+	}
+
+	fn push_event(&mut self, event: CpalBackendEvent) {
+		// if the app isn't checking for events, old events
+		// are kept and new ones are discarded
+		self.event_producer.push(event).ok();
+	}
 }
 
 fn device_name(device: &Device) -> String {
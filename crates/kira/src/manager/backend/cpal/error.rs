@@ -1,6 +1,9 @@
 use std::fmt::{Display, Formatter};
 
-use cpal::{BuildStreamError, DefaultStreamConfigError, PlayStreamError};
+use cpal::{
+	BuildStreamError, DefaultStreamConfigError, DevicesError, PlayStreamError,
+	SupportedStreamConfigsError,
+};
 
 /// Errors that can occur when using the cpal backend.
 #[derive(Debug)]
@@ -8,6 +11,14 @@ use cpal::{BuildStreamError, DefaultStreamConfigError, PlayStreamError};
 pub enum Error {
 	/// A default audio output device could not be determined.
 	NoDefaultOutputDevice,
+	/// No audio output device with the requested name exists.
+	DeviceNotFound(String),
+	/// The audio output device does not support the requested sample rate.
+	UnsupportedSampleRate(u32),
+	/// An error occurred when listing the available audio devices.
+	DevicesError(DevicesError),
+	/// An error occurred when getting the supported output configurations.
+	SupportedStreamConfigsError(SupportedStreamConfigsError),
 	/// An error occurred when getting the default output configuration.
 	DefaultStreamConfigError(DefaultStreamConfigError),
 	/// An error occured when building the audio stream.
@@ -22,6 +33,18 @@ impl Display for Error {
 			Error::NoDefaultOutputDevice => {
 				f.write_str("Cannot find the default audio output device")
 			}
+			Error::DeviceNotFound(device_name) => write!(
+				f,
+				"Cannot find an audio output device named \"{}\"",
+				device_name
+			),
+			Error::UnsupportedSampleRate(sample_rate) => write!(
+				f,
+				"The audio output device does not support a sample rate of {} Hz",
+				sample_rate
+			),
+			Error::DevicesError(error) => error.fmt(f),
+			Error::SupportedStreamConfigsError(error) => error.fmt(f),
 			Error::DefaultStreamConfigError(error) => error.fmt(f),
 			Error::BuildStreamError(error) => error.fmt(f),
 			Error::PlayStreamError(error) => error.fmt(f),
@@ -32,6 +55,8 @@ impl Display for Error {
 impl std::error::Error for Error {
 	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
 		match self {
+			Error::DevicesError(error) => Some(error),
+			Error::SupportedStreamConfigsError(error) => Some(error),
 			Error::DefaultStreamConfigError(error) => Some(error),
 			Error::BuildStreamError(error) => Some(error),
 			Error::PlayStreamError(error) => Some(error),
@@ -40,6 +65,18 @@ impl std::error::Error for Error {
 	}
 }
 
+impl From<DevicesError> for Error {
+	fn from(v: DevicesError) -> Self {
+		Self::DevicesError(v)
+	}
+}
+
+impl From<SupportedStreamConfigsError> for Error {
+	fn from(v: SupportedStreamConfigsError) -> Self {
+		Self::SupportedStreamConfigsError(v)
+	}
+}
+
 impl From<DefaultStreamConfigError> for Error {
 	fn from(v: DefaultStreamConfigError) -> Self {
 		Self::DefaultStreamConfigError(v)
@@ -0,0 +1,25 @@
+/// Something that happened to the cpal backend's audio stream.
+///
+/// Events can be retrieved with
+/// [`CpalBackend::pop_event`](super::CpalBackend::pop_event).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CpalBackendEvent {
+	/// The audio output device was disconnected, and audio
+	/// stopped playing.
+	///
+	/// The backend will try to keep playing audio on another
+	/// device.
+	DeviceDisconnected,
+	/// The audio stream was restarted after the output device
+	/// was disconnected or the default output device changed.
+	///
+	/// If the new device uses a different sample rate, all
+	/// effects are notified of the new sample rate.
+	StreamRestarted {
+		/// The name of the device audio is now playing on.
+		device_name: String,
+		/// The sample rate of the new audio stream.
+		sample_rate: u32,
+	},
+}
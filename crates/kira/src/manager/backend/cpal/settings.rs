@@ -0,0 +1,20 @@
+/// Settings for the cpal backend.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CpalBackendSettings {
+	/// The name of the output device to use.
+	///
+	/// If this is `None`, the default output device will be used.
+	/// The names of the available devices can be retrieved with
+	/// [`CpalBackend::output_device_names`](super::CpalBackend::output_device_names).
+	pub device_name: Option<String>,
+	/// The sample rate to request from the output device.
+	///
+	/// If this is `None`, the device's default sample rate will be used.
+	pub sample_rate: Option<u32>,
+	/// The number of frames to request the device to process at a time.
+	///
+	/// Smaller buffers reduce latency but make it more likely for
+	/// audio to stutter if the audio thread can't keep up. If this is
+	/// `None`, the device's default buffer size will be used.
+	pub buffer_size: Option<u32>,
+}
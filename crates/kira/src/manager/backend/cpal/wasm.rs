@@ -1,10 +1,18 @@
+use std::{
+	sync::{atomic::AtomicU64, Arc},
+	time::Duration,
+};
+
 use crate::manager::backend::{Backend, Renderer};
 use cpal::{
-	traits::{DeviceTrait, HostTrait, StreamTrait},
+	traits::{DeviceTrait, StreamTrait},
 	Device, Stream, StreamConfig,
 };
 
-use super::Error;
+use super::{
+	device_and_config, load_output_latency, store_output_latency, CpalBackendEvent,
+	CpalBackendSettings, Error, OUTPUT_LATENCY_UNKNOWN,
+};
 
 enum State {
 	Empty,
@@ -14,6 +22,7 @@ enum State {
 	},
 	Initialized {
 		_stream: Stream,
+		output_latency: Arc<AtomicU64>,
 	},
 }
 
@@ -23,17 +32,23 @@ pub struct CpalBackend {
 	state: State,
 }
 
+impl CpalBackend {
+	/// Returns the next event that happened to the audio stream.
+	///
+	/// Device changes are not detected on the web, so this
+	/// always returns `None`.
+	pub fn pop_event(&mut self) -> Option<CpalBackendEvent> {
+		None
+	}
+}
+
 impl Backend for CpalBackend {
-	type Settings = ();
+	type Settings = CpalBackendSettings;
 
 	type Error = Error;
 
-	fn setup(_settings: Self::Settings) -> Result<(Self, u32), Self::Error> {
-		let host = cpal::default_host();
-		let device = host
-			.default_output_device()
-			.ok_or(Error::NoDefaultOutputDevice)?;
-		let config = device.default_output_config()?.config();
+	fn setup(settings: Self::Settings) -> Result<(Self, u32), Self::Error> {
+		let (device, config) = device_and_config(&settings)?;
 		let sample_rate = config.sample_rate.0;
 		Ok((
 			Self {
@@ -48,9 +63,18 @@ impl Backend for CpalBackend {
 			std::mem::replace(&mut self.state, State::Empty)
 		{
 			let channels = config.channels;
+			let sample_rate = config.sample_rate.0;
+			let output_latency = Arc::new(AtomicU64::new(OUTPUT_LATENCY_UNKNOWN));
+			let output_latency_clone = output_latency.clone();
 			let stream = device.build_output_stream(
 				&config,
-				move |data: &mut [f32], _| {
+				move |data: &mut [f32], info| {
+					store_output_latency(
+						&output_latency_clone,
+						info,
+						data.len() / channels as usize,
+						sample_rate,
+					);
 					renderer.on_start_processing();
 					for frame in data.chunks_exact_mut(channels as usize) {
 						let out = renderer.process();
@@ -65,10 +89,21 @@ impl Backend for CpalBackend {
 				move |_| {},
 			)?;
 			stream.play()?;
-			self.state = State::Initialized { _stream: stream };
+			self.state = State::Initialized {
+				_stream: stream,
+				output_latency,
+			};
 		} else {
 			panic!("Cannot initialize the backend multiple times")
 		}
 		Ok(())
 	}
+
+	fn output_latency(&self) -> Option<Duration> {
+		if let State::Initialized { output_latency, .. } = &self.state {
+			load_output_latency(output_latency)
+		} else {
+			None
+		}
+	}
 }
@@ -1,5 +1,7 @@
 //! Useful for testing and benchmarking.
 
+use std::time::Duration;
+
 use crate::dsp::Frame;
 
 use super::{Backend, Renderer};
@@ -27,6 +29,13 @@ impl Default for MockBackendSettings {
 /// [`Renderer::on_start_processing`] and [`Renderer::process`].
 ///
 /// This is useful for testing and benchmarking.
+///
+/// Audio only advances when these methods are called, so the output
+/// doesn't depend on real time. To get the exact same output every
+/// time (for example, to compare a mix against a known-good
+/// rendering), also set
+/// [`AudioManagerSettings::random_seed`](crate::manager::AudioManagerSettings::random_seed)
+/// and render in fixed-size blocks with [`process_block`](Self::process_block).
 pub struct MockBackend {
 	sample_rate: u32,
 	state: State,
@@ -59,6 +68,14 @@ impl MockBackend {
 			panic!("backend is not initialized")
 		}
 	}
+
+	/// Renders a block of `num_frames` frames, calling
+	/// [`on_start_processing`](Renderer::on_start_processing) once
+	/// at the start of the block, like an audio driver would.
+	pub fn process_block(&mut self, num_frames: usize) -> Vec<Frame> {
+		self.on_start_processing();
+		(0..num_frames).map(|_| self.process()).collect()
+	}
 }
 
 impl Backend for MockBackend {
@@ -80,4 +97,8 @@ impl Backend for MockBackend {
 		self.state = State::Initialized { renderer };
 		Ok(())
 	}
+
+	fn output_latency(&self) -> Option<Duration> {
+		Some(Duration::ZERO)
+	}
 }
@@ -1,14 +1,17 @@
-use std::sync::{
-	atomic::{AtomicU8, Ordering},
-	Arc,
+use std::{
+	sync::{
+		atomic::{AtomicU8, Ordering},
+		Arc,
+	},
+	time::Duration,
 };
 
 use ringbuf::Consumer;
 
 use crate::{
-	dsp::Frame,
+	dsp::{DcBlocker, Frame, Muffle},
 	manager::{command::Command, MainPlaybackState},
-	tween::Tweener,
+	tween::{Tween, Tweener},
 	Volume,
 };
 
@@ -42,6 +45,8 @@ pub struct Renderer {
 	command_consumer: Consumer<Command>,
 	state: MainPlaybackState,
 	fade_volume: Tweener<Volume>,
+	dc_blocker: Option<DcBlocker>,
+	muffle: Muffle,
 }
 
 impl Renderer {
@@ -49,6 +54,8 @@ impl Renderer {
 		sample_rate: u32,
 		resources: Resources,
 		command_consumer: Consumer<Command>,
+		fade_in_duration: Duration,
+		dc_blocker: bool,
 	) -> Self {
 		Self {
 			dt: 1.0 / sample_rate as f64,
@@ -56,7 +63,25 @@ impl Renderer {
 			resources,
 			command_consumer,
 			state: MainPlaybackState::Playing,
-			fade_volume: Tweener::new(Volume::Decibels(0.0)),
+			fade_volume: if fade_in_duration.is_zero() {
+				Tweener::new(Volume::Decibels(0.0))
+			} else {
+				let mut fade_volume = Tweener::new(Volume::Decibels(Volume::MIN_DECIBELS));
+				fade_volume.set(
+					Volume::Decibels(0.0),
+					Tween {
+						duration: fade_in_duration,
+						..Default::default()
+					},
+				);
+				fade_volume
+			},
+			dc_blocker: if dc_blocker {
+				Some(DcBlocker::new(sample_rate))
+			} else {
+				None
+			},
+			muffle: Muffle::new(),
 		}
 	}
 
@@ -69,35 +94,48 @@ impl Renderer {
 	pub fn on_change_sample_rate(&mut self, sample_rate: u32) {
 		self.dt = 1.0 / sample_rate as f64;
 		self.resources.mixer.on_change_sample_rate(sample_rate);
+		if let Some(dc_blocker) = &mut self.dc_blocker {
+			dc_blocker.on_change_sample_rate(sample_rate);
+		}
 	}
 
 	/// Called by the backend when it's time to process
 	/// a new batch of samples.
 	pub fn on_start_processing(&mut self) {
 		self.resources.sounds.on_start_processing();
+		self.resources
+			.sounds
+			.count_sounds_on_tracks(&mut self.resources.mixer);
 		self.resources.mixer.on_start_processing();
+		self.resources
+			.sounds
+			.forget_removed_tracks(&mut self.resources.mixer);
 		self.resources.clocks.on_start_processing();
+		self.resources.modulators.on_start_processing();
 
 		while let Some(command) = self.command_consumer.pop() {
 			match command {
 				Command::Sound(command) => self.resources.sounds.run_command(command),
 				Command::Mixer(command) => self.resources.mixer.run_command(command),
 				Command::Clock(command) => self.resources.clocks.run_command(command),
+				Command::Modulator(command) => self.resources.modulators.run_command(command),
 				Command::Pause(fade_out_tween) => {
 					self.state = MainPlaybackState::Pausing;
 					self.shared
 						.state
 						.store(MainPlaybackState::Pausing as u8, Ordering::SeqCst);
 					self.fade_volume
-						.set(Volume::Decibels(Volume::MIN_DECIBELS), fade_out_tween);
+						.set(fade_out_tween.fade_curve.silent_volume(), fade_out_tween);
 				}
 				Command::Resume(fade_in_tween) => {
 					self.state = MainPlaybackState::Playing;
 					self.shared
 						.state
 						.store(MainPlaybackState::Playing as u8, Ordering::SeqCst);
-					self.fade_volume.set(Volume::Decibels(0.0), fade_in_tween);
+					self.fade_volume
+						.set(fade_in_tween.fade_curve.full_volume(), fade_in_tween);
 				}
+				Command::SetMuffle(amount, tween) => self.muffle.set_amount(amount, tween),
 			}
 		}
 	}
@@ -118,12 +156,21 @@ impl Renderer {
 			for time in clock_tick_events {
 				self.resources.sounds.on_clock_tick(*time);
 				self.resources.mixer.on_clock_tick(*time);
+				self.resources.modulators.on_clock_tick(*time);
+				self.muffle.on_clock_tick(*time);
 			}
 		}
+		// modulators are updated before sounds and effects are
+		// processed so effects always read the current value
+		self.resources.modulators.update(self.dt);
 		self.resources
 			.sounds
 			.process(self.dt, &mut self.resources.mixer);
-		let out = self.resources.mixer.process(self.dt);
+		let mut out = self.resources.mixer.process(self.dt);
+		out = self.muffle.process(out, self.dt);
+		if let Some(dc_blocker) = &mut self.dc_blocker {
+			out = dc_blocker.process(out);
+		}
 		out * self.fade_volume.value().as_amplitude() as f32
 	}
 }
@@ -1,28 +1,35 @@
 pub(crate) mod mixer;
 pub(crate) mod sounds;
 
-use atomic_arena::Controller;
+use std::sync::Arc;
+
+use atomic_arena::{Arena, Controller};
 use ringbuf::{Consumer, Producer, RingBuffer};
 
 use crate::{
 	clock::{clocks::Clocks, Clock},
 	manager::settings::Capacities,
+	modulator::modulators::{ModulatorWrapper, Modulators},
 	sound::Sound,
-	track::{Track, TrackBuilder},
+	track::{Track, TrackBuilder, TrackShared},
 };
 
 use self::{mixer::Mixer, sounds::Sounds};
 
 pub(crate) struct UnusedResourceProducers {
 	pub sound: Producer<Box<dyn Sound>>,
+	pub sound_arena: Producer<Arena<Box<dyn Sound>>>,
 	pub sub_track: Producer<Track>,
 	pub clock: Producer<Clock>,
+	pub modulator: Producer<ModulatorWrapper>,
 }
 
 pub(crate) struct UnusedResourceConsumers {
 	pub sound: Consumer<Box<dyn Sound>>,
+	pub sound_arena: Consumer<Arena<Box<dyn Sound>>>,
 	pub sub_track: Consumer<Track>,
 	pub clock: Consumer<Clock>,
+	pub modulator: Consumer<ModulatorWrapper>,
 }
 
 pub(crate) fn create_unused_resource_channels(
@@ -30,20 +37,29 @@ pub(crate) fn create_unused_resource_channels(
 ) -> (UnusedResourceProducers, UnusedResourceConsumers) {
 	let (unused_sound_producer, unused_sound_consumer) =
 		RingBuffer::new(capacities.sound_capacity).split();
+	// the sound arena can only be grown once at a time,
+	// so there will never be more than one old arena to send back
+	let (unused_sound_arena_producer, unused_sound_arena_consumer) = RingBuffer::new(1).split();
 	let (unused_sub_track_producer, unused_sub_track_consumer) =
 		RingBuffer::new(capacities.sub_track_capacity).split();
 	let (unused_clock_producer, unused_clock_consumer) =
 		RingBuffer::new(capacities.clock_capacity).split();
+	let (unused_modulator_producer, unused_modulator_consumer) =
+		RingBuffer::new(capacities.modulator_capacity).split();
 	(
 		UnusedResourceProducers {
 			sound: unused_sound_producer,
+			sound_arena: unused_sound_arena_producer,
 			sub_track: unused_sub_track_producer,
 			clock: unused_clock_producer,
+			modulator: unused_modulator_producer,
 		},
 		UnusedResourceConsumers {
 			sound: unused_sound_consumer,
+			sound_arena: unused_sound_arena_consumer,
 			sub_track: unused_sub_track_consumer,
 			clock: unused_clock_consumer,
+			modulator: unused_modulator_consumer,
 		},
 	)
 }
@@ -52,41 +68,68 @@ pub(crate) struct Resources {
 	pub sounds: Sounds,
 	pub mixer: Mixer,
 	pub clocks: Clocks,
+	pub modulators: Modulators,
 }
 
 pub(crate) struct ResourceControllers {
 	pub sound_controller: Controller,
+	/// The controller for the old sound arena while the sounds
+	/// are being moved to a larger one.
+	pub previous_sound_controller: Option<Controller>,
 	pub sub_track_controller: Controller,
+	pub main_track_shared: Arc<TrackShared>,
+	pub main_track_effect_names: Vec<&'static str>,
 	pub clock_controller: Controller,
+	pub modulator_controller: Controller,
 }
 
 pub(crate) fn create_resources(
 	capacities: Capacities,
 	main_track_builder: TrackBuilder,
+	random_seed: Option<u64>,
 	unused_resource_producers: UnusedResourceProducers,
 	sample_rate: u32,
 ) -> (Resources, ResourceControllers) {
-	let sounds = Sounds::new(capacities.sound_capacity, unused_resource_producers.sound);
+	let sounds = Sounds::new(
+		capacities.sound_capacity,
+		unused_resource_producers.sound,
+		unused_resource_producers.sound_arena,
+		// the main track plus every sub-track
+		capacities.sub_track_capacity + 1,
+	);
 	let sound_controller = sounds.controller();
+	let main_track_effect_names = main_track_builder.effect_names.clone();
 	let mixer = Mixer::new(
 		capacities.sub_track_capacity,
 		unused_resource_producers.sub_track,
 		sample_rate,
 		main_track_builder,
+		random_seed,
 	);
 	let sub_track_controller = mixer.sub_track_controller();
+	let main_track_shared = mixer.main_track_shared();
 	let clocks = Clocks::new(capacities.clock_capacity, unused_resource_producers.clock);
 	let clock_controller = clocks.controller();
+	let modulators = Modulators::new(
+		capacities.modulator_capacity,
+		unused_resource_producers.modulator,
+	);
+	let modulator_controller = modulators.controller();
 	(
 		Resources {
 			sounds,
 			mixer,
 			clocks,
+			modulators,
 		},
 		ResourceControllers {
 			sound_controller,
+			previous_sound_controller: None,
 			sub_track_controller,
+			main_track_shared,
+			main_track_effect_names,
 			clock_controller,
+			modulator_controller,
 		},
 	)
 }
@@ -1,24 +1,36 @@
 #[cfg(test)]
 mod test;
 
+use std::sync::Arc;
+
 use atomic_arena::{Arena, Controller};
 use ringbuf::Producer;
 
 use crate::{
 	clock::ClockTime,
-	dsp::Frame,
+	dsp::{Frame, Random},
 	manager::command::MixerCommand,
-	track::{SubTrackId, Track, TrackBuilder, TrackId},
+	track::{DelayLine, SendPoint, SubTrackId, Track, TrackBuilder, TrackId, TrackShared},
 	tween::Tweener,
 	Volume,
 };
 
 pub(crate) struct Mixer {
 	sample_rate: u32,
+	/// Generates the random seeds for the effects on each track.
+	random: Random,
 	main_track: Track,
 	sub_tracks: Arena<Track>,
 	sub_track_ids: Vec<SubTrackId>,
-	dummy_routes: Vec<(TrackId, Tweener<Volume>)>,
+	/// The sub-tracks sorted so that each track comes before
+	/// the tracks it routes to. This is the order the tracks
+	/// are processed in.
+	sorted_sub_track_ids: Vec<SubTrackId>,
+	/// Whether tracks or routes have been added since the
+	/// sub-tracks were last sorted.
+	routing_changed: bool,
+	dummy_routes: Vec<(TrackId, Tweener<Volume>, SendPoint)>,
+	dummy_route_delays: Vec<DelayLine>,
 	unused_track_producer: Producer<Track>,
 }
 
@@ -28,21 +40,31 @@ impl Mixer {
 		unused_sub_track_producer: Producer<Track>,
 		sample_rate: u32,
 		main_track_builder: TrackBuilder,
+		random_seed: Option<u64>,
 	) -> Self {
+		let mut random = random_seed.map_or_else(Random::from_entropy, Random::new);
 		Self {
 			sample_rate,
 			main_track: {
 				let mut track = Track::new(main_track_builder);
-				track.init_effects(sample_rate);
+				track.init_effects(sample_rate, &mut random);
 				track
 			},
+			random,
 			sub_tracks: Arena::new(sub_track_capacity),
 			sub_track_ids: Vec::with_capacity(sub_track_capacity),
+			sorted_sub_track_ids: Vec::with_capacity(sub_track_capacity),
+			routing_changed: false,
 			dummy_routes: vec![],
+			dummy_route_delays: vec![],
 			unused_track_producer: unused_sub_track_producer,
 		}
 	}
 
+	pub fn main_track_shared(&self) -> Arc<TrackShared> {
+		self.main_track.shared()
+	}
+
 	pub fn sub_track_controller(&self) -> Controller {
 		self.sub_tracks.controller()
 	}
@@ -57,17 +79,29 @@ impl Mixer {
 	pub fn run_command(&mut self, command: MixerCommand) {
 		match command {
 			MixerCommand::AddSubTrack(id, mut track) => {
-				track.init_effects(self.sample_rate);
+				track.init_effects(self.sample_rate, &mut self.random);
 				self.sub_tracks
 					.insert_with_key(id.0, track)
 					.expect("Sub-track arena is full");
 				self.sub_track_ids.push(id);
+				self.routing_changed = true;
 			}
 			MixerCommand::SetTrackVolume(id, volume, tween) => {
 				if let Some(track) = self.track_mut(id) {
 					track.set_volume(volume, tween);
 				}
 			}
+			MixerCommand::StopTrack(id, tween) => match id {
+				// the main track can't be removed, so just fade it out
+				TrackId::Main => self
+					.main_track
+					.set_volume(tween.fade_curve.silent_volume(), tween),
+				TrackId::Sub(_) => {
+					if let Some(track) = self.track_mut(id) {
+						track.stop(tween);
+					}
+				}
+			},
 			MixerCommand::SetTrackRoutes {
 				from,
 				to,
@@ -78,6 +112,19 @@ impl Mixer {
 					track.set_route(to, volume, tween);
 				}
 			}
+			MixerCommand::AddTrackRoute {
+				from,
+				to,
+				volume,
+				send_point,
+				tween,
+				delay_line,
+			} => {
+				if let Some(track) = self.track_mut(from) {
+					track.add_route(to, volume, send_point, tween, delay_line);
+				}
+				self.routing_changed = true;
+			}
 		}
 	}
 
@@ -94,6 +141,100 @@ impl Mixer {
 			track.on_start_processing();
 		}
 		self.main_track.on_start_processing();
+		self.update_latency_compensation();
+	}
+
+	/// Delays the signals arriving at each track so that they line
+	/// up with the signal with the most latency.
+	fn update_latency_compensation(&mut self) {
+		self.routing_changed = false;
+		for (_, track) in &mut self.sub_tracks {
+			track.set_input_latency(0);
+		}
+		self.main_track.set_input_latency(0);
+		// going through the tracks in topological order, we always
+		// know the full latency of a track's inputs by the time we
+		// reach it
+		self.sort_sub_tracks();
+		for i in 0..self.sorted_sub_track_ids.len() {
+			let id = self.sorted_sub_track_ids[i];
+			let track = &self.sub_tracks[id.0];
+			let output_latency = track.output_latency();
+			for route_index in 0..track.routes().len() {
+				let destination_id = self.sub_tracks[id.0].routes()[route_index].0;
+				if let Some(destination_track) = self.track_mut(destination_id) {
+					if output_latency > destination_track.input_latency() {
+						destination_track.set_input_latency(output_latency);
+					}
+				}
+			}
+		}
+		for i in 0..self.sub_track_ids.len() {
+			let id = self.sub_track_ids[i];
+			let track = &self.sub_tracks[id.0];
+			let output_latency = track.output_latency();
+			for route_index in 0..track.routes().len() {
+				let destination_id = self.sub_tracks[id.0].routes()[route_index].0;
+				let destination_latency = match destination_id {
+					TrackId::Main => Some(&self.main_track),
+					TrackId::Sub(id) => self.sub_tracks.get(id.0),
+				}
+				.map_or(output_latency, Track::input_latency);
+				self.sub_tracks[id.0].route_delays_mut()[route_index]
+					.set_delay(destination_latency.saturating_sub(output_latency));
+			}
+		}
+	}
+
+	/// Sorts the sub-tracks so that every track comes before the
+	/// tracks it routes to.
+	///
+	/// The track handles don't allow adding routes that create
+	/// cycles, but if there are any, the tracks in them are put
+	/// at the end in order from newest to oldest.
+	fn sort_sub_tracks(&mut self) {
+		for (_, track) in &mut self.sub_tracks {
+			*track.unsorted_inputs_mut() = 0;
+		}
+		for i in 0..self.sub_track_ids.len() {
+			let id = self.sub_track_ids[i];
+			for route_index in 0..self.sub_tracks[id.0].routes().len() {
+				if let TrackId::Sub(destination_id) = self.sub_tracks[id.0].routes()[route_index].0
+				{
+					if let Some(destination_track) = self.sub_tracks.get_mut(destination_id.0) {
+						*destination_track.unsorted_inputs_mut() += 1;
+					}
+				}
+			}
+		}
+		self.sorted_sub_track_ids.clear();
+		for id in &self.sub_track_ids {
+			if *self.sub_tracks[id.0].unsorted_inputs_mut() == 0 {
+				self.sorted_sub_track_ids.push(*id);
+			}
+		}
+		let mut i = 0;
+		while i < self.sorted_sub_track_ids.len() {
+			let id = self.sorted_sub_track_ids[i];
+			for route_index in 0..self.sub_tracks[id.0].routes().len() {
+				if let TrackId::Sub(destination_id) = self.sub_tracks[id.0].routes()[route_index].0
+				{
+					if let Some(destination_track) = self.sub_tracks.get_mut(destination_id.0) {
+						let unsorted_inputs = destination_track.unsorted_inputs_mut();
+						*unsorted_inputs -= 1;
+						if *unsorted_inputs == 0 {
+							self.sorted_sub_track_ids.push(destination_id);
+						}
+					}
+				}
+			}
+			i += 1;
+		}
+		for id in self.sub_track_ids.iter().rev() {
+			if *self.sub_tracks[id.0].unsorted_inputs_mut() > 0 {
+				self.sorted_sub_track_ids.push(*id);
+			}
+		}
 	}
 
 	fn remove_unused_tracks(&mut self) {
@@ -101,16 +242,23 @@ impl Mixer {
 		while i < self.sub_track_ids.len() && !self.unused_track_producer.is_full() {
 			let id = self.sub_track_ids[i];
 			let track = &mut self.sub_tracks[id.0];
-			if track.shared().is_marked_for_removal() {
-				if self
-					.unused_track_producer
-					.push(
-						self.sub_tracks
-							.remove(id.0)
-							.unwrap_or_else(|| panic!("Sub track with ID {:?} does not exist", id)),
-					)
-					.is_err()
-				{
+			// if the track is still fading out, wait for the fade
+			// to finish before cutting off its input
+			let should_remove = if track.shared().is_marked_for_removal() && !track.is_stopping() {
+				// let the track's effects ring out before removing it
+				// so reverb and delay tails aren't cut off
+				track.stop_receiving_input();
+				track.is_idle()
+			} else {
+				false
+			};
+			if should_remove {
+				let mut track = self
+					.sub_tracks
+					.remove(id.0)
+					.unwrap_or_else(|| panic!("Sub track with ID {:?} does not exist", id));
+				track.on_removed();
+				if self.unused_track_producer.push(track).is_err() {
 					panic!("Unused track producer is full")
 				}
 				self.sub_track_ids.remove(i);
@@ -121,26 +269,44 @@ impl Mixer {
 	}
 
 	pub fn process(&mut self, dt: f64) -> Frame {
-		// iterate through the sub-tracks newest to oldest
-		for id in self.sub_track_ids.iter().rev() {
+		// tracks or routes added since the start of this batch
+		// of samples need to be sorted before processing
+		if self.routing_changed {
+			self.update_latency_compensation();
+		}
+		// process each track before the tracks it routes to
+		for id in &self.sorted_sub_track_ids {
 			// process the track and get its output
 			let track = self
 				.sub_tracks
 				.get_mut(id.0)
 				.expect("sub track IDs and sub tracks are out of sync");
-			let output = track.process(dt);
+			let post_fader_output = track.process(dt);
+			let pre_fader_output = track.pre_fader_output();
 			// temporarily take ownership of its routes. we can't just
 			// borrow the routes because then we can't get mutable
 			// references to the other tracks
 			std::mem::swap(track.routes_mut(), &mut self.dummy_routes);
+			std::mem::swap(track.route_delays_mut(), &mut self.dummy_route_delays);
 			// send the output to the destination tracks
-			for (id, amount) in &self.dummy_routes {
+			for ((id, amount, send_point), delay) in self
+				.dummy_routes
+				.iter()
+				.zip(self.dummy_route_delays.iter_mut())
+			{
+				// delay the output to line up with the other
+				// signals arriving at the destination track
+				let output = delay.process(match send_point {
+					SendPoint::PreFader => pre_fader_output,
+					SendPoint::PostFader => post_fader_output,
+				});
 				let destination_track = match id {
 					TrackId::Main => Some(&mut self.main_track),
 					TrackId::Sub(id) => self.sub_tracks.get_mut(id.0),
 				};
 				if let Some(destination_track) = destination_track {
-					destination_track.add_input(output * amount.value().as_amplitude() as f32);
+					destination_track
+						.add_routed_input(output * amount.value().as_amplitude() as f32);
 				}
 			}
 			// borrow the track again and give it back its routes
@@ -149,6 +315,7 @@ impl Mixer {
 				.get_mut(id.0)
 				.expect("sub track IDs and sub tracks are out of sync");
 			std::mem::swap(track.routes_mut(), &mut self.dummy_routes);
+			std::mem::swap(track.route_delays_mut(), &mut self.dummy_route_delays);
 		}
 		self.main_track.process(dt)
 	}
@@ -1,9 +1,17 @@
+use std::time::Duration;
+
 use ringbuf::RingBuffer;
 
 use crate::{
 	dsp::Frame,
 	manager::command::MixerCommand,
-	track::{SubTrackId, Track, TrackBuilder, TrackRoutes},
+	track::TrackId,
+	track::{
+		effect::{delay::DelayBuilder, Effect, EffectBuilder},
+		DelayLine, SendPoint, SubTrackId, Track, TrackBuilder, TrackRoutes,
+	},
+	tween::Tween,
+	Volume,
 };
 
 use super::Mixer;
@@ -11,7 +19,13 @@ use super::Mixer;
 #[test]
 fn parent_routing() {
 	let (unused_sub_track_producer, _) = RingBuffer::new(1).split();
-	let mut mixer = Mixer::new(100, unused_sub_track_producer, 1, TrackBuilder::new());
+	let mut mixer = Mixer::new(
+		100,
+		unused_sub_track_producer,
+		1,
+		TrackBuilder::new(),
+		Some(0),
+	);
 	let sub_track_controller = mixer.sub_track_controller();
 	let parent_track_id = SubTrackId(sub_track_controller.try_reserve().unwrap());
 	mixer.run_command(MixerCommand::AddSubTrack(
@@ -35,7 +49,13 @@ fn parent_routing() {
 #[test]
 fn send_routing() {
 	let (unused_sub_track_producer, _) = RingBuffer::new(1).split();
-	let mut mixer = Mixer::new(100, unused_sub_track_producer, 1, TrackBuilder::new());
+	let mut mixer = Mixer::new(
+		100,
+		unused_sub_track_producer,
+		1,
+		TrackBuilder::new(),
+		Some(0),
+	);
 	let sub_track_controller = mixer.sub_track_controller();
 	let send_track_id = SubTrackId(sub_track_controller.try_reserve().unwrap());
 	mixer.run_command(MixerCommand::AddSubTrack(
@@ -53,3 +73,251 @@ fn send_routing() {
 		.add_input(Frame::from_mono(1.0));
 	assert_eq!(mixer.process(1.0), Frame::from_mono(1.25));
 }
+
+/// Tests that a sub-track marked for removal isn't removed
+/// until its effects have finished ringing out.
+#[test]
+fn waits_for_tails_before_removing_tracks() {
+	let (unused_sub_track_producer, unused_sub_track_consumer) = RingBuffer::new(1).split();
+	let mut mixer = Mixer::new(
+		100,
+		unused_sub_track_producer,
+		1,
+		TrackBuilder::new(),
+		Some(0),
+	);
+	let sub_track_controller = mixer.sub_track_controller();
+	let track_id = SubTrackId(sub_track_controller.try_reserve().unwrap());
+	let track = Track::new({
+		let mut builder = TrackBuilder::new();
+		builder.add_effect(
+			DelayBuilder::new()
+				.delay_time(3.0)
+				.buffer_length(4.0)
+				.feedback(Volume::Amplitude(0.0))
+				.mix(1.0),
+		);
+		builder
+	});
+	let shared = track.shared();
+	mixer.run_command(MixerCommand::AddSubTrack(track_id, track));
+	mixer.on_start_processing();
+	mixer
+		.track_mut(track_id.into())
+		.unwrap()
+		.add_input(Frame::from_mono(1.0));
+	mixer.process(1.0);
+	shared.mark_for_removal();
+	// the echo hasn't been heard yet, so the track should be kept
+	mixer.on_start_processing();
+	assert!(unused_sub_track_consumer.is_empty());
+	// input sent after the track is marked for removal is ignored
+	mixer
+		.track_mut(track_id.into())
+		.unwrap()
+		.add_input(Frame::from_mono(1.0));
+	let output = (0..12).map(|_| mixer.process(1.0)).collect::<Vec<_>>();
+	assert_eq!(
+		output
+			.iter()
+			.filter(|frame| frame.left.abs() > 1.0e-6)
+			.count(),
+		1
+	);
+	mixer.on_start_processing();
+	assert_eq!(unused_sub_track_consumer.len(), 1);
+}
+
+/// Tests that a stopped sub-track fades out and is only
+/// removed after the fade finishes.
+#[test]
+fn stop_fades_out_before_removing_track() {
+	let (unused_sub_track_producer, unused_sub_track_consumer) = RingBuffer::new(1).split();
+	let mut mixer = Mixer::new(
+		100,
+		unused_sub_track_producer,
+		1,
+		TrackBuilder::new(),
+		Some(0),
+	);
+	let sub_track_controller = mixer.sub_track_controller();
+	let track_id = SubTrackId(sub_track_controller.try_reserve().unwrap());
+	mixer.run_command(MixerCommand::AddSubTrack(
+		track_id,
+		Track::new(TrackBuilder::new()),
+	));
+	mixer.run_command(MixerCommand::StopTrack(
+		track_id.into(),
+		Tween {
+			duration: Duration::from_secs(2),
+			..Default::default()
+		},
+	));
+	// the track should still receive input while it's fading out
+	mixer.on_start_processing();
+	mixer
+		.track_mut(track_id.into())
+		.unwrap()
+		.add_input(Frame::from_mono(1.0));
+	let output = mixer.process(1.0);
+	assert!(output.left > 0.0 && output.left < 1.0);
+	mixer.on_start_processing();
+	assert!(unused_sub_track_consumer.is_empty());
+	// the fade finishes on this frame
+	mixer.process(1.0);
+	mixer.on_start_processing();
+	assert_eq!(unused_sub_track_consumer.len(), 1);
+}
+
+/// Tests that signals going through effects with latency stay
+/// aligned with dry copies of the same signal.
+#[test]
+fn compensates_for_effect_latency() {
+	let (unused_sub_track_producer, _) = RingBuffer::new(1).split();
+	let mut mixer = Mixer::new(
+		100,
+		unused_sub_track_producer,
+		1,
+		TrackBuilder::new(),
+		Some(0),
+	);
+	let sub_track_controller = mixer.sub_track_controller();
+	// a bus with no latency of its own that a slow track and
+	// a dry signal are both sent to
+	let bus_id = SubTrackId(sub_track_controller.try_reserve().unwrap());
+	mixer.run_command(MixerCommand::AddSubTrack(
+		bus_id,
+		Track::new(TrackBuilder::new()),
+	));
+	let slow_track_id = SubTrackId(sub_track_controller.try_reserve().unwrap());
+	mixer.run_command(MixerCommand::AddSubTrack(
+		slow_track_id,
+		Track::new({
+			let mut builder =
+				TrackBuilder::new().routes(TrackRoutes::empty().with_route(bus_id, 1.0));
+			builder.add_effect(LatencyEffect::new(2));
+			builder
+		}),
+	));
+	mixer.on_start_processing();
+
+	// send an impulse through the slow track, the bus, and
+	// the main track at the same time
+	mixer
+		.track_mut(slow_track_id.into())
+		.unwrap()
+		.add_input(Frame::from_mono(1.0));
+	mixer
+		.track_mut(bus_id.into())
+		.unwrap()
+		.add_input(Frame::from_mono(1.0));
+	mixer
+		.track_mut(TrackId::Main)
+		.unwrap()
+		.add_input(Frame::from_mono(1.0));
+	let output: Vec<Frame> = (0..5).map(|_| mixer.process(1.0)).collect();
+	assert_eq!(
+		output,
+		vec![
+			Frame::ZERO,
+			Frame::ZERO,
+			Frame::from_mono(3.0),
+			Frame::ZERO,
+			Frame::ZERO,
+		]
+	);
+}
+
+/// Tests that latency is compensated for when a track routes
+/// to a track that was created after it.
+#[test]
+fn compensates_for_latency_of_routes_to_newer_tracks() {
+	let (unused_sub_track_producer, _) = RingBuffer::new(1).split();
+	let mut mixer = Mixer::new(
+		100,
+		unused_sub_track_producer,
+		1,
+		TrackBuilder::new(),
+		Some(0),
+	);
+	let sub_track_controller = mixer.sub_track_controller();
+	let slow_track_id = SubTrackId(sub_track_controller.try_reserve().unwrap());
+	mixer.run_command(MixerCommand::AddSubTrack(
+		slow_track_id,
+		Track::new({
+			let mut builder = TrackBuilder::new().routes(TrackRoutes::empty());
+			builder.add_effect(LatencyEffect::new(2));
+			builder
+		}),
+	));
+	let bus_id = SubTrackId(sub_track_controller.try_reserve().unwrap());
+	mixer.run_command(MixerCommand::AddSubTrack(
+		bus_id,
+		Track::new(TrackBuilder::new()),
+	));
+	mixer.run_command(MixerCommand::AddTrackRoute {
+		from: slow_track_id.into(),
+		to: bus_id.into(),
+		volume: Volume::Amplitude(1.0),
+		send_point: SendPoint::PostFader,
+		tween: Tween::default(),
+		delay_line: Some(DelayLine::new()),
+	});
+	mixer.on_start_processing();
+
+	mixer
+		.track_mut(slow_track_id.into())
+		.unwrap()
+		.add_input(Frame::from_mono(1.0));
+	mixer
+		.track_mut(bus_id.into())
+		.unwrap()
+		.add_input(Frame::from_mono(1.0));
+	mixer
+		.track_mut(TrackId::Main)
+		.unwrap()
+		.add_input(Frame::from_mono(1.0));
+	let output: Vec<Frame> = (0..5).map(|_| mixer.process(1.0)).collect();
+	assert_eq!(
+		output,
+		vec![
+			Frame::ZERO,
+			Frame::ZERO,
+			Frame::from_mono(3.0),
+			Frame::ZERO,
+			Frame::ZERO,
+		]
+	);
+}
+
+/// Delays its input by a fixed number of frames.
+struct LatencyEffect {
+	buffer: Vec<Frame>,
+}
+
+impl LatencyEffect {
+	fn new(latency: usize) -> Self {
+		Self {
+			buffer: vec![Frame::ZERO; latency],
+		}
+	}
+}
+
+impl EffectBuilder for LatencyEffect {
+	type Handle = ();
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		(Box::new(self), ())
+	}
+}
+
+impl Effect for LatencyEffect {
+	fn process(&mut self, input: Frame, _dt: f64) -> Frame {
+		self.buffer.push(input);
+		self.buffer.remove(0)
+	}
+
+	fn latency_frames(&self) -> usize {
+		self.buffer.len()
+	}
+}
@@ -1,20 +1,35 @@
 use atomic_arena::{Arena, Controller};
 use ringbuf::Producer;
 
-use crate::{clock::ClockTime, manager::command::SoundCommand, sound::Sound};
+use std::time::Duration;
+
+use crate::{
+	clock::ClockTime, manager::command::SoundCommand, sound::Sound, track::TrackId, tween::Tween,
+};
 
 use super::mixer::Mixer;
 
 pub(crate) struct Sounds {
 	sounds: Arena<Box<dyn Sound>>,
 	unused_sound_producer: Producer<Box<dyn Sound>>,
+	unused_arena_producer: Producer<Arena<Box<dyn Sound>>>,
+	/// Tracks whose sounds have been paused. New sounds
+	/// on these tracks start out paused.
+	paused_tracks: Vec<TrackId>,
 }
 
 impl Sounds {
-	pub fn new(capacity: usize, unused_sound_producer: Producer<Box<dyn Sound>>) -> Self {
+	pub fn new(
+		capacity: usize,
+		unused_sound_producer: Producer<Box<dyn Sound>>,
+		unused_arena_producer: Producer<Arena<Box<dyn Sound>>>,
+		track_capacity: usize,
+	) -> Self {
 		Self {
 			sounds: Arena::new(capacity),
 			unused_sound_producer,
+			unused_arena_producer,
+			paused_tracks: Vec::with_capacity(track_capacity),
 		}
 	}
 
@@ -29,6 +44,23 @@ impl Sounds {
 		self.remove_unused_sounds();
 	}
 
+	/// Tells each track how many sounds are playing on it.
+	pub fn count_sounds_on_tracks(&mut self, mixer: &mut Mixer) {
+		for (_, sound) in &mut self.sounds {
+			if let Some(track) = mixer.track_mut(sound.track()) {
+				track.count_sound();
+			}
+		}
+	}
+
+	/// Forgets the paused state of tracks that have been removed
+	/// from the mixer so the list never grows past the number of
+	/// tracks.
+	pub fn forget_removed_tracks(&mut self, mixer: &mut Mixer) {
+		self.paused_tracks
+			.retain(|track| mixer.track_mut(*track).is_some());
+	}
+
 	fn remove_unused_sounds(&mut self) {
 		if self.unused_sound_producer.is_full() {
 			return;
@@ -45,10 +77,56 @@ impl Sounds {
 
 	pub fn run_command(&mut self, command: SoundCommand) {
 		match command {
-			SoundCommand::Add(key, sound) => self
-				.sounds
-				.insert_with_key(key, sound)
-				.expect("Sound arena is full"),
+			SoundCommand::Add(key, mut sound) => {
+				if self.paused_tracks.contains(&sound.track()) {
+					sound.pause(Tween {
+						duration: Duration::ZERO,
+						..Default::default()
+					});
+				}
+				self.sounds
+					.insert_with_key(key, sound)
+					.expect("Sound arena is full")
+			}
+			SoundCommand::Grow(arena) => self.grow(arena),
+			SoundCommand::PauseTrack(track, tween) => {
+				if !self.paused_tracks.contains(&track) {
+					self.paused_tracks.push(track);
+				}
+				self.for_each_sound_on_track(track, |sound| sound.pause(tween));
+			}
+			SoundCommand::ResumeTrack(track, tween) => {
+				self.paused_tracks
+					.retain(|paused_track| *paused_track != track);
+				self.for_each_sound_on_track(track, |sound| sound.resume(tween));
+			}
+			SoundCommand::StopTrack(track, tween) => {
+				self.for_each_sound_on_track(track, |sound| sound.stop(tween));
+			}
+		}
+	}
+
+	fn for_each_sound_on_track(&mut self, track: TrackId, mut f: impl FnMut(&mut Box<dyn Sound>)) {
+		for (_, sound) in &mut self.sounds {
+			if sound.track() == track {
+				f(sound);
+			}
+		}
+	}
+
+	fn grow(&mut self, arena: Arena<Box<dyn Sound>>) {
+		// the new arena is allocated on the main thread, so moving
+		// the sounds into it doesn't allocate on the audio thread
+		let mut old_arena = std::mem::replace(&mut self.sounds, arena);
+		for (_, sound) in old_arena.drain_filter(|_| true) {
+			self.sounds
+				.insert(sound)
+				.expect("New sound arena is too small");
+		}
+		// send the old arena back to the main thread so it's
+		// deallocated there
+		if self.unused_arena_producer.push(old_arena).is_err() {
+			panic!("Unused sound arena producer is full")
 		}
 	}
 
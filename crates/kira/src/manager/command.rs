@@ -1,28 +1,47 @@
 pub mod producer;
 
-use atomic_arena::Key;
+use atomic_arena::{Arena, Key};
 
 use crate::{
 	clock::{Clock, ClockId},
+	modulator::{Modulator, ModulatorId},
 	sound::Sound,
-	track::{SubTrackId, Track, TrackId},
+	track::{DelayLine, SendPoint, SubTrackId, Track, TrackId},
 	tween::Tween,
 	ClockSpeed, Volume,
 };
 
 pub(crate) enum SoundCommand {
 	Add(Key, Box<dyn Sound>),
+	/// Moves all of the sounds into a new, larger arena.
+	Grow(Arena<Box<dyn Sound>>),
+	/// Pauses all of the sounds playing on a track.
+	PauseTrack(TrackId, Tween),
+	/// Resumes all of the sounds playing on a track.
+	ResumeTrack(TrackId, Tween),
+	/// Stops all of the sounds playing on a track.
+	StopTrack(TrackId, Tween),
 }
 
 pub(crate) enum MixerCommand {
 	AddSubTrack(SubTrackId, Track),
 	SetTrackVolume(TrackId, Volume, Tween),
+	/// Fades out a track's volume and then removes it.
+	StopTrack(TrackId, Tween),
 	SetTrackRoutes {
 		from: TrackId,
 		to: TrackId,
 		volume: Volume,
 		tween: Tween,
 	},
+	AddTrackRoute {
+		from: TrackId,
+		to: TrackId,
+		volume: Volume,
+		send_point: SendPoint,
+		tween: Tween,
+		delay_line: Option<DelayLine>,
+	},
 }
 
 pub(crate) enum ClockCommand {
@@ -33,10 +52,16 @@ pub(crate) enum ClockCommand {
 	Stop(ClockId),
 }
 
+pub(crate) enum ModulatorCommand {
+	Add(ModulatorId, Box<dyn Modulator>),
+}
+
 pub(crate) enum Command {
 	Sound(SoundCommand),
 	Mixer(MixerCommand),
 	Clock(ClockCommand),
+	Modulator(ModulatorCommand),
 	Pause(Tween),
 	Resume(Tween),
+	SetMuffle(f64, Tween),
 }
@@ -48,6 +48,43 @@ impl<E> From<CommandError> for PlaySoundError<E> {
 	}
 }
 
+/// Errors that can occur when reserving space for more sounds.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReserveSoundsError {
+	/// Could not reserve space for more sounds because the audio thread
+	/// has not finished using the space from the previous reservation.
+	ReservationInProgress,
+	/// An error occured when sending a command to the audio thread.
+	CommandError(CommandError),
+}
+
+impl Display for ReserveSoundsError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ReserveSoundsError::ReservationInProgress => f.write_str(
+				"Could not reserve space for more sounds because the audio thread has not finished using the space from the previous reservation.",
+			),
+			ReserveSoundsError::CommandError(error) => error.fmt(f),
+		}
+	}
+}
+
+impl Error for ReserveSoundsError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			ReserveSoundsError::CommandError(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<CommandError> for ReserveSoundsError {
+	fn from(v: CommandError) -> Self {
+		Self::CommandError(v)
+	}
+}
+
 /// Errors that can occur when creating a mixer sub-track.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -88,6 +125,9 @@ impl From<CommandError> for AddSubTrackError {
 pub enum AddClockError {
 	/// Could not add a clock because the maximum number of clocks has been reached.
 	ClockLimitReached,
+	/// Could not add a clock because clocks were disabled by setting
+	/// [`Capacities::clock_capacity`](super::Capacities::clock_capacity) to 0.
+	ClocksDisabled,
 	/// An error occured when sending a command to the audio thread.
 	CommandError(CommandError),
 }
@@ -98,6 +138,9 @@ impl Display for AddClockError {
 			AddClockError::ClockLimitReached => f.write_str(
 				"Could not add a clock because the maximum number of clocks has been reached.",
 			),
+			AddClockError::ClocksDisabled => f.write_str(
+				"Could not add a clock because clocks were disabled by setting the clock capacity to 0.",
+			),
 			AddClockError::CommandError(error) => error.fmt(f),
 		}
 	}
@@ -117,3 +160,45 @@ impl From<CommandError> for AddClockError {
 		Self::CommandError(v)
 	}
 }
+
+/// Errors that can occur when creating a modulator.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AddModulatorError {
+	/// Could not add a modulator because the maximum number of modulators has been reached.
+	ModulatorLimitReached,
+	/// Could not add a modulator because modulators were disabled by setting
+	/// [`Capacities::modulator_capacity`](super::Capacities::modulator_capacity) to 0.
+	ModulatorsDisabled,
+	/// An error occured when sending a command to the audio thread.
+	CommandError(CommandError),
+}
+
+impl Display for AddModulatorError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			AddModulatorError::ModulatorLimitReached => f.write_str(
+				"Could not add a modulator because the maximum number of modulators has been reached.",
+			),
+			AddModulatorError::ModulatorsDisabled => f.write_str(
+				"Could not add a modulator because modulators were disabled by setting the modulator capacity to 0.",
+			),
+			AddModulatorError::CommandError(error) => error.fmt(f),
+		}
+	}
+}
+
+impl Error for AddModulatorError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			AddModulatorError::CommandError(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<CommandError> for AddModulatorError {
+	fn from(v: CommandError) -> Self {
+		Self::CommandError(v)
+	}
+}
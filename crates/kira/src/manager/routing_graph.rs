@@ -0,0 +1,44 @@
+use crate::{track::TrackId, Volume};
+
+/// A snapshot of the mixer tracks and how audio flows
+/// between them.
+///
+/// This is meant for debugging, for example to find out why
+/// a sound can't be heard. Returned by
+/// [`AudioManager::routing_snapshot`](super::AudioManager::routing_snapshot).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutingGraph {
+	/// The main track followed by the sub-tracks, from
+	/// oldest to newest.
+	pub tracks: Vec<TrackSnapshot>,
+}
+
+impl RoutingGraph {
+	/// Returns the snapshot of the track with the given ID,
+	/// if it exists.
+	pub fn track(&self, id: impl Into<TrackId>) -> Option<&TrackSnapshot> {
+		let id = id.into();
+		self.tracks.iter().find(|track| track.id == id)
+	}
+}
+
+/// The state of a single mixer track in a [`RoutingGraph`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct TrackSnapshot {
+	/// The ID of the track.
+	pub id: TrackId,
+	/// The current volume of the track.
+	pub volume: Volume,
+	/// The tracks this track's output is sent to, along with
+	/// the current volume of each route.
+	///
+	/// A sub-track with no routes can't be heard. The main
+	/// track's output goes straight to the backend, so it never
+	/// has any routes.
+	pub routes: Vec<(TrackId, Volume)>,
+	/// The names of the effects on the track, in processing order.
+	pub effect_names: Vec<&'static str>,
+	/// The number of sounds playing on the track.
+	pub num_sounds: usize,
+}
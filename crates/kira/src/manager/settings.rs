@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::track::TrackBuilder;
 
 use super::backend::Backend;
@@ -16,7 +18,14 @@ pub struct Capacities {
 	/// The maximum number of mixer sub-tracks that can exist at a time.
 	pub sub_track_capacity: usize,
 	/// The maximum number of clocks that can exist at a time.
+	///
+	/// Setting this to 0 disables clocks, which saves a small
+	/// amount of memory for apps that don't need them.
 	pub clock_capacity: usize,
+	/// The maximum number of modulators that can exist at a time.
+	///
+	/// Setting this to 0 disables modulators.
+	pub modulator_capacity: usize,
 }
 
 impl Default for Capacities {
@@ -26,6 +35,7 @@ impl Default for Capacities {
 			sound_capacity: 128,
 			sub_track_capacity: 128,
 			clock_capacity: 8,
+			modulator_capacity: 16,
 		}
 	}
 }
@@ -39,6 +49,38 @@ pub struct AudioManagerSettings<B: Backend> {
 	pub main_track_builder: TrackBuilder,
 	/// Configures the backend.
 	pub backend_settings: B::Settings,
+	/// The seed used to generate randomness for effects.
+	///
+	/// If this is `None`, a seed will be chosen by the operating
+	/// system. Setting a seed makes effects that use randomness
+	/// produce the same output every time, which is useful for
+	/// testing.
+	pub random_seed: Option<u64>,
+	/// How long to fade in the audio output when the backend starts
+	/// and fade it out when the [`AudioManager`](super::AudioManager)
+	/// is dropped.
+	///
+	/// Jumping straight from silence to full volume (or back) can
+	/// cause an audible click on some audio hardware. Set this to
+	/// [`Duration::ZERO`] to disable the fades, for example when
+	/// calling [`Renderer::process`](super::backend::Renderer::process)
+	/// manually in tests.
+	///
+	/// Dropping the [`AudioManager`](super::AudioManager) doesn't block.
+	/// Backends that render audio on another thread, like the cpal
+	/// backend on desktop, keep the output running in the background
+	/// until the fade-out is finished. Other backends, including the
+	/// mock backend and the cpal backend on wasm32 targets, stop
+	/// right away, so only the fade-in is applied.
+	pub start_stop_fade_duration: Duration,
+	/// Whether to remove DC offset from the final audio output.
+	///
+	/// Some effects, like asymmetric distortion, can push the
+	/// average level of a signal away from zero. This offset is
+	/// inaudible, but it wastes headroom and can damage speakers.
+	/// When enabled, a gentle high-pass filter at 5 Hz is applied
+	/// to the output of the main mixer track.
+	pub dc_blocker: bool,
 }
 
 impl<B: Backend> Default for AudioManagerSettings<B>
@@ -50,6 +92,9 @@ where
 			capacities: Capacities::default(),
 			main_track_builder: TrackBuilder::default(),
 			backend_settings: B::Settings::default(),
+			random_seed: None,
+			start_stop_fade_duration: Duration::from_millis(5),
+			dc_blocker: false,
 		}
 	}
 }
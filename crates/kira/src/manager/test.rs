@@ -0,0 +1,769 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+	dsp::{Frame, Random},
+	modulator::lfo::LfoBuilder,
+	sound::static_sound::{PlaybackState, StaticSoundData, StaticSoundSettings},
+	track::{
+		effect::{
+			delay::DelayBuilder, filter::FilterBuilder, panning_control::PanningControlBuilder,
+			Effect, EffectBuilder,
+		},
+		AddRouteError, SendPoint, SetRouteError, TrackBuilder, TrackId, TrackRoutes,
+	},
+	tween::Tween,
+	ClockSpeed, CommandError, Volume,
+};
+
+use super::{
+	backend::mock::{MockBackend, MockBackendSettings},
+	error::{AddClockError, AddModulatorError, PlaySoundError, ReserveSoundsError},
+	AudioManager, AudioManagerSettings, Capacities,
+};
+
+/// Tests that a detached sound plays to completion and
+/// is removed when it finishes.
+#[test]
+fn play_detached() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	manager
+		.play_detached(StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new(vec![Frame::from_mono(1.0); 3]),
+			settings: StaticSoundSettings::new(),
+		})
+		.unwrap();
+	manager.backend_mut().on_start_processing();
+	assert_eq!(manager.num_sounds(), 1);
+	for _ in 0..3 {
+		assert_eq!(
+			manager.backend_mut().process(),
+			Frame::from_mono(1.0).panned(0.5)
+		);
+	}
+	for _ in 0..10 {
+		manager.backend_mut().process();
+	}
+	manager.backend_mut().on_start_processing();
+	assert_eq!(manager.num_sounds(), 0);
+}
+
+/// Tests that dropping a sound handle does not stop the sound.
+#[test]
+fn dropping_handle_does_not_stop_sound() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let handle = manager
+		.play(StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+			settings: StaticSoundSettings::new(),
+		})
+		.unwrap();
+	drop(handle);
+	manager.backend_mut().on_start_processing();
+	for _ in 0..3 {
+		assert_eq!(
+			manager.backend_mut().process(),
+			Frame::from_mono(1.0).panned(0.5)
+		);
+	}
+	manager.backend_mut().on_start_processing();
+	assert_eq!(manager.num_sounds(), 1);
+}
+
+/// Tests that more sounds can be played after reserving space for them.
+#[test]
+fn reserve_sounds() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		capacities: Capacities {
+			sound_capacity: 1,
+			..Default::default()
+		},
+		..Default::default()
+	})
+	.unwrap();
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(0.5); 10]),
+		settings: StaticSoundSettings::new(),
+	};
+	manager.play_detached(data.clone()).unwrap();
+	assert!(matches!(
+		manager.play_detached(data.clone()),
+		Err(PlaySoundError::SoundLimitReached)
+	));
+	manager.reserve_sounds(1).unwrap();
+	assert_eq!(manager.sound_capacity(), 2);
+	assert_eq!(manager.num_sounds(), 1);
+	assert!(matches!(
+		manager.reserve_sounds(1),
+		Err(ReserveSoundsError::ReservationInProgress)
+	));
+	manager.play_detached(data.clone()).unwrap();
+	assert_eq!(manager.num_sounds(), 2);
+	assert!(matches!(
+		manager.play_detached(data),
+		Err(PlaySoundError::SoundLimitReached)
+	));
+	manager.backend_mut().on_start_processing();
+	assert_eq!(manager.num_sounds(), 2);
+	assert_eq!(
+		manager.backend_mut().process(),
+		Frame::from_mono(1.0).panned(0.5)
+	);
+	manager.reserve_sounds(1).unwrap();
+	assert_eq!(manager.sound_capacity(), 3);
+	// reserving space that already exists does nothing
+	manager.backend_mut().on_start_processing();
+	manager.reserve_sounds(1).unwrap();
+	assert_eq!(manager.sound_capacity(), 3);
+}
+
+/// Outputs white noise using the random seed it's given.
+struct NoiseEffect {
+	random: Random,
+}
+
+impl Effect for NoiseEffect {
+	fn set_random_seed(&mut self, seed: u64) {
+		self.random = Random::new(seed);
+	}
+
+	fn process(&mut self, _input: Frame, _dt: f64) -> Frame {
+		Frame::from_mono(self.random.next_f32())
+	}
+
+	fn tail_frames(&self) -> usize {
+		usize::MAX
+	}
+}
+
+struct NoiseEffectBuilder;
+
+impl EffectBuilder for NoiseEffectBuilder {
+	type Handle = ();
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		(
+			Box::new(NoiseEffect {
+				random: Random::new(0),
+			}),
+			(),
+		)
+	}
+}
+
+fn render_noise(random_seed: u64) -> Vec<Frame> {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		main_track_builder: {
+			let mut builder = TrackBuilder::new();
+			builder.add_effect(NoiseEffectBuilder);
+			builder
+		},
+		random_seed: Some(random_seed),
+		..Default::default()
+	})
+	.unwrap();
+	manager
+		.add_sub_track({
+			let mut builder = TrackBuilder::new();
+			builder.add_effect(NoiseEffectBuilder);
+			builder
+		})
+		.unwrap();
+	manager.backend_mut().on_start_processing();
+	(0..100).map(|_| manager.backend_mut().process()).collect()
+}
+
+/// Tests that effects produce the same output every time
+/// when the same random seed is used.
+#[test]
+fn random_seed_is_deterministic() {
+	assert_eq!(render_noise(1), render_noise(1));
+	assert_ne!(render_noise(1), render_noise(2));
+}
+
+/// Tests that clocks and modulators can be disabled by
+/// setting their capacities to 0.
+#[test]
+fn disabled_subsystems() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		capacities: Capacities {
+			clock_capacity: 0,
+			modulator_capacity: 0,
+			..Default::default()
+		},
+		..Default::default()
+	})
+	.unwrap();
+	manager.backend_mut().on_start_processing();
+	manager.backend_mut().process();
+	assert!(matches!(
+		manager.add_clock(ClockSpeed::TicksPerSecond(1.0)),
+		Err(AddClockError::ClocksDisabled)
+	));
+	assert!(matches!(
+		manager.add_modulator(LfoBuilder::new()),
+		Err(AddModulatorError::ModulatorsDisabled)
+	));
+}
+
+/// Tests that a group's volume is applied on top of the
+/// volume of the sounds in the group.
+#[test]
+fn group_volume() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let mut group = manager.add_group(0.5).unwrap();
+	manager
+		.play(StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+			settings: StaticSoundSettings::new().volume(0.5).track(&group),
+		})
+		.unwrap();
+	manager.backend_mut().on_start_processing();
+	assert_eq!(
+		manager.backend_mut().process(),
+		Frame::from_mono(0.25).panned(0.5)
+	);
+	group.set_volume(0.25, Tween::default()).unwrap();
+	manager.backend_mut().on_start_processing();
+	assert_eq!(
+		manager.backend_mut().process(),
+		Frame::from_mono(0.125).panned(0.5)
+	);
+}
+
+/// Tests that all of the sounds in a group can be paused,
+/// resumed, and stopped at once.
+#[test]
+#[allow(clippy::float_cmp)]
+fn group_pause_resume_stop() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let mut group = manager.add_group(1.0).unwrap();
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new().track(&group),
+	};
+	let sound = manager.play(data.clone()).unwrap();
+	manager.backend_mut().on_start_processing();
+	assert_eq!(
+		manager.backend_mut().process(),
+		Frame::from_mono(1.0).panned(0.5)
+	);
+
+	group.pause(Tween::default()).unwrap();
+	manager.backend_mut().on_start_processing();
+	// allow for a few samples of delay because of the resampling
+	for _ in 0..3 {
+		manager.backend_mut().process();
+	}
+	assert_eq!(sound.state(), PlaybackState::Paused);
+	assert_eq!(manager.backend_mut().process(), Frame::ZERO);
+
+	// sounds started while the group is paused should start out paused
+	let late_sound = manager.play(data).unwrap();
+	manager.backend_mut().on_start_processing();
+	for _ in 0..3 {
+		manager.backend_mut().process();
+	}
+	assert_eq!(late_sound.state(), PlaybackState::Paused);
+	assert_eq!(manager.backend_mut().process(), Frame::ZERO);
+
+	group.resume(Tween::default()).unwrap();
+	manager.backend_mut().on_start_processing();
+	manager.backend_mut().process();
+	assert_eq!(sound.state(), PlaybackState::Playing);
+	assert_eq!(late_sound.state(), PlaybackState::Playing);
+
+	group.stop(Tween::default()).unwrap();
+	manager.backend_mut().on_start_processing();
+	manager.backend_mut().process();
+	assert_eq!(sound.state(), PlaybackState::Stopped);
+	assert_eq!(late_sound.state(), PlaybackState::Stopped);
+}
+
+/// Tests that handles report when their resources are
+/// no longer alive.
+#[test]
+fn handles_report_liveness() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let sound = manager
+		.play(StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+			settings: StaticSoundSettings::new().track(&track),
+		})
+		.unwrap();
+	assert!(track.is_alive());
+	assert!(sound.is_alive());
+	track.stop(Tween::default()).unwrap();
+	manager.backend_mut().on_start_processing();
+	for _ in 0..3 {
+		manager.backend_mut().process();
+	}
+	assert!(!track.is_alive());
+	assert!(!sound.is_alive());
+}
+
+/// Tests that sending commands to removed resources fails
+/// with `CommandError::ResourceRemoved`.
+#[test]
+fn commands_to_removed_resources_fail() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut sound = manager
+		.play(StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+			settings: StaticSoundSettings::new().track(&track),
+		})
+		.unwrap();
+	track.stop(Tween::default()).unwrap();
+	manager.backend_mut().on_start_processing();
+	for _ in 0..3 {
+		manager.backend_mut().process();
+	}
+	assert!(matches!(
+		track.set_volume(0.5, Tween::default()),
+		Err(CommandError::ResourceRemoved)
+	));
+	assert!(matches!(
+		sound.set_volume(0.5, Tween::default()),
+		Err(CommandError::ResourceRemoved)
+	));
+}
+
+/// Tests that sending commands to the effects of a removed
+/// track, including the feedback effects of a delay, fails
+/// with `CommandError::ResourceRemoved`.
+#[test]
+fn commands_to_removed_effects_fail() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let mut track_builder = TrackBuilder::new();
+	let mut filter = track_builder.add_effect(FilterBuilder::new());
+	let mut delay_builder = DelayBuilder::new().buffer_length(2.0);
+	let mut feedback_filter = delay_builder.add_feedback_effect(FilterBuilder::new());
+	let mut delay = track_builder.add_effect(delay_builder);
+	let track = manager.add_sub_track(track_builder).unwrap();
+	manager.backend_mut().on_start_processing();
+	manager.backend_mut().process();
+	assert!(filter.set_mix(0.5, Tween::default()).is_ok());
+	drop(track);
+	// wait for the delay to ring out so the track is removed
+	for _ in 0..5 {
+		manager.backend_mut().on_start_processing();
+		manager.backend_mut().process();
+	}
+	assert!(matches!(
+		filter.set_mix(0.5, Tween::default()),
+		Err(CommandError::ResourceRemoved)
+	));
+	assert!(matches!(
+		delay.set_mix(0.5, Tween::default()),
+		Err(CommandError::ResourceRemoved)
+	));
+	assert!(matches!(
+		feedback_filter.set_mix(0.5, Tween::default()),
+		Err(CommandError::ResourceRemoved)
+	));
+}
+
+/// Tests that stopping the main track fades it out without
+/// removing it.
+#[test]
+fn stopping_main_track_does_not_remove_it() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let mut main_track = manager.main_track();
+	main_track.stop(Tween::default()).unwrap();
+	manager.backend_mut().on_start_processing();
+	for _ in 0..3 {
+		manager.backend_mut().process();
+	}
+	assert!(main_track.is_alive());
+	assert!(main_track.set_volume(1.0, Tween::default()).is_ok());
+}
+
+/// Tests that dropping an `AudioManager` doesn't wait for the
+/// shutdown fade when the backend isn't rendering audio.
+#[test]
+fn drop_does_not_block() {
+	let manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		start_stop_fade_duration: Duration::from_secs(10),
+		..Default::default()
+	})
+	.unwrap();
+	let start = std::time::Instant::now();
+	drop(manager);
+	assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+/// Tests that the audio output fades in when the backend starts.
+#[test]
+#[allow(clippy::float_cmp)]
+fn fades_in_on_start() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		backend_settings: MockBackendSettings { sample_rate: 100 },
+		start_stop_fade_duration: Duration::from_millis(40),
+		..Default::default()
+	})
+	.unwrap();
+	manager
+		.play(StaticSoundData {
+			sample_rate: 100,
+			frames: Arc::new(vec![Frame::from_mono(1.0); 100]),
+			settings: StaticSoundSettings::new(),
+		})
+		.unwrap();
+	manager.backend_mut().on_start_processing();
+	let output: Vec<Frame> = (0..10).map(|_| manager.backend_mut().process()).collect();
+	let full_volume = Frame::from_mono(1.0).panned(0.5);
+	for i in 1..3 {
+		assert!(output[i].left > output[i - 1].left);
+	}
+	assert!(output[2].left < full_volume.left);
+	for frame in &output[3..] {
+		assert_eq!(*frame, full_volume);
+	}
+}
+
+/// Tests that the DC blocker removes a constant offset
+/// from the audio output.
+#[test]
+fn blocks_dc_offset() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		backend_settings: MockBackendSettings { sample_rate: 1000 },
+		start_stop_fade_duration: Duration::ZERO,
+		dc_blocker: true,
+		..Default::default()
+	})
+	.unwrap();
+	manager
+		.play(StaticSoundData {
+			sample_rate: 1000,
+			frames: Arc::new(vec![Frame::from_mono(0.5); 2000]),
+			settings: StaticSoundSettings::new(),
+		})
+		.unwrap();
+	manager.backend_mut().on_start_processing();
+	let output: Vec<f32> = (0..1000)
+		.map(|_| manager.backend_mut().process().left)
+		.collect();
+	let mean = |frames: &[f32]| frames.iter().sum::<f32>() / frames.len() as f32;
+	assert!(mean(&output[..100]) > 0.1);
+	assert!(mean(&output[900..]).abs() < 0.001);
+}
+
+/// Tests that muffling the audio attenuates high frequencies
+/// on the main output.
+#[test]
+fn muffle() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		backend_settings: MockBackendSettings {
+			sample_rate: 48_000,
+		},
+		start_stop_fade_duration: Duration::ZERO,
+		..Default::default()
+	})
+	.unwrap();
+	manager
+		.play(StaticSoundData {
+			sample_rate: 48_000,
+			frames: Arc::new(
+				(0..4000)
+					.map(|i| Frame::from_mono(if i % 2 == 0 { 0.5 } else { -0.5 }))
+					.collect(),
+			),
+			settings: StaticSoundSettings::new(),
+		})
+		.unwrap();
+	manager.backend_mut().on_start_processing();
+	let peak = |manager: &mut AudioManager<MockBackend>| {
+		(0..1000)
+			.map(|_| manager.backend_mut().process().left.abs())
+			.skip(100)
+			.fold(0.0f32, f32::max)
+	};
+	let unmuffled_peak = peak(&mut manager);
+	assert!(unmuffled_peak > 0.3);
+	manager
+		.set_muffle(
+			1.0,
+			Tween {
+				duration: Duration::ZERO,
+				..Default::default()
+			},
+		)
+		.unwrap();
+	manager.backend_mut().on_start_processing();
+	let muffled_peak = peak(&mut manager);
+	assert!(muffled_peak < unmuffled_peak * 0.1);
+	// a NaN amount turns the muffle off instead of
+	// corrupting the output
+	manager
+		.set_muffle(
+			f64::NAN,
+			Tween {
+				duration: Duration::ZERO,
+				..Default::default()
+			},
+		)
+		.unwrap();
+	manager.backend_mut().on_start_processing();
+	assert!(peak(&mut manager) > 0.3);
+}
+
+/// Tests that the mock backend reports no output latency.
+#[test]
+fn output_latency() {
+	let manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	assert_eq!(manager.output_latency(), Some(Duration::ZERO));
+}
+
+/// Tests that a routing snapshot lists each track's routes,
+/// effects, and sounds.
+#[test]
+fn routing_snapshot() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let track = manager
+		.add_sub_track({
+			let mut builder = TrackBuilder::new()
+				.volume(0.5)
+				.routes(TrackRoutes::new().with_route(TrackId::Main, 0.25));
+			builder.add_effect(FilterBuilder::new());
+			builder
+		})
+		.unwrap();
+	let sound = |track: TrackId| StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new().track(track),
+	};
+	manager.play(sound(track.id())).unwrap();
+	manager.play(sound(track.id())).unwrap();
+	manager.play(sound(TrackId::Main)).unwrap();
+	// sounds are counted at the start of the next batch of samples
+	manager.backend_mut().on_start_processing();
+	manager.backend_mut().on_start_processing();
+
+	let snapshot = manager.routing_snapshot();
+	assert_eq!(snapshot.tracks.len(), 2);
+	let main_track = snapshot.track(TrackId::Main).unwrap();
+	assert_eq!(main_track.num_sounds, 1);
+	assert!(main_track.routes.is_empty());
+	let sub_track = snapshot.track(track.id()).unwrap();
+	assert_eq!(sub_track.volume, Volume::Amplitude(0.5));
+	assert_eq!(
+		sub_track.routes,
+		vec![(TrackId::Main, Volume::Amplitude(0.25))]
+	);
+	assert_eq!(sub_track.effect_names, vec!["Filter"]);
+	assert_eq!(sub_track.num_sounds, 2);
+
+	// removed tracks aren't included in the snapshot
+	drop(track);
+	assert_eq!(manager.routing_snapshot().tracks.len(), 1);
+}
+
+/// Tests that a track can send its output to multiple tracks
+/// at different volumes, and that routes can be added and
+/// removed after the track is created.
+#[test]
+#[allow(clippy::float_cmp)]
+fn multiple_routes() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		start_stop_fade_duration: Duration::ZERO,
+		..Default::default()
+	})
+	.unwrap();
+	let pan = |panning: f64| {
+		let mut builder = TrackBuilder::new();
+		builder.add_effect(PanningControlBuilder::new(panning));
+		builder
+	};
+	let left_track = manager.add_sub_track(pan(0.0)).unwrap();
+	let right_track = manager.add_sub_track(pan(1.0)).unwrap();
+	let mut track = manager
+		.add_sub_track(
+			TrackBuilder::new().routes(TrackRoutes::empty().with_route(&left_track, 1.0)),
+		)
+		.unwrap();
+	let instant = Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	};
+	track.add_route(&right_track, 0.5, instant).unwrap();
+	manager
+		.play(StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+			settings: StaticSoundSettings::new().track(&track),
+		})
+		.unwrap();
+	manager.backend_mut().on_start_processing();
+	let output = manager.backend_mut().process();
+	assert!(output.left > 0.0);
+	assert!((output.right / output.left - 0.5).abs() < 1.0e-6);
+
+	track.remove_route(&left_track, instant).unwrap();
+	manager.backend_mut().on_start_processing();
+	manager.backend_mut().process();
+	let output = manager.backend_mut().process();
+	assert_eq!(output.left, 0.0);
+	assert!(output.right > 0.0);
+	assert!(matches!(
+		track.set_route(&left_track, 1.0, instant),
+		Err(SetRouteError::NonexistentRoute)
+	));
+	assert!(matches!(
+		track.add_route(track.id(), 1.0, instant),
+		Err(AddRouteError::RouteToSelf)
+	));
+}
+
+/// Tests that routes that would create a cycle or lead to
+/// removed tracks can't be added.
+#[test]
+fn rejects_invalid_routes() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		start_stop_fade_duration: Duration::ZERO,
+		..Default::default()
+	})
+	.unwrap();
+	let instant = Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	};
+	let mut track_a = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut track_b = manager
+		.add_sub_track(TrackBuilder::new().routes(TrackRoutes::parent(&track_a)))
+		.unwrap();
+	let mut track_c = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	track_a.add_route(&track_c, 1.0, instant).unwrap();
+	assert!(matches!(
+		track_c.add_route(&track_b, 1.0, instant),
+		Err(AddRouteError::RoutingCycle)
+	));
+	// removed routes are still connected on the audio thread
+	track_a.remove_route(&track_c, instant).unwrap();
+	assert!(matches!(
+		track_c.add_route(&track_a, 1.0, instant),
+		Err(AddRouteError::RoutingCycle)
+	));
+	// restoring a removed route doesn't create a new connection
+	track_a.add_route(&track_c, 1.0, instant).unwrap();
+	let track_c_id = track_c.id();
+	track_c.stop(instant).unwrap();
+	manager.backend_mut().on_start_processing();
+	for _ in 0..3 {
+		manager.backend_mut().process();
+	}
+	assert!(matches!(
+		track_b.add_route(track_c_id, 1.0, instant),
+		Err(AddRouteError::NonexistentTrack)
+	));
+}
+
+/// Tests that a route isn't recorded if the command to add
+/// it couldn't be sent.
+#[test]
+fn route_changes_roll_back_when_command_queue_is_full() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		capacities: Capacities {
+			command_capacity: 3,
+			..Default::default()
+		},
+		..Default::default()
+	})
+	.unwrap();
+	let instant = Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	};
+	let destination = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	let mut track = manager.add_sub_track(TrackBuilder::new()).unwrap();
+	manager.backend_mut().on_start_processing();
+	track.set_volume(1.0, instant).unwrap();
+	track.set_volume(1.0, instant).unwrap();
+	track.set_volume(1.0, instant).unwrap();
+	assert!(matches!(
+		track.add_route(&destination, 1.0, instant),
+		Err(AddRouteError::CommandError(CommandError::CommandQueueFull))
+	));
+	assert!(matches!(
+		track.set_route(&destination, 1.0, instant),
+		Err(SetRouteError::NonexistentRoute)
+	));
+	manager.backend_mut().on_start_processing();
+	track.add_route(&destination, 1.0, instant).unwrap();
+	track.set_volume(1.0, instant).unwrap();
+	track.set_volume(1.0, instant).unwrap();
+	assert!(matches!(
+		track.remove_route(&destination, instant),
+		Err(SetRouteError::CommandError(CommandError::CommandQueueFull))
+	));
+	manager.backend_mut().on_start_processing();
+	assert!(track.set_route(&destination, 0.5, instant).is_ok());
+}
+
+/// Tests that the level of a pre-fader send is not affected
+/// by the volume of the track it's sent from.
+#[test]
+#[allow(clippy::float_cmp)]
+fn pre_fader_send() {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		start_stop_fade_duration: Duration::ZERO,
+		..Default::default()
+	})
+	.unwrap();
+	let pan = |panning: f64| {
+		let mut builder = TrackBuilder::new();
+		builder.add_effect(PanningControlBuilder::new(panning));
+		builder
+	};
+	let left_track = manager.add_sub_track(pan(0.0)).unwrap();
+	let right_track = manager.add_sub_track(pan(1.0)).unwrap();
+	let mut track = manager
+		.add_sub_track(
+			TrackBuilder::new().routes(
+				TrackRoutes::empty()
+					.with_route_at(&left_track, 1.0, SendPoint::PreFader)
+					.with_route(&right_track, 1.0),
+			),
+		)
+		.unwrap();
+	let instant = Tween {
+		duration: Duration::ZERO,
+		..Default::default()
+	};
+	manager
+		.play(StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+			settings: StaticSoundSettings::new().track(&track),
+		})
+		.unwrap();
+	manager.backend_mut().on_start_processing();
+	let full_volume_output = manager.backend_mut().process();
+	assert!(full_volume_output.left > 0.0);
+	assert_eq!(full_volume_output.left, full_volume_output.right);
+
+	// the pre-fader send should stay the same while the
+	// post-fader send follows the track's volume
+	track.set_volume(Volume::Amplitude(0.5), instant).unwrap();
+	manager.backend_mut().on_start_processing();
+	let output = manager.backend_mut().process();
+	assert_eq!(output.left, full_volume_output.left);
+	assert!((output.right / output.left - 0.5).abs() < 1.0e-6);
+
+	// switching the post-fader route to pre-fader
+	track
+		.add_route_at(&right_track, 1.0, SendPoint::PreFader, instant)
+		.unwrap();
+	manager.backend_mut().on_start_processing();
+	let output = manager.backend_mut().process();
+	assert_eq!(output.left, full_volume_output.left);
+	assert_eq!(output.right, full_volume_output.right);
+}
@@ -0,0 +1,132 @@
+//! Values that change over time and can drive the parameters
+//! of multiple effects at once.
+//!
+//! Modulators are added to the audio context with
+//! [`AudioManager::add_modulator`](crate::manager::AudioManager::add_modulator).
+//! Effects that support modulation can then be linked to a
+//! modulator by its [`ModulatorId`], and multiple effects can
+//! follow the same modulator.
+
+pub mod envelope;
+pub mod lfo;
+mod mapping;
+pub(crate) mod modulators;
+
+#[cfg(test)]
+mod test;
+
+pub use mapping::*;
+
+use std::{
+	fmt::{Debug, Formatter},
+	hash::{Hash, Hasher},
+	sync::{
+		atomic::{AtomicBool, AtomicU64, Ordering},
+		Arc,
+	},
+};
+
+use atomic_arena::Key;
+
+use crate::clock::ClockTime;
+
+/// A source of values that change over time.
+#[allow(unused_variables)]
+pub trait Modulator: Send {
+	/// Called whenever a new batch of audio samples is requested by the backend.
+	///
+	/// This is a good place to put code that needs to run fairly frequently,
+	/// but not for every single audio sample.
+	fn on_start_processing(&mut self) {}
+
+	/// Updates the modulator.
+	///
+	/// `dt` is the time that's elapsed since the previous round of
+	/// processing (in seconds).
+	fn update(&mut self, dt: f64);
+
+	/// Returns the current output of the modulator.
+	fn value(&self) -> f64;
+
+	/// Called whenever a [clock](crate::clock) ticks.
+	fn on_clock_tick(&mut self, time: ClockTime) {}
+}
+
+/// Configures a modulator.
+pub trait ModulatorBuilder {
+	/// Allows the user to control the modulator from gameplay code.
+	type Handle;
+
+	/// Creates the modulator and a handle to the modulator.
+	fn build(self, id: ModulatorId) -> (Box<dyn Modulator>, Self::Handle);
+}
+
+pub(crate) struct ModulatorShared {
+	value: AtomicU64,
+	removed: AtomicBool,
+}
+
+impl ModulatorShared {
+	fn new() -> Self {
+		Self {
+			value: AtomicU64::new(0.0f64.to_bits()),
+			removed: AtomicBool::new(false),
+		}
+	}
+
+	pub fn value(&self) -> f64 {
+		f64::from_bits(self.value.load(Ordering::SeqCst))
+	}
+
+	pub fn is_marked_for_removal(&self) -> bool {
+		self.removed.load(Ordering::SeqCst)
+	}
+
+	pub fn mark_for_removal(&self) {
+		self.removed.store(true, Ordering::SeqCst);
+	}
+}
+
+/// A unique identifier for a modulator.
+///
+/// Effects linked to a modulator will keep using the last
+/// value the modulator output after it's removed.
+#[derive(Clone)]
+pub struct ModulatorId {
+	pub(crate) key: Key,
+	pub(crate) shared: Arc<ModulatorShared>,
+}
+
+impl ModulatorId {
+	pub(crate) fn new(key: Key) -> Self {
+		Self {
+			key,
+			shared: Arc::new(ModulatorShared::new()),
+		}
+	}
+
+	/// Returns the most recent output of the modulator.
+	pub(crate) fn value(&self) -> f64 {
+		self.shared.value()
+	}
+}
+
+impl Debug for ModulatorId {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_tuple("ModulatorId").field(&self.key).finish()
+	}
+}
+
+impl PartialEq for ModulatorId {
+	fn eq(&self, other: &Self) -> bool {
+		self.key == other.key
+	}
+}
+
+impl Eq for ModulatorId {}
+
+impl Hash for ModulatorId {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.key.hash(state);
+	}
+}
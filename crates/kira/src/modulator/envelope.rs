@@ -0,0 +1,51 @@
+//! Rises and falls once each time it's triggered.
+
+mod builder;
+mod handle;
+
+pub use builder::*;
+pub use handle::*;
+
+use std::time::Duration;
+
+use ringbuf::Consumer;
+
+use super::Modulator;
+
+enum Command {
+	Trigger,
+}
+
+struct Envelope {
+	command_consumer: Consumer<Command>,
+	attack_duration: Duration,
+	release_duration: Duration,
+	/// The time since the envelope was triggered (in seconds).
+	time: f64,
+}
+
+impl Modulator for Envelope {
+	fn on_start_processing(&mut self) {
+		while let Some(command) = self.command_consumer.pop() {
+			match command {
+				Command::Trigger => self.time = 0.0,
+			}
+		}
+	}
+
+	fn update(&mut self, dt: f64) {
+		self.time += dt;
+	}
+
+	fn value(&self) -> f64 {
+		let attack_duration = self.attack_duration.as_secs_f64();
+		let release_duration = self.release_duration.as_secs_f64();
+		if self.time < attack_duration {
+			self.time / attack_duration
+		} else if self.time < attack_duration + release_duration {
+			1.0 - (self.time - attack_duration) / release_duration
+		} else {
+			0.0
+		}
+	}
+}
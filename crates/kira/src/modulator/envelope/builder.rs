@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use ringbuf::RingBuffer;
+
+use crate::modulator::{Modulator, ModulatorBuilder, ModulatorId};
+
+use super::{Envelope, EnvelopeHandle};
+
+const COMMAND_CAPACITY: usize = 8;
+
+/// Configures an envelope modulator.
+///
+/// The envelope's output rises linearly from `0.0` to `1.0`
+/// over the attack duration, and then falls back to `0.0` over
+/// the release duration. The envelope is triggered as soon as
+/// it's created, and it can be triggered again with
+/// [`EnvelopeHandle::trigger`].
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub struct EnvelopeBuilder {
+	/// How long it takes the output to rise from `0.0` to `1.0`.
+	pub attack_duration: Duration,
+	/// How long it takes the output to fall from `1.0` to `0.0`.
+	pub release_duration: Duration,
+}
+
+impl EnvelopeBuilder {
+	/// Creates a new [`EnvelopeBuilder`] with the default settings.
+	pub fn new() -> Self {
+		Self {
+			attack_duration: Duration::from_millis(10),
+			release_duration: Duration::from_secs(1),
+		}
+	}
+
+	/// Sets how long it takes the output to rise from `0.0` to `1.0`.
+	pub fn attack_duration(self, attack_duration: Duration) -> Self {
+		Self {
+			attack_duration,
+			..self
+		}
+	}
+
+	/// Sets how long it takes the output to fall from `1.0` to `0.0`.
+	pub fn release_duration(self, release_duration: Duration) -> Self {
+		Self {
+			release_duration,
+			..self
+		}
+	}
+}
+
+impl Default for EnvelopeBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl ModulatorBuilder for EnvelopeBuilder {
+	type Handle = EnvelopeHandle;
+
+	fn build(self, id: ModulatorId) -> (Box<dyn Modulator>, Self::Handle) {
+		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_CAPACITY).split();
+		(
+			Box::new(Envelope {
+				command_consumer,
+				attack_duration: self.attack_duration,
+				release_duration: self.release_duration,
+				time: 0.0,
+			}),
+			EnvelopeHandle {
+				id,
+				command_producer,
+			},
+		)
+	}
+}
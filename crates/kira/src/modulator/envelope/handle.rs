@@ -0,0 +1,40 @@
+use ringbuf::Producer;
+
+use crate::{modulator::ModulatorId, CommandError};
+
+use super::Command;
+
+/// Controls an envelope modulator.
+///
+/// When an [`EnvelopeHandle`] is dropped, the corresponding
+/// modulator will be removed.
+pub struct EnvelopeHandle {
+	pub(super) id: ModulatorId,
+	pub(super) command_producer: Producer<Command>,
+}
+
+impl EnvelopeHandle {
+	/// Returns the unique identifier for the modulator.
+	pub fn id(&self) -> ModulatorId {
+		self.id.clone()
+	}
+
+	/// Restarts the envelope from the beginning of the attack.
+	pub fn trigger(&mut self) -> Result<(), CommandError> {
+		self.command_producer
+			.push(Command::Trigger)
+			.map_err(|_| CommandError::CommandQueueFull)
+	}
+}
+
+impl Drop for EnvelopeHandle {
+	fn drop(&mut self) {
+		self.id.shared.mark_for_removal();
+	}
+}
+
+impl From<&EnvelopeHandle> for ModulatorId {
+	fn from(handle: &EnvelopeHandle) -> Self {
+		handle.id()
+	}
+}
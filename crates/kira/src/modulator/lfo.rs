@@ -0,0 +1,63 @@
+//! Oscillates back and forth.
+
+mod builder;
+mod handle;
+
+pub use builder::*;
+pub use handle::*;
+
+use ringbuf::Consumer;
+
+use crate::{
+	clock::ClockTime,
+	dsp::{Lfo as Oscillator, LfoShape},
+	tween::{Tween, Tweener},
+};
+
+use super::Modulator;
+
+enum Command {
+	SetShape(LfoShape),
+	SetFrequency(f64, Tween),
+	SetAmplitude(f64, Tween),
+	SetOffset(f64, Tween),
+}
+
+struct Lfo {
+	command_consumer: Consumer<Command>,
+	oscillator: Oscillator,
+	shape: LfoShape,
+	frequency: Tweener,
+	amplitude: Tweener,
+	offset: Tweener,
+}
+
+impl Modulator for Lfo {
+	fn on_start_processing(&mut self) {
+		while let Some(command) = self.command_consumer.pop() {
+			match command {
+				Command::SetShape(shape) => self.shape = shape,
+				Command::SetFrequency(frequency, tween) => self.frequency.set(frequency, tween),
+				Command::SetAmplitude(amplitude, tween) => self.amplitude.set(amplitude, tween),
+				Command::SetOffset(offset, tween) => self.offset.set(offset, tween),
+			}
+		}
+	}
+
+	fn update(&mut self, dt: f64) {
+		self.frequency.update(dt);
+		self.amplitude.update(dt);
+		self.offset.update(dt);
+		self.oscillator.update(self.frequency.value(), dt);
+	}
+
+	fn value(&self) -> f64 {
+		self.offset.value() + self.amplitude.value() * self.oscillator.value(self.shape)
+	}
+
+	fn on_clock_tick(&mut self, time: ClockTime) {
+		self.frequency.on_clock_tick(time);
+		self.amplitude.on_clock_tick(time);
+		self.offset.on_clock_tick(time);
+	}
+}
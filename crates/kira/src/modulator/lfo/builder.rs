@@ -0,0 +1,85 @@
+use ringbuf::RingBuffer;
+
+use crate::{
+	dsp::{Lfo as Oscillator, LfoShape},
+	modulator::{Modulator, ModulatorBuilder, ModulatorId},
+	tween::Tweener,
+};
+
+use super::{Lfo, LfoHandle};
+
+const COMMAND_CAPACITY: usize = 8;
+
+/// Configures an LFO modulator.
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub struct LfoBuilder {
+	/// The shape of the oscillation.
+	pub shape: LfoShape,
+	/// How many times the LFO oscillates per second.
+	pub frequency: f64,
+	/// How far the output moves away from the offset.
+	pub amplitude: f64,
+	/// The value the output oscillates around.
+	pub offset: f64,
+}
+
+impl LfoBuilder {
+	/// Creates a new [`LfoBuilder`] with the default settings.
+	pub fn new() -> Self {
+		Self {
+			shape: LfoShape::Sine,
+			frequency: 2.0,
+			amplitude: 1.0,
+			offset: 0.0,
+		}
+	}
+
+	/// Sets the shape of the oscillation.
+	pub fn shape(self, shape: LfoShape) -> Self {
+		Self { shape, ..self }
+	}
+
+	/// Sets how many times the LFO oscillates per second.
+	pub fn frequency(self, frequency: f64) -> Self {
+		Self { frequency, ..self }
+	}
+
+	/// Sets how far the output moves away from the offset.
+	pub fn amplitude(self, amplitude: f64) -> Self {
+		Self { amplitude, ..self }
+	}
+
+	/// Sets the value the output oscillates around.
+	pub fn offset(self, offset: f64) -> Self {
+		Self { offset, ..self }
+	}
+}
+
+impl Default for LfoBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl ModulatorBuilder for LfoBuilder {
+	type Handle = LfoHandle;
+
+	fn build(self, id: ModulatorId) -> (Box<dyn Modulator>, Self::Handle) {
+		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_CAPACITY).split();
+		(
+			Box::new(Lfo {
+				command_consumer,
+				oscillator: Oscillator::new(),
+				shape: self.shape,
+				frequency: Tweener::new(self.frequency),
+				amplitude: Tweener::new(self.amplitude),
+				offset: Tweener::new(self.offset),
+			}),
+			LfoHandle {
+				id,
+				command_producer,
+			},
+		)
+	}
+}
@@ -0,0 +1,61 @@
+use ringbuf::Producer;
+
+use crate::{dsp::LfoShape, modulator::ModulatorId, tween::Tween, CommandError};
+
+use super::Command;
+
+/// Controls an LFO modulator.
+///
+/// When an [`LfoHandle`] is dropped, the corresponding
+/// modulator will be removed.
+pub struct LfoHandle {
+	pub(super) id: ModulatorId,
+	pub(super) command_producer: Producer<Command>,
+}
+
+impl LfoHandle {
+	/// Returns the unique identifier for the modulator.
+	pub fn id(&self) -> ModulatorId {
+		self.id.clone()
+	}
+
+	/// Sets the shape of the oscillation.
+	pub fn set_shape(&mut self, shape: LfoShape) -> Result<(), CommandError> {
+		self.command_producer
+			.push(Command::SetShape(shape))
+			.map_err(|_| CommandError::CommandQueueFull)
+	}
+
+	/// Sets how many times the LFO oscillates per second.
+	pub fn set_frequency(&mut self, frequency: f64, tween: Tween) -> Result<(), CommandError> {
+		self.command_producer
+			.push(Command::SetFrequency(frequency, tween))
+			.map_err(|_| CommandError::CommandQueueFull)
+	}
+
+	/// Sets how far the output moves away from the offset.
+	pub fn set_amplitude(&mut self, amplitude: f64, tween: Tween) -> Result<(), CommandError> {
+		self.command_producer
+			.push(Command::SetAmplitude(amplitude, tween))
+			.map_err(|_| CommandError::CommandQueueFull)
+	}
+
+	/// Sets the value the output oscillates around.
+	pub fn set_offset(&mut self, offset: f64, tween: Tween) -> Result<(), CommandError> {
+		self.command_producer
+			.push(Command::SetOffset(offset, tween))
+			.map_err(|_| CommandError::CommandQueueFull)
+	}
+}
+
+impl Drop for LfoHandle {
+	fn drop(&mut self) {
+		self.id.shared.mark_for_removal();
+	}
+}
+
+impl From<&LfoHandle> for ModulatorId {
+	fn from(handle: &LfoHandle) -> Self {
+		handle.id()
+	}
+}
@@ -0,0 +1,46 @@
+/// Maps the output of a modulator to a range of values
+/// for an effect parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModulatorMapping {
+	/// The range of modulator outputs to map from.
+	pub input_range: (f64, f64),
+	/// The range of parameter values to map to.
+	pub output_range: (f64, f64),
+	/// Whether the parameter value should be kept within
+	/// the output range.
+	pub clamp: bool,
+}
+
+impl ModulatorMapping {
+	/// Creates a new [`ModulatorMapping`] that maps the input
+	/// range to the output range and clamps the result.
+	pub fn new(input_range: (f64, f64), output_range: (f64, f64)) -> Self {
+		Self {
+			input_range,
+			output_range,
+			clamp: true,
+		}
+	}
+
+	/// Maps a modulator output to a parameter value.
+	pub fn map(&self, value: f64) -> f64 {
+		let relative = (value - self.input_range.0) / (self.input_range.1 - self.input_range.0);
+		let mapped = self.output_range.0 + (self.output_range.1 - self.output_range.0) * relative;
+		if self.clamp {
+			let (min, max) = if self.output_range.0 <= self.output_range.1 {
+				(self.output_range.0, self.output_range.1)
+			} else {
+				(self.output_range.1, self.output_range.0)
+			};
+			mapped.clamp(min, max)
+		} else {
+			mapped
+		}
+	}
+}
+
+impl Default for ModulatorMapping {
+	fn default() -> Self {
+		Self::new((0.0, 1.0), (0.0, 1.0))
+	}
+}
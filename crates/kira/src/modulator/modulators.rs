@@ -0,0 +1,89 @@
+use std::sync::{atomic::Ordering, Arc};
+
+use atomic_arena::{Arena, Controller};
+use ringbuf::Producer;
+
+use crate::{clock::ClockTime, manager::command::ModulatorCommand};
+
+use super::{Modulator, ModulatorShared};
+
+pub(crate) struct ModulatorWrapper {
+	modulator: Box<dyn Modulator>,
+	shared: Arc<ModulatorShared>,
+}
+
+pub(crate) struct Modulators {
+	modulators: Arena<ModulatorWrapper>,
+	unused_modulator_producer: Producer<ModulatorWrapper>,
+}
+
+impl Modulators {
+	pub(crate) fn new(
+		capacity: usize,
+		unused_modulator_producer: Producer<ModulatorWrapper>,
+	) -> Self {
+		Self {
+			modulators: Arena::new(capacity),
+			unused_modulator_producer,
+		}
+	}
+
+	pub(crate) fn controller(&self) -> Controller {
+		self.modulators.controller()
+	}
+
+	pub(crate) fn on_start_processing(&mut self) {
+		self.remove_unused_modulators();
+		for (_, wrapper) in &mut self.modulators {
+			wrapper.modulator.on_start_processing();
+		}
+	}
+
+	fn remove_unused_modulators(&mut self) {
+		if self.unused_modulator_producer.is_full() {
+			return;
+		}
+		for (_, wrapper) in self
+			.modulators
+			.drain_filter(|wrapper| wrapper.shared.is_marked_for_removal())
+		{
+			if self.unused_modulator_producer.push(wrapper).is_err() {
+				panic!("Unused modulator producer is full")
+			}
+			if self.unused_modulator_producer.is_full() {
+				return;
+			}
+		}
+	}
+
+	pub(crate) fn run_command(&mut self, command: ModulatorCommand) {
+		match command {
+			ModulatorCommand::Add(id, modulator) => self
+				.modulators
+				.insert_with_key(
+					id.key,
+					ModulatorWrapper {
+						modulator,
+						shared: id.shared,
+					},
+				)
+				.expect("Modulator arena is full"),
+		}
+	}
+
+	pub(crate) fn update(&mut self, dt: f64) {
+		for (_, wrapper) in &mut self.modulators {
+			wrapper.modulator.update(dt);
+			wrapper
+				.shared
+				.value
+				.store(wrapper.modulator.value().to_bits(), Ordering::SeqCst);
+		}
+	}
+
+	pub(crate) fn on_clock_tick(&mut self, time: ClockTime) {
+		for (_, wrapper) in &mut self.modulators {
+			wrapper.modulator.on_clock_tick(time);
+		}
+	}
+}
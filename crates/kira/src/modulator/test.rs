@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use crate::manager::{backend::mock::MockBackend, AudioManager};
+
+use super::{envelope::EnvelopeBuilder, ModulatorId, ModulatorMapping};
+
+/// Tests that a `ModulatorMapping` maps values between ranges
+/// and optionally clamps them.
+#[test]
+#[allow(clippy::float_cmp)]
+fn mapping() {
+	let mapping = ModulatorMapping::new((-1.0, 1.0), (100.0, 200.0));
+	assert_eq!(mapping.map(-1.0), 100.0);
+	assert_eq!(mapping.map(0.0), 150.0);
+	assert_eq!(mapping.map(1.0), 200.0);
+	assert_eq!(mapping.map(3.0), 200.0);
+	assert_eq!(mapping.map(-3.0), 100.0);
+
+	// reversed output ranges should still be clamped
+	let mapping = ModulatorMapping::new((0.0, 1.0), (1.0, 0.0));
+	assert_eq!(mapping.map(0.25), 0.75);
+	assert_eq!(mapping.map(2.0), 0.0);
+
+	let mapping = ModulatorMapping {
+		clamp: false,
+		..ModulatorMapping::new((0.0, 1.0), (0.0, 10.0))
+	};
+	assert_eq!(mapping.map(2.0), 20.0);
+}
+
+/// Tests that the output of a modulator is shared with
+/// its `ModulatorId`s.
+#[test]
+#[allow(clippy::float_cmp)]
+fn outputs_values() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let handle = manager
+		.add_modulator(
+			EnvelopeBuilder::new()
+				.attack_duration(Duration::from_secs(2))
+				.release_duration(Duration::from_secs(4)),
+		)
+		.unwrap();
+	let id = ModulatorId::from(&handle);
+	manager.backend_mut().on_start_processing();
+	let mut values = vec![];
+	for _ in 0..8 {
+		manager.backend_mut().process();
+		values.push(id.value());
+	}
+	assert_eq!(values, vec![0.5, 1.0, 0.75, 0.5, 0.25, 0.0, 0.0, 0.0]);
+}
+
+/// Tests that a modulator is removed from the audio context
+/// when its handle is dropped.
+#[test]
+fn removed_when_handle_dropped() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let handle = manager.add_modulator(EnvelopeBuilder::new()).unwrap();
+	manager.backend_mut().on_start_processing();
+	assert_eq!(manager.num_modulators(), 1);
+	drop(handle);
+	manager.backend_mut().on_start_processing();
+	assert_eq!(manager.num_modulators(), 0);
+}
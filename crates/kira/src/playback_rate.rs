@@ -12,6 +12,10 @@ pub enum PlaybackRate {
 	/// The sound is played faster or slower so that the
 	/// pitch of the sound is adjusted by the given number
 	/// of semitones.
+	///
+	/// For example, `PlaybackRate::Semitones(7.0)` transposes
+	/// the sound up a perfect fifth, and `PlaybackRate::Semitones(12.0)`
+	/// plays the sound twice as fast (one octave higher).
 	Semitones(f64),
 }
 
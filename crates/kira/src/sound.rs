@@ -2,16 +2,23 @@
 Sources of audio.
 */
 
+#[cfg(feature = "symphonia")]
+mod audio_info;
 #[cfg(feature = "symphonia")]
 mod error;
+pub mod layered_music;
+mod playback_state_manager;
+pub mod sequence;
 pub mod static_sound;
 #[cfg(all(feature = "symphonia", not(target_arch = "wasm32")))]
 pub mod streaming;
 
+#[cfg(feature = "symphonia")]
+pub use audio_info::*;
 #[cfg(feature = "symphonia")]
 pub use error::*;
 
-use crate::{clock::ClockTime, dsp::Frame, track::TrackId};
+use crate::{clock::ClockTime, dsp::Frame, track::TrackId, tween::Tween};
 
 /// A source of audio that is loaded, but not yet playing.
 pub trait SoundData {
@@ -46,6 +53,24 @@ pub trait Sound: Send {
 	/// Called whenever a [clock](crate::clock) ticks.
 	fn on_clock_tick(&mut self, time: ClockTime) {}
 
+	/// Fades out and pauses the sound.
+	///
+	/// This is called when all of the sounds on a mixer track
+	/// are paused using [`TrackHandle::pause_sounds`](crate::track::TrackHandle::pause_sounds).
+	fn pause(&mut self, fade_out_tween: Tween) {}
+
+	/// Resumes and fades in the sound if it's paused.
+	///
+	/// This is called when all of the sounds on a mixer track
+	/// are resumed using [`TrackHandle::resume_sounds`](crate::track::TrackHandle::resume_sounds).
+	fn resume(&mut self, fade_in_tween: Tween) {}
+
+	/// Fades out and stops the sound.
+	///
+	/// This is called when all of the sounds on a mixer track
+	/// are stopped using [`TrackHandle::stop_sounds`](crate::track::TrackHandle::stop_sounds).
+	fn stop(&mut self, fade_out_tween: Tween) {}
+
 	/// Returns `true` if the sound is finished and can be unloaded.
 	fn finished(&self) -> bool;
 }
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod test;
+
+use std::{io::Cursor, time::Duration};
+
+use symphonia::core::io::{MediaSource, MediaSourceStream};
+
+use super::FromFileError;
+
+/// Information about an audio file that can be read without
+/// decoding the audio.
+///
+/// This is useful for deciding whether to load a file as a
+/// [`StaticSoundData`](crate::sound::static_sound::StaticSoundData)
+/// or stream it, for example based on its duration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(
+	docsrs,
+	doc(cfg(any(feature = "mp3", feature = "ogg", feature = "flac", feature = "wav")))
+)]
+pub struct AudioInfo {
+	/// The number of frames per second of audio.
+	pub sample_rate: u32,
+	/// The number of channels of audio, if the file reports it.
+	pub channels: Option<usize>,
+	/// The total length of the audio, if the file reports it.
+	pub duration: Option<Duration>,
+	/// The short name of the codec the audio is encoded with,
+	/// if the codec is supported by the enabled features.
+	pub codec: Option<&'static str>,
+}
+
+impl AudioInfo {
+	fn from_media_source(media_source: Box<dyn MediaSource>) -> Result<Self, FromFileError> {
+		let codecs = symphonia::default::get_codecs();
+		let probe = symphonia::default::get_probe();
+		let mss = MediaSourceStream::new(media_source, Default::default());
+		let format_reader = probe
+			.format(
+				&Default::default(),
+				mss,
+				&Default::default(),
+				&Default::default(),
+			)?
+			.format;
+		let codec_params = &format_reader
+			.default_track()
+			.ok_or(FromFileError::NoDefaultTrack)?
+			.codec_params;
+		let sample_rate = codec_params
+			.sample_rate
+			.ok_or(FromFileError::UnknownSampleRate)?;
+		Ok(Self {
+			sample_rate,
+			channels: codec_params.channels.map(|channels| channels.count()),
+			duration: codec_params
+				.n_frames
+				.map(|n_frames| Duration::from_secs_f64(n_frames as f64 / sample_rate as f64)),
+			codec: codecs
+				.get_codec(codec_params.codec)
+				.map(|descriptor| descriptor.short_name),
+		})
+	}
+
+	/// Reads information about an audio file.
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(
+		docsrs,
+		doc(cfg(all(
+			any(feature = "mp3", feature = "ogg", feature = "flac", feature = "wav"),
+			not(wasm32)
+		)))
+	)]
+	pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, FromFileError> {
+		Self::from_media_source(Box::new(std::fs::File::open(path)?))
+	}
+
+	/// Reads information about a cursor wrapping audio file data.
+	#[cfg_attr(
+		docsrs,
+		doc(cfg(any(feature = "mp3", feature = "ogg", feature = "flac", feature = "wav")))
+	)]
+	pub fn from_cursor<T: AsRef<[u8]> + Send + Sync + 'static>(
+		cursor: Cursor<T>,
+	) -> Result<Self, FromFileError> {
+		Self::from_media_source(Box::new(cursor))
+	}
+}
@@ -0,0 +1,26 @@
+use std::io::Cursor;
+
+use crate::sound::{AudioInfo, FromFileError};
+
+#[test]
+#[cfg(feature = "ogg")]
+fn reads_audio_info() {
+	use crate::sound::static_sound::{StaticSoundData, StaticSoundSettings};
+
+	const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../examples/assets/blip.ogg");
+	let info = AudioInfo::from_file(PATH).unwrap();
+	assert_eq!(info.sample_rate, 44100);
+	assert_eq!(info.channels, Some(2));
+	assert_eq!(info.codec, Some("vorbis"));
+	// the reported duration should match the fully decoded sound
+	let data = StaticSoundData::from_file(PATH, StaticSoundSettings::new()).unwrap();
+	assert!((info.duration.unwrap().as_secs_f64() - data.duration().as_secs_f64()).abs() < 0.001);
+}
+
+#[test]
+fn reports_unrecognized_formats() {
+	assert!(matches!(
+		AudioInfo::from_cursor(Cursor::new(vec![0u8; 64])),
+		Err(FromFileError::SymphoniaError(_))
+	));
+}
@@ -19,6 +19,8 @@ pub enum FromFileError {
 	IoError(std::io::Error),
 	/// An error occurred when parsing the file.
 	SymphoniaError(symphonia::core::errors::Error),
+	/// The thread loading the file in the background panicked.
+	LoaderThreadPanicked,
 }
 
 impl Display for FromFileError {
@@ -35,6 +37,9 @@ impl Display for FromFileError {
 			}
 			FromFileError::IoError(error) => error.fmt(f),
 			FromFileError::SymphoniaError(error) => error.fmt(f),
+			FromFileError::LoaderThreadPanicked => {
+				f.write_str("The thread loading the file panicked")
+			}
 		}
 	}
 }
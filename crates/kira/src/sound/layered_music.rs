@@ -0,0 +1,34 @@
+//! Plays multiple stems of a piece of music in sync.
+//!
+//! Adaptive game music is often split into stems (like drums, bass,
+//! and melody) that are faded in and out depending on what's happening
+//! in the game. [`LayeredMusicData`] plays a set of
+//! [`StaticSoundData`](super::static_sound::StaticSoundData) stems
+//! from a single shared playback position, so the stems always stay
+//! sample-aligned, even after seeking. Each stem's volume can be
+//! changed independently using the [`LayeredMusicHandle`].
+
+mod data;
+mod error;
+mod handle;
+mod settings;
+mod sound;
+
+#[cfg(test)]
+mod test;
+
+pub use data::*;
+pub use error::*;
+pub use handle::*;
+pub use settings::*;
+
+use crate::{tween::Tween, Volume};
+
+enum Command {
+	SetVolume(Volume, Tween),
+	SetStemVolume(usize, Volume, Tween),
+	Pause(Tween),
+	Resume(Tween),
+	Stop(Tween),
+	SeekTo(f64),
+}
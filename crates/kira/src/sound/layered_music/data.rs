@@ -0,0 +1,74 @@
+use ringbuf::RingBuffer;
+
+use crate::{
+	sound::{static_sound::StaticSoundData, Sound, SoundData},
+	LoopBehavior,
+};
+
+use super::{sound::LayeredMusic, LayeredMusicError, LayeredMusicHandle, LayeredMusicSettings};
+
+const COMMAND_BUFFER_CAPACITY: usize = 8;
+
+/// Multiple stems of a piece of music that are played in sync.
+#[derive(Clone)]
+pub struct LayeredMusicData {
+	/// The stems of the music.
+	///
+	/// Every stem must have the same sample rate and number of
+	/// frames. Each stem starts at the volume and panning from
+	/// its own settings, and the rest of its settings are ignored.
+	pub stems: Vec<StaticSoundData>,
+	/// Settings for the music.
+	pub settings: LayeredMusicSettings,
+}
+
+impl LayeredMusicData {
+	/// Creates a new [`LayeredMusicData`] from a list of stems.
+	pub fn new(stems: Vec<StaticSoundData>, settings: LayeredMusicSettings) -> Self {
+		Self { stems, settings }
+	}
+
+	/// Checks that the stems line up with each other and that the
+	/// loop start position is within the bounds of the music.
+	fn validate(&self) -> Result<(), LayeredMusicError> {
+		let first_stem = self.stems.first().ok_or(LayeredMusicError::NoStems)?;
+		for stem in &self.stems {
+			if stem.sample_rate != first_stem.sample_rate {
+				return Err(LayeredMusicError::SampleRateMismatch);
+			}
+			if stem.frames.len() != first_stem.frames.len() {
+				return Err(LayeredMusicError::LengthMismatch);
+			}
+		}
+		if let Some(LoopBehavior { start_position }) = self.settings.loop_behavior {
+			let duration = first_stem.duration().as_secs_f64();
+			if !(0.0..duration).contains(&start_position) {
+				return Err(LayeredMusicError::LoopStartOutOfBounds);
+			}
+		}
+		Ok(())
+	}
+}
+
+impl SoundData for LayeredMusicData {
+	type Error = LayeredMusicError;
+
+	type Handle = LayeredMusicHandle;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		self.validate()?;
+		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_BUFFER_CAPACITY).split();
+		let num_stems = self.stems.len();
+		let sound = LayeredMusic::new(self, command_consumer);
+		let shared = sound.shared();
+		Ok((
+			Box::new(sound),
+			LayeredMusicHandle {
+				command_producer,
+				shared,
+				num_stems,
+			},
+		))
+	}
+}
@@ -0,0 +1,105 @@
+use std::{error::Error, fmt::Display};
+
+use crate::CommandError;
+
+/// Errors that can occur when preparing layered music for playback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LayeredMusicError {
+	/// The music has no stems.
+	NoStems,
+	/// The stems don't all have the same sample rate.
+	SampleRateMismatch,
+	/// The stems don't all have the same number of frames.
+	LengthMismatch,
+	/// The loop start position of the music is negative or not
+	/// before the end of the music.
+	LoopStartOutOfBounds,
+}
+
+impl Display for LayeredMusicError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(match self {
+			LayeredMusicError::NoStems => "The music has no stems",
+			LayeredMusicError::SampleRateMismatch => {
+				"The stems don't all have the same sample rate"
+			}
+			LayeredMusicError::LengthMismatch => "The stems don't all have the same length",
+			LayeredMusicError::LoopStartOutOfBounds => {
+				"The loop start position of the music is negative or not before the end of the music"
+			}
+		})
+	}
+}
+
+impl Error for LayeredMusicError {}
+
+/// Errors that can occur when changing the volume of a stem.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SetStemVolumeError {
+	/// The music does not have a stem with the given index.
+	NonexistentStem,
+	/// An error occurred when sending a command to the audio renderer.
+	CommandError(CommandError),
+}
+
+impl Display for SetStemVolumeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			SetStemVolumeError::NonexistentStem => {
+				f.write_str("The music does not have a stem with the given index")
+			}
+			SetStemVolumeError::CommandError(error) => error.fmt(f),
+		}
+	}
+}
+
+impl Error for SetStemVolumeError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			SetStemVolumeError::CommandError(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<CommandError> for SetStemVolumeError {
+	fn from(v: CommandError) -> Self {
+		Self::CommandError(v)
+	}
+}
+
+/// Errors that can occur when seeking layered music.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SeekError {
+	/// The seek position is NaN or infinite.
+	NonFinitePosition,
+	/// An error occurred when sending a command to the audio renderer.
+	CommandError(CommandError),
+}
+
+impl Display for SeekError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			SeekError::NonFinitePosition => f.write_str("The seek position is NaN or infinite"),
+			SeekError::CommandError(error) => error.fmt(f),
+		}
+	}
+}
+
+impl Error for SeekError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			SeekError::CommandError(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<CommandError> for SeekError {
+	fn from(v: CommandError) -> Self {
+		Self::CommandError(v)
+	}
+}
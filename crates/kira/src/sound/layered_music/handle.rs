@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use ringbuf::Producer;
+
+use crate::{
+	sound::{playback_state_manager::Shared, static_sound::PlaybackState},
+	tween::Tween,
+	CommandError, Volume,
+};
+
+use super::{Command, SeekError, SetStemVolumeError};
+
+/// Controls layered music.
+pub struct LayeredMusicHandle {
+	pub(super) command_producer: Producer<Command>,
+	pub(super) shared: Arc<Shared>,
+	pub(super) num_stems: usize,
+}
+
+impl LayeredMusicHandle {
+	/// Returns the current playback state of the music.
+	pub fn state(&self) -> PlaybackState {
+		self.shared.state()
+	}
+
+	/// Returns `false` if the music has stopped and will be
+	/// (or already has been) removed from the audio renderer.
+	///
+	/// This is only advisory: the music may finish right after
+	/// this returns `true`.
+	pub fn is_alive(&self) -> bool {
+		self.shared.state() != PlaybackState::Stopped
+	}
+
+	/// Returns the current playback position of the music (in seconds).
+	pub fn position(&self) -> f64 {
+		self.shared.position()
+	}
+
+	/// Returns the number of stems in the music.
+	pub fn num_stems(&self) -> usize {
+		self.num_stems
+	}
+
+	/// Sets the volume of all the stems together (as a factor
+	/// of the original volume).
+	pub fn set_volume(
+		&mut self,
+		volume: impl Into<Volume>,
+		tween: Tween,
+	) -> Result<(), CommandError> {
+		self.push_command(Command::SetVolume(volume.into(), tween))
+	}
+
+	/// Sets the volume of a single stem, where `stem` is the stem's
+	/// index in [`LayeredMusicData::stems`](super::LayeredMusicData::stems).
+	pub fn set_stem_volume(
+		&mut self,
+		stem: usize,
+		volume: impl Into<Volume>,
+		tween: Tween,
+	) -> Result<(), SetStemVolumeError> {
+		if stem >= self.num_stems {
+			return Err(SetStemVolumeError::NonexistentStem);
+		}
+		self.push_command(Command::SetStemVolume(stem, volume.into(), tween))?;
+		Ok(())
+	}
+
+	/// Fades out the music to silence with the given tween and then
+	/// pauses playback of every stem.
+	pub fn pause(&mut self, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::Pause(tween))
+	}
+
+	/// Resumes playback of every stem and fades in the music from
+	/// silence with the given tween.
+	pub fn resume(&mut self, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::Resume(tween))
+	}
+
+	/// Fades out the music to silence with the given tween and then
+	/// stops playback.
+	///
+	/// Once the music is stopped, it cannot be restarted.
+	pub fn stop(&mut self, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::Stop(tween))
+	}
+
+	/// Sets the playback position of every stem to the specified
+	/// time in seconds.
+	///
+	/// If the music is looping, positions past the end are wrapped
+	/// back into the loop region.
+	pub fn seek_to(&mut self, position: f64) -> Result<(), SeekError> {
+		if !position.is_finite() {
+			return Err(SeekError::NonFinitePosition);
+		}
+		self.push_command(Command::SeekTo(position))?;
+		Ok(())
+	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		if !self.is_alive() {
+			return Err(CommandError::ResourceRemoved);
+		}
+		self.command_producer
+			.push(command)
+			.map_err(|_| CommandError::CommandQueueFull)
+	}
+}
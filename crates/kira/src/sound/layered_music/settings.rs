@@ -0,0 +1,69 @@
+use crate::{track::TrackId, tween::Tween, LoopBehavior, Volume};
+
+/// Settings for layered music.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct LayeredMusicSettings {
+	/// The volume of all the stems together.
+	pub volume: Volume,
+	/// The looping behavior of the music.
+	///
+	/// The loop start position must be before the end of the music,
+	/// or the music will fail to play with
+	/// [`LayeredMusicError::LoopStartOutOfBounds`](super::LayeredMusicError::LoopStartOutOfBounds).
+	pub loop_behavior: Option<LoopBehavior>,
+	/// The mixer track the music should play on.
+	pub track: TrackId,
+	/// An optional fade-in from silence.
+	pub fade_in_tween: Option<Tween>,
+}
+
+impl LayeredMusicSettings {
+	/// Creates a new [`LayeredMusicSettings`] with the default settings.
+	pub fn new() -> Self {
+		Self {
+			volume: Volume::Amplitude(1.0),
+			loop_behavior: None,
+			track: TrackId::Main,
+			fade_in_tween: None,
+		}
+	}
+
+	/// Sets the volume of all the stems together.
+	pub fn volume(self, volume: impl Into<Volume>) -> Self {
+		Self {
+			volume: volume.into(),
+			..self
+		}
+	}
+
+	/// Sets the looping behavior of the music.
+	pub fn loop_behavior(self, loop_behavior: impl Into<Option<LoopBehavior>>) -> Self {
+		Self {
+			loop_behavior: loop_behavior.into(),
+			..self
+		}
+	}
+
+	/// Sets the mixer track the music should play on.
+	pub fn track(self, track: impl Into<TrackId>) -> Self {
+		Self {
+			track: track.into(),
+			..self
+		}
+	}
+
+	/// Sets the tween used to fade in the music from silence.
+	pub fn fade_in_tween(self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
+		Self {
+			fade_in_tween: fade_in_tween.into(),
+			..self
+		}
+	}
+}
+
+impl Default for LayeredMusicSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
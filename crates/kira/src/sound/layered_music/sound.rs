@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use ringbuf::Consumer;
+
+use crate::{
+	clock::ClockTime,
+	dsp::Frame,
+	sound::{
+		playback_state_manager::{PlaybackStateManager, Shared},
+		static_sound::{PlaybackState, StaticSoundData},
+		Sound,
+	},
+	track::TrackId,
+	tween::{Tween, Tweener},
+	LoopBehavior, Volume,
+};
+
+use super::{Command, LayeredMusicData};
+
+struct Stem {
+	data: StaticSoundData,
+	volume: Tweener<Volume>,
+}
+
+pub(super) struct LayeredMusic {
+	command_consumer: Consumer<Command>,
+	stems: Vec<Stem>,
+	loop_behavior: Option<LoopBehavior>,
+	track: TrackId,
+	playback_state_manager: PlaybackStateManager,
+	/// The playback position shared by all of the stems (in seconds).
+	position: f64,
+	/// The duration of every stem (in seconds).
+	duration: f64,
+	volume: Tweener<Volume>,
+}
+
+impl LayeredMusic {
+	pub fn new(data: LayeredMusicData, command_consumer: Consumer<Command>) -> Self {
+		let settings = data.settings;
+		let duration = data.stems[0].duration().as_secs_f64();
+		Self {
+			command_consumer,
+			stems: data
+				.stems
+				.into_iter()
+				.map(|data| Stem {
+					volume: Tweener::new(data.settings.volume),
+					data,
+				})
+				.collect(),
+			loop_behavior: settings.loop_behavior,
+			track: settings.track,
+			playback_state_manager: PlaybackStateManager::new(settings.fade_in_tween),
+			position: 0.0,
+			duration,
+			volume: Tweener::new(settings.volume),
+		}
+	}
+
+	pub fn shared(&self) -> Arc<Shared> {
+		self.playback_state_manager.shared()
+	}
+
+	/// Wraps the playback position back into the loop region if the
+	/// music is looping, or stops the music if it reached the end.
+	fn handle_end_of_music(&mut self) {
+		if self.position < self.duration {
+			return;
+		}
+		if let Some(LoopBehavior { start_position }) = self.loop_behavior {
+			let loop_duration = self.duration - start_position;
+			self.position =
+				start_position + (self.position - start_position).rem_euclid(loop_duration);
+		} else {
+			self.playback_state_manager
+				.set_state(PlaybackState::Stopped);
+		}
+	}
+}
+
+impl Sound for LayeredMusic {
+	fn track(&mut self) -> TrackId {
+		self.track
+	}
+
+	fn on_start_processing(&mut self) {
+		self.playback_state_manager.set_position(self.position);
+		while let Some(command) = self.command_consumer.pop() {
+			match command {
+				Command::SetVolume(volume, tween) => self.volume.set(volume, tween),
+				Command::SetStemVolume(stem, volume, tween) => {
+					if let Some(stem) = self.stems.get_mut(stem) {
+						stem.volume.set(volume, tween);
+					}
+				}
+				Command::Pause(tween) => self.pause(tween),
+				Command::Resume(tween) => self.resume(tween),
+				Command::Stop(tween) => self.stop(tween),
+				Command::SeekTo(position) => {
+					self.position = position.max(0.0);
+					self.handle_end_of_music();
+				}
+			}
+		}
+	}
+
+	fn process(&mut self, dt: f64) -> Frame {
+		self.volume.update(dt);
+		for stem in &mut self.stems {
+			stem.volume.update(dt);
+		}
+		self.playback_state_manager.update(dt);
+		if self.playback_state_manager.is_silent() {
+			return Frame::ZERO;
+		}
+		let mut out = Frame::ZERO;
+		for Stem { data, volume } in &self.stems {
+			out += (data.frame_at_position(self.position) * volume.value().as_amplitude() as f32)
+				.panned(data.settings.panning as f32);
+		}
+		self.position += dt;
+		self.handle_end_of_music();
+		out * self.playback_state_manager.fade_volume().as_amplitude() as f32
+			* self.volume.value().as_amplitude() as f32
+	}
+
+	fn on_clock_tick(&mut self, time: ClockTime) {
+		self.volume.on_clock_tick(time);
+		self.playback_state_manager.on_clock_tick(time);
+		for stem in &mut self.stems {
+			stem.volume.on_clock_tick(time);
+		}
+	}
+
+	fn pause(&mut self, fade_out_tween: Tween) {
+		self.playback_state_manager.pause(fade_out_tween);
+	}
+
+	fn resume(&mut self, fade_in_tween: Tween) {
+		self.playback_state_manager.resume(fade_in_tween);
+	}
+
+	fn stop(&mut self, fade_out_tween: Tween) {
+		self.playback_state_manager.stop(fade_out_tween);
+	}
+
+	fn finished(&self) -> bool {
+		self.playback_state_manager.finished()
+	}
+}
@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use crate::{
+	dsp::Frame,
+	sound::{
+		static_sound::{PlaybackState, StaticSoundData, StaticSoundSettings},
+		SoundData,
+	},
+	tween::Tween,
+	LoopBehavior, Volume,
+};
+
+use super::{
+	LayeredMusicData, LayeredMusicError, LayeredMusicSettings, SeekError, SetStemVolumeError,
+};
+
+fn stem(scale: f32, num_frames: usize) -> StaticSoundData {
+	StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(
+			(0..num_frames)
+				.map(|i| Frame::from_mono(i as f32 * scale))
+				.collect(),
+		),
+		settings: StaticSoundSettings::new(),
+	}
+}
+
+/// Tests that all of the stems play in sync and the music
+/// stops at the end.
+#[test]
+fn plays_stems_in_sync() {
+	let data = LayeredMusicData::new(
+		vec![stem(1.0, 3), stem(10.0, 3)],
+		LayeredMusicSettings::new(),
+	);
+	let (mut sound, handle) = data.into_sound().unwrap();
+	sound.on_start_processing();
+	for i in 0..3 {
+		assert_eq!(sound.process(1.0), Frame::from_mono(i as f32 * 11.0));
+	}
+	assert!(sound.finished());
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+}
+
+/// Tests that the stems stay frame-aligned after seeking.
+#[test]
+fn stems_stay_aligned_after_seek() {
+	let data = LayeredMusicData::new(
+		vec![stem(1.0, 10), stem(10.0, 10)],
+		LayeredMusicSettings::new(),
+	);
+	let (mut sound, mut handle) = data.into_sound().unwrap();
+	sound.on_start_processing();
+	sound.process(1.0);
+	handle.seek_to(5.0).unwrap();
+	sound.on_start_processing();
+	assert_eq!(sound.process(1.0), Frame::from_mono(55.0));
+	assert_eq!(sound.process(1.0), Frame::from_mono(66.0));
+}
+
+/// Tests that each stem's volume can be changed independently.
+#[test]
+fn set_stem_volume() {
+	let data = LayeredMusicData::new(
+		vec![stem(1.0, 10), stem(10.0, 10)],
+		LayeredMusicSettings::new(),
+	);
+	let (mut sound, mut handle) = data.into_sound().unwrap();
+	handle
+		.set_stem_volume(1, Volume::Amplitude(0.0), Tween::default())
+		.unwrap();
+	assert!(matches!(
+		handle.set_stem_volume(2, Volume::Amplitude(0.0), Tween::default()),
+		Err(SetStemVolumeError::NonexistentStem)
+	));
+	sound.on_start_processing();
+	sound.process(1.0);
+	assert_eq!(sound.process(1.0), Frame::from_mono(1.0));
+	assert_eq!(sound.process(1.0), Frame::from_mono(2.0));
+}
+
+/// Tests that looping music wraps around to the loop start position.
+#[test]
+fn loops() {
+	let data = LayeredMusicData::new(
+		vec![stem(1.0, 4)],
+		LayeredMusicSettings::new().loop_behavior(LoopBehavior {
+			start_position: 2.0,
+		}),
+	);
+	let (mut sound, _) = data.into_sound().unwrap();
+	sound.on_start_processing();
+	let output: Vec<f32> = (0..8).map(|_| sound.process(1.0).left).collect();
+	assert_eq!(output, vec![0.0, 1.0, 2.0, 3.0, 2.0, 3.0, 2.0, 3.0]);
+	assert!(!sound.finished());
+}
+
+/// Tests that seeking far past the end of looping music wraps
+/// the position into the loop region, and that non-finite seek
+/// positions are rejected.
+#[test]
+fn seeks_past_end_of_loop() {
+	let data = LayeredMusicData::new(
+		vec![stem(1.0, 4)],
+		LayeredMusicSettings::new().loop_behavior(LoopBehavior {
+			start_position: 2.0,
+		}),
+	);
+	let (mut sound, mut handle) = data.into_sound().unwrap();
+	handle.seek_to(1.0e15 + 1.0).unwrap();
+	sound.on_start_processing();
+	assert_eq!(sound.process(1.0), Frame::from_mono(3.0));
+	assert_eq!(sound.process(1.0), Frame::from_mono(2.0));
+	assert!(matches!(
+		handle.seek_to(f64::INFINITY),
+		Err(SeekError::NonFinitePosition)
+	));
+	assert!(matches!(
+		handle.seek_to(f64::NAN),
+		Err(SeekError::NonFinitePosition)
+	));
+}
+
+/// Tests that stems that don't line up are rejected.
+#[test]
+fn validates_stems() {
+	assert!(matches!(
+		LayeredMusicData::new(vec![], LayeredMusicSettings::new()).into_sound(),
+		Err(LayeredMusicError::NoStems)
+	));
+	assert!(matches!(
+		LayeredMusicData::new(
+			vec![stem(1.0, 3), stem(1.0, 4)],
+			LayeredMusicSettings::new()
+		)
+		.into_sound(),
+		Err(LayeredMusicError::LengthMismatch)
+	));
+	let mut other_sample_rate = stem(1.0, 3);
+	other_sample_rate.sample_rate = 2;
+	assert!(matches!(
+		LayeredMusicData::new(
+			vec![stem(1.0, 3), other_sample_rate],
+			LayeredMusicSettings::new()
+		)
+		.into_sound(),
+		Err(LayeredMusicError::SampleRateMismatch)
+	));
+}
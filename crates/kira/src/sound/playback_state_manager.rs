@@ -0,0 +1,138 @@
+use std::sync::{
+	atomic::{AtomicU64, AtomicU8, Ordering},
+	Arc,
+};
+
+use crate::{
+	clock::ClockTime,
+	sound::static_sound::PlaybackState,
+	tween::{Tween, Tweener},
+	Volume,
+};
+
+/// The playback state and position of a sound, shared
+/// between the sound and its handle.
+pub(crate) struct Shared {
+	state: AtomicU8,
+	position: AtomicU64,
+}
+
+impl Shared {
+	fn new() -> Self {
+		Self {
+			state: AtomicU8::new(PlaybackState::Playing as u8),
+			position: AtomicU64::new(0.0f64.to_bits()),
+		}
+	}
+
+	pub fn state(&self) -> PlaybackState {
+		match self.state.load(Ordering::SeqCst) {
+			0 => PlaybackState::Playing,
+			1 => PlaybackState::Pausing,
+			2 => PlaybackState::Paused,
+			3 => PlaybackState::Stopping,
+			4 => PlaybackState::Stopped,
+			_ => panic!("Invalid playback state"),
+		}
+	}
+
+	pub fn position(&self) -> f64 {
+		f64::from_bits(self.position.load(Ordering::SeqCst))
+	}
+
+	pub fn set_position(&self, position: f64) {
+		self.position.store(position.to_bits(), Ordering::SeqCst);
+	}
+}
+
+/// Moves a sound between playback states, fading its volume
+/// when it's paused, resumed, or stopped.
+pub(crate) struct PlaybackStateManager {
+	state: PlaybackState,
+	volume_fade: Tweener<Volume>,
+	shared: Arc<Shared>,
+}
+
+impl PlaybackStateManager {
+	pub fn new(fade_in_tween: Option<Tween>) -> Self {
+		Self {
+			state: PlaybackState::Playing,
+			volume_fade: if let Some(tween) = fade_in_tween {
+				let mut tweenable = Tweener::new(tween.fade_curve.silent_volume());
+				tweenable.set(tween.fade_curve.full_volume(), tween);
+				tweenable
+			} else {
+				Tweener::new(Volume::Decibels(0.0))
+			},
+			shared: Arc::new(Shared::new()),
+		}
+	}
+
+	pub fn shared(&self) -> Arc<Shared> {
+		self.shared.clone()
+	}
+
+	/// Reports the playback position to the handle.
+	pub fn set_position(&self, position: f64) {
+		self.shared.set_position(position);
+	}
+
+	pub fn set_state(&mut self, state: PlaybackState) {
+		self.state = state;
+		self.shared.state.store(state as u8, Ordering::SeqCst);
+	}
+
+	/// Returns `true` if the sound shouldn't produce any output.
+	pub fn is_silent(&self) -> bool {
+		matches!(self.state, PlaybackState::Paused | PlaybackState::Stopped)
+	}
+
+	/// The current volume of the pause, resume, and stop fades.
+	pub fn fade_volume(&self) -> Volume {
+		self.volume_fade.value()
+	}
+
+	/// Updates the volume fade, finishing pausing or stopping
+	/// the sound once the fade is done.
+	pub fn update(&mut self, dt: f64) {
+		if self.volume_fade.update(dt) {
+			match self.state {
+				PlaybackState::Pausing => self.set_state(PlaybackState::Paused),
+				PlaybackState::Stopping => self.set_state(PlaybackState::Stopped),
+				_ => {}
+			}
+		}
+	}
+
+	pub fn on_clock_tick(&mut self, time: ClockTime) {
+		self.volume_fade.on_clock_tick(time);
+	}
+
+	pub fn pause(&mut self, fade_out_tween: Tween) {
+		if matches!(self.state, PlaybackState::Stopping | PlaybackState::Stopped) {
+			return;
+		}
+		self.set_state(PlaybackState::Pausing);
+		self.volume_fade
+			.set(fade_out_tween.fade_curve.silent_volume(), fade_out_tween);
+	}
+
+	pub fn resume(&mut self, fade_in_tween: Tween) {
+		if matches!(self.state, PlaybackState::Stopping | PlaybackState::Stopped) {
+			return;
+		}
+		self.set_state(PlaybackState::Playing);
+		self.volume_fade
+			.set(fade_in_tween.fade_curve.full_volume(), fade_in_tween);
+	}
+
+	pub fn stop(&mut self, fade_out_tween: Tween) {
+		self.set_state(PlaybackState::Stopping);
+		self.volume_fade
+			.set(fade_out_tween.fade_curve.silent_volume(), fade_out_tween);
+	}
+
+	pub fn finished(&self) -> bool {
+		self.state == PlaybackState::Stopped
+	}
+}
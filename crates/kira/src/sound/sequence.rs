@@ -0,0 +1,33 @@
+//! Plays a list of static sounds at scheduled times as a single sound.
+//!
+//! A [`SequenceSoundData`] holds a list of entries, each with a
+//! [`StaticSoundData`](super::static_sound::StaticSoundData) and
+//! the time (in seconds) it should start at relative to the start
+//! of the sequence. Entries can overlap, which makes sequences useful
+//! for layered stingers and segments of adaptive music that should
+//! be controlled as one unit.
+
+mod data;
+mod error;
+mod handle;
+mod settings;
+mod sound;
+
+#[cfg(test)]
+mod test;
+
+pub use data::*;
+pub use error::*;
+pub use handle::*;
+pub use settings::*;
+
+use crate::{tween::Tween, Volume};
+
+enum Command {
+	SetVolume(Volume, Tween),
+	Pause(Tween),
+	Resume(Tween),
+	Stop(Tween),
+	SeekTo(f64),
+	Skip,
+}
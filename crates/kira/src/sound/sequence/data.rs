@@ -0,0 +1,82 @@
+use std::convert::Infallible;
+
+use ringbuf::RingBuffer;
+
+use crate::sound::{static_sound::StaticSoundData, Sound, SoundData};
+
+use super::{sound::SequenceSound, SequenceSoundHandle, SequenceSoundSettings};
+
+const COMMAND_BUFFER_CAPACITY: usize = 8;
+
+/// A sound in a sequence and the time it starts at.
+#[derive(Clone)]
+pub struct SequenceEntry {
+	/// The audio to play.
+	///
+	/// Only the volume and panning in the sound's settings are used.
+	/// Entries always play once from the beginning at their original
+	/// playback rate.
+	pub data: StaticSoundData,
+	/// When the sound should start playing (in seconds), relative
+	/// to the start of the sequence.
+	///
+	/// A negative start offset will cause the sound to start
+	/// partway through.
+	pub start_offset: f64,
+}
+
+/// A list of static sounds that are played at scheduled times
+/// as a single sound.
+#[derive(Clone)]
+pub struct SequenceSoundData {
+	/// The sounds in the sequence.
+	pub entries: Vec<SequenceEntry>,
+	/// Settings for the sequence.
+	pub settings: SequenceSoundSettings,
+}
+
+impl SequenceSoundData {
+	/// Creates a new, empty [`SequenceSoundData`].
+	pub fn new(settings: SequenceSoundSettings) -> Self {
+		Self {
+			entries: vec![],
+			settings,
+		}
+	}
+
+	/// Adds a sound that starts playing `start_offset` seconds
+	/// after the start of the sequence.
+	pub fn with_entry(mut self, data: StaticSoundData, start_offset: f64) -> Self {
+		self.entries.push(SequenceEntry { data, start_offset });
+		self
+	}
+
+	/// Returns the time (in seconds) when the last sound in
+	/// the sequence finishes.
+	pub fn duration(&self) -> f64 {
+		self.entries
+			.iter()
+			.map(|entry| entry.start_offset + entry.data.duration().as_secs_f64())
+			.fold(0.0, f64::max)
+	}
+}
+
+impl SoundData for SequenceSoundData {
+	type Error = Infallible;
+
+	type Handle = SequenceSoundHandle;
+
+	#[allow(clippy::type_complexity)]
+	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_BUFFER_CAPACITY).split();
+		let sound = SequenceSound::new(self, command_consumer);
+		let shared = sound.shared();
+		Ok((
+			Box::new(sound),
+			SequenceSoundHandle {
+				command_producer,
+				shared,
+			},
+		))
+	}
+}
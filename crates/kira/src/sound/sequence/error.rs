@@ -0,0 +1,37 @@
+use std::{error::Error, fmt::Display};
+
+use crate::CommandError;
+
+/// Errors that can occur when seeking a sequence.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SeekError {
+	/// The seek position is NaN or infinite.
+	NonFinitePosition,
+	/// An error occurred when sending a command to the audio renderer.
+	CommandError(CommandError),
+}
+
+impl Display for SeekError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			SeekError::NonFinitePosition => f.write_str("The seek position is NaN or infinite"),
+			SeekError::CommandError(error) => error.fmt(f),
+		}
+	}
+}
+
+impl Error for SeekError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			SeekError::CommandError(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<CommandError> for SeekError {
+	fn from(v: CommandError) -> Self {
+		Self::CommandError(v)
+	}
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use ringbuf::Producer;
+
+use crate::{
+	sound::{playback_state_manager::Shared, static_sound::PlaybackState},
+	tween::Tween,
+	CommandError, Volume,
+};
+
+use super::{Command, SeekError};
+
+/// Controls a sequence of sounds.
+pub struct SequenceSoundHandle {
+	pub(super) command_producer: Producer<Command>,
+	pub(super) shared: Arc<Shared>,
+}
+
+impl SequenceSoundHandle {
+	/// Returns the current playback state of the sequence.
+	pub fn state(&self) -> PlaybackState {
+		self.shared.state()
+	}
+
+	/// Returns `false` if the sequence has stopped and will be
+	/// (or already has been) removed from the audio renderer.
+	///
+	/// This is only advisory: the sequence may finish right after
+	/// this returns `true`.
+	pub fn is_alive(&self) -> bool {
+		self.shared.state() != PlaybackState::Stopped
+	}
+
+	/// Returns the current position on the sequence's timeline
+	/// (in seconds).
+	pub fn position(&self) -> f64 {
+		self.shared.position()
+	}
+
+	/// Sets the volume of the sequence (as a factor of the original volume).
+	pub fn set_volume(
+		&mut self,
+		volume: impl Into<Volume>,
+		tween: Tween,
+	) -> Result<(), CommandError> {
+		self.push_command(Command::SetVolume(volume.into(), tween))
+	}
+
+	/// Fades out the sequence to silence with the given tween and then
+	/// pauses playback.
+	pub fn pause(&mut self, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::Pause(tween))
+	}
+
+	/// Resumes playback and fades in the sequence from silence
+	/// with the given tween.
+	pub fn resume(&mut self, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::Resume(tween))
+	}
+
+	/// Fades out the sequence to silence with the given tween and then
+	/// stops playback.
+	///
+	/// Once the sequence is stopped, it cannot be restarted.
+	pub fn stop(&mut self, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::Stop(tween))
+	}
+
+	/// Moves the playback position to the specified time on the
+	/// sequence's timeline (in seconds).
+	///
+	/// Every entry that overlaps that time will play from the
+	/// corresponding point in the entry. Positions before the
+	/// start of the sequence are treated as the start.
+	pub fn seek_to(&mut self, position: f64) -> Result<(), SeekError> {
+		if !position.is_finite() {
+			return Err(SeekError::NonFinitePosition);
+		}
+		self.push_command(Command::SeekTo(position))?;
+		Ok(())
+	}
+
+	/// Moves the playback position to the start of the next entry
+	/// in the sequence.
+	///
+	/// If there are no entries that start after the current position,
+	/// the sequence stops.
+	pub fn skip(&mut self) -> Result<(), CommandError> {
+		self.push_command(Command::Skip)
+	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		if !self.is_alive() {
+			return Err(CommandError::ResourceRemoved);
+		}
+		self.command_producer
+			.push(command)
+			.map_err(|_| CommandError::CommandQueueFull)
+	}
+}
@@ -0,0 +1,54 @@
+use crate::{track::TrackId, tween::Tween, Volume};
+
+/// Settings for a sequence of sounds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct SequenceSoundSettings {
+	/// The volume of the whole sequence.
+	pub volume: Volume,
+	/// The mixer track the sequence should play on.
+	pub track: TrackId,
+	/// An optional fade-in from silence.
+	pub fade_in_tween: Option<Tween>,
+}
+
+impl SequenceSoundSettings {
+	/// Creates a new [`SequenceSoundSettings`] with the default settings.
+	pub fn new() -> Self {
+		Self {
+			volume: Volume::Amplitude(1.0),
+			track: TrackId::Main,
+			fade_in_tween: None,
+		}
+	}
+
+	/// Sets the volume of the whole sequence.
+	pub fn volume(self, volume: impl Into<Volume>) -> Self {
+		Self {
+			volume: volume.into(),
+			..self
+		}
+	}
+
+	/// Sets the mixer track the sequence should play on.
+	pub fn track(self, track: impl Into<TrackId>) -> Self {
+		Self {
+			track: track.into(),
+			..self
+		}
+	}
+
+	/// Sets the tween used to fade in the sequence from silence.
+	pub fn fade_in_tween(self, fade_in_tween: impl Into<Option<Tween>>) -> Self {
+		Self {
+			fade_in_tween: fade_in_tween.into(),
+			..self
+		}
+	}
+}
+
+impl Default for SequenceSoundSettings {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use ringbuf::Consumer;
+
+use crate::{
+	clock::ClockTime,
+	dsp::Frame,
+	sound::{
+		playback_state_manager::{PlaybackStateManager, Shared},
+		static_sound::PlaybackState,
+		Sound,
+	},
+	track::TrackId,
+	tween::{Tween, Tweener},
+	Volume,
+};
+
+use super::{data::SequenceEntry, Command, SequenceSoundData};
+
+pub(super) struct SequenceSound {
+	command_consumer: Consumer<Command>,
+	entries: Vec<SequenceEntry>,
+	track: TrackId,
+	playback_state_manager: PlaybackStateManager,
+	/// The current position on the sequence's timeline (in seconds).
+	position: f64,
+	/// The time when the last entry finishes (in seconds).
+	duration: f64,
+	volume: Tweener<Volume>,
+}
+
+impl SequenceSound {
+	pub fn new(data: SequenceSoundData, command_consumer: Consumer<Command>) -> Self {
+		let duration = data.duration();
+		let settings = data.settings;
+		Self {
+			command_consumer,
+			entries: data.entries,
+			track: settings.track,
+			playback_state_manager: PlaybackStateManager::new(settings.fade_in_tween),
+			position: 0.0,
+			duration,
+			volume: Tweener::new(settings.volume),
+		}
+	}
+
+	pub fn shared(&self) -> Arc<Shared> {
+		self.playback_state_manager.shared()
+	}
+
+	/// Moves the playback position to the start of the first entry
+	/// that starts after the current position.
+	fn skip(&mut self) {
+		let next_start_offset = self
+			.entries
+			.iter()
+			.map(|entry| entry.start_offset)
+			.filter(|start_offset| *start_offset > self.position)
+			.fold(None, |min: Option<f64>, start_offset| {
+				Some(min.map_or(start_offset, |min| min.min(start_offset)))
+			});
+		self.position = next_start_offset.unwrap_or(self.duration);
+	}
+}
+
+impl Sound for SequenceSound {
+	fn track(&mut self) -> TrackId {
+		self.track
+	}
+
+	fn on_start_processing(&mut self) {
+		self.playback_state_manager.set_position(self.position);
+		while let Some(command) = self.command_consumer.pop() {
+			match command {
+				Command::SetVolume(volume, tween) => self.volume.set(volume, tween),
+				Command::Pause(tween) => self.pause(tween),
+				Command::Resume(tween) => self.resume(tween),
+				Command::Stop(tween) => self.stop(tween),
+				Command::SeekTo(position) => self.position = position.max(0.0),
+				Command::Skip => self.skip(),
+			}
+		}
+	}
+
+	fn process(&mut self, dt: f64) -> Frame {
+		self.volume.update(dt);
+		self.playback_state_manager.update(dt);
+		if self.playback_state_manager.is_silent() {
+			return Frame::ZERO;
+		}
+		if self.position >= self.duration {
+			self.playback_state_manager
+				.set_state(PlaybackState::Stopped);
+			return Frame::ZERO;
+		}
+		let mut out = Frame::ZERO;
+		for entry in &self.entries {
+			out += entry_frame(entry, self.position - entry.start_offset);
+		}
+		self.position += dt;
+		out * self.playback_state_manager.fade_volume().as_amplitude() as f32
+			* self.volume.value().as_amplitude() as f32
+	}
+
+	fn on_clock_tick(&mut self, time: ClockTime) {
+		self.volume.on_clock_tick(time);
+		self.playback_state_manager.on_clock_tick(time);
+	}
+
+	fn pause(&mut self, fade_out_tween: Tween) {
+		self.playback_state_manager.pause(fade_out_tween);
+	}
+
+	fn resume(&mut self, fade_in_tween: Tween) {
+		self.playback_state_manager.resume(fade_in_tween);
+	}
+
+	fn stop(&mut self, fade_out_tween: Tween) {
+		self.playback_state_manager.stop(fade_out_tween);
+	}
+
+	fn finished(&self) -> bool {
+		self.playback_state_manager.finished()
+	}
+}
+
+/// Returns the output of a sequence entry at the given time
+/// (in seconds) relative to the start of the entry.
+fn entry_frame(entry: &SequenceEntry, time: f64) -> Frame {
+	let out = entry.data.frame_at_position(time);
+	let settings = entry.data.settings;
+	(out * settings.volume.as_amplitude() as f32).panned(settings.panning as f32)
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use crate::{
+	dsp::Frame,
+	sound::{
+		static_sound::{PlaybackState, StaticSoundData, StaticSoundSettings},
+		SoundData,
+	},
+};
+
+use super::{SeekError, SequenceSoundData, SequenceSoundSettings};
+
+fn constant_sound(value: f32, num_frames: usize) -> StaticSoundData {
+	StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(value); num_frames]),
+		settings: StaticSoundSettings::new(),
+	}
+}
+
+/// Tests that a sequence plays each entry at its start offset,
+/// mixing overlapping entries, and finishes after the last entry.
+#[test]
+fn plays_entries_at_start_offsets() {
+	let data = SequenceSoundData::new(SequenceSoundSettings::new())
+		.with_entry(constant_sound(1.0, 2), 0.0)
+		.with_entry(constant_sound(2.0, 2), 1.0);
+	let (mut sound, handle) = data.into_sound().unwrap();
+	sound.on_start_processing();
+	assert_eq!(sound.process(1.0), Frame::from_mono(1.0));
+	assert_eq!(sound.process(1.0), Frame::from_mono(3.0));
+	assert_eq!(sound.process(1.0), Frame::from_mono(2.0));
+	assert!(!sound.finished());
+	assert_eq!(sound.process(1.0), Frame::ZERO);
+	assert!(sound.finished());
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+}
+
+/// Tests that skipping moves to the start of the next entry
+/// and stops the sequence after the last entry.
+#[test]
+#[allow(clippy::float_cmp)]
+fn skip() {
+	let data = SequenceSoundData::new(SequenceSoundSettings::new())
+		.with_entry(constant_sound(1.0, 10), 0.0)
+		.with_entry(constant_sound(2.0, 10), 10.0);
+	let (mut sound, mut handle) = data.into_sound().unwrap();
+	sound.on_start_processing();
+	assert_eq!(sound.process(1.0), Frame::from_mono(1.0));
+	handle.skip().unwrap();
+	sound.on_start_processing();
+	assert_eq!(sound.process(1.0), Frame::from_mono(2.0));
+	sound.on_start_processing();
+	assert_eq!(handle.position(), 11.0);
+	handle.skip().unwrap();
+	sound.on_start_processing();
+	assert_eq!(sound.process(1.0), Frame::ZERO);
+	assert!(sound.finished());
+}
+
+/// Tests that seeking moves every entry to the corresponding
+/// point on the sequence's timeline, and that non-finite
+/// positions are rejected.
+#[test]
+fn seek_to() {
+	let data = SequenceSoundData::new(SequenceSoundSettings::new()).with_entry(
+		StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new((0..10).map(|i| Frame::from_mono(i as f32)).collect()),
+			settings: StaticSoundSettings::new(),
+		},
+		2.0,
+	);
+	let (mut sound, mut handle) = data.into_sound().unwrap();
+	handle.seek_to(5.0).unwrap();
+	sound.on_start_processing();
+	assert_eq!(sound.process(1.0), Frame::from_mono(3.0));
+	assert_eq!(sound.process(1.0), Frame::from_mono(4.0));
+	assert!(matches!(
+		handle.seek_to(f64::INFINITY),
+		Err(SeekError::NonFinitePosition)
+	));
+	assert!(matches!(
+		handle.seek_to(f64::NAN),
+		Err(SeekError::NonFinitePosition)
+	));
+}
@@ -1,25 +1,30 @@
 //! Playable chunks of audio that are loaded into memory all at once.
 
 mod data;
+mod error;
 mod handle;
 mod settings;
 mod sound;
 
 pub use data::*;
+pub use error::*;
 pub use handle::*;
 pub use settings::*;
 pub use sound::PlaybackState;
 
-use crate::{tween::Tween, PlaybackRate, Volume};
+use crate::{tween::Tween, LoopBehavior, PlaybackRate, StartTime, Volume};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
 enum Command {
+	Start(StartTime),
 	SetVolume(Volume, Tween),
 	SetPlaybackRate(PlaybackRate, Tween),
 	SetPanning(f64, Tween),
 	Pause(Tween),
 	Resume(Tween),
 	Stop(Tween),
+	StopAfterLoop(Tween),
 	SeekBy(f64),
 	SeekTo(f64),
+	SetLoopBehavior(Option<LoopBehavior>),
+	QueueNext(Box<StaticSoundData>),
 }
@@ -1,21 +1,31 @@
 #[cfg(feature = "symphonia")]
 mod from_file;
+#[cfg(all(feature = "symphonia", not(target_arch = "wasm32")))]
+mod load_in_background;
 
 #[cfg(test)]
 mod test;
 
+#[cfg(feature = "symphonia")]
+pub use from_file::LoadReport;
+#[cfg(all(feature = "symphonia", not(target_arch = "wasm32")))]
+pub use load_in_background::*;
+
 use std::{sync::Arc, time::Duration};
 
 use ringbuf::RingBuffer;
 
 use crate::{
-	dsp::Frame,
+	dsp::{interpolate_frame, Frame},
 	sound::{Sound, SoundData},
+	track::effect::Effect,
+	LoopBehavior,
 };
 
-use super::{handle::StaticSoundHandle, sound::StaticSound, StaticSoundSettings};
-
-const COMMAND_BUFFER_CAPACITY: usize = 8;
+use super::{
+	handle::StaticSoundHandle, sound::StaticSound, ConcatError, MixError, StaticSoundError,
+	StaticSoundSettings,
+};
 
 /// A piece of audio loaded into memory all at once.
 ///
@@ -32,19 +42,320 @@ pub struct StaticSoundData {
 }
 
 impl StaticSoundData {
+	/// Creates a [`StaticSoundData`] from interleaved stereo samples
+	/// (left, right, left, right, ...).
+	///
+	/// If there's an odd number of samples, the last one is ignored.
+	pub fn from_interleaved_stereo(sample_rate: u32, samples: &[f32]) -> Self {
+		Self::from_frames(
+			sample_rate,
+			samples
+				.chunks_exact(2)
+				.map(|chunk| Frame::new(chunk[0], chunk[1]))
+				.collect(),
+		)
+	}
+
+	/// Creates a [`StaticSoundData`] from interleaved stereo 16-bit
+	/// samples (left, right, left, right, ...).
+	///
+	/// If there's an odd number of samples, the last one is ignored.
+	pub fn from_interleaved_stereo_i16(sample_rate: u32, samples: &[i16]) -> Self {
+		Self::from_frames(
+			sample_rate,
+			samples
+				.chunks_exact(2)
+				.map(|chunk| Frame::new(i16_to_f32(chunk[0]), i16_to_f32(chunk[1])))
+				.collect(),
+		)
+	}
+
+	fn from_frames(sample_rate: u32, frames: Vec<Frame>) -> Self {
+		Self {
+			sample_rate,
+			frames: Arc::new(frames),
+			settings: StaticSoundSettings::default(),
+		}
+	}
+
+	/// Returns a copy of the sound data with different settings.
+	///
+	/// This is cheap, since the audio data is shared with the
+	/// original sound data. The new settings completely replace
+	/// the old ones.
+	pub fn with_settings(&self, settings: StaticSoundSettings) -> Self {
+		Self {
+			settings,
+			..self.clone()
+		}
+	}
+
+	/// Returns a copy of the sound data with settings modified by
+	/// the given function.
+	///
+	/// The function receives the current settings, so any settings
+	/// it changes override the original ones, and any settings it
+	/// doesn't change are kept. This is useful for playing the same
+	/// sound with small variations each time:
+	///
+	/// ```no_run
+	/// # use kira::{
+	/// # 	manager::{AudioManager, AudioManagerSettings, backend::mock::MockBackend},
+	/// # 	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+	/// # };
+	/// # let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings::default()).unwrap();
+	/// let sound_data = StaticSoundData::from_file("sound.ogg", StaticSoundSettings::default())?;
+	/// manager.play(sound_data.with_modified_settings(|settings| settings.volume(0.5).panning(0.2)))?;
+	/// # Result::<(), Box<dyn std::error::Error>>::Ok(())
+	/// ```
+	pub fn with_modified_settings(
+		&self,
+		f: impl FnOnce(StaticSoundSettings) -> StaticSoundSettings,
+	) -> Self {
+		self.with_settings(f(self.settings))
+	}
+
 	/// Returns the duration of the audio.
 	pub fn duration(&self) -> Duration {
 		Duration::from_secs_f64(self.frames.len() as f64 / self.sample_rate as f64)
 	}
 
+	/// Returns the frame at the given position in the audio (in seconds),
+	/// interpolating between frames if the position is between two frames.
+	///
+	/// Returns [`Frame::ZERO`] if the position is before the start
+	/// or after the end of the audio. The sound's settings aren't
+	/// taken into account.
+	pub fn frame_at_position(&self, position: f64) -> Frame {
+		let position = position * self.sample_rate as f64;
+		if position < 0.0 || position >= self.frames.len() as f64 {
+			return Frame::ZERO;
+		}
+		let index = position as usize;
+		let frame_at = |index: Option<usize>| {
+			index
+				.and_then(|index| self.frames.get(index))
+				.copied()
+				.unwrap_or(Frame::ZERO)
+		};
+		interpolate_frame(
+			frame_at(index.checked_sub(1)),
+			frame_at(Some(index)),
+			frame_at(Some(index + 1)),
+			frame_at(Some(index + 2)),
+			position.fract() as f32,
+		)
+	}
+
+	/// Splits the audio into the given number of equally sized
+	/// buckets and returns the minimum and maximum sample value
+	/// (across both channels) in each bucket.
+	///
+	/// This is useful for drawing an overview of the waveform.
+	/// If the sound has fewer frames than the requested number of
+	/// buckets, one bucket per frame will be returned.
+	pub fn waveform_peaks(&self, buckets: usize) -> Vec<(f32, f32)> {
+		let num_frames = self.frames.len();
+		let buckets = buckets.min(num_frames);
+		(0..buckets)
+			.map(|i| {
+				let start = i * num_frames / buckets;
+				let end = (i + 1) * num_frames / buckets;
+				self.frames[start..end].iter().fold(
+					(f32::INFINITY, f32::NEG_INFINITY),
+					|(min, max), frame| {
+						(
+							min.min(frame.left).min(frame.right),
+							max.max(frame.left).max(frame.right),
+						)
+					},
+				)
+			})
+			.collect()
+	}
+
+	/// Renders the audio through a chain of effects and returns
+	/// the result as new sound data with the same settings.
+	///
+	/// Each effect is initialized with the sound's sample rate, and
+	/// `tail` worth of silence is processed after the end of the sound
+	/// so that the tails of effects like reverbs and delays are kept.
+	/// This is useful for baking effects into sounds ahead of time
+	/// instead of processing them while the sound plays.
+	///
+	/// Effects only receive commands from their handles when
+	/// [`Effect::on_start_processing`] is called, which happens once
+	/// before rendering starts, and clocks don't tick during rendering.
+	/// Any effect parameters that would normally be automated or
+	/// changed over time will stay at their initial values.
+	pub fn process_through(&self, effects: &mut [Box<dyn Effect>], tail: Duration) -> Self {
+		for effect in effects.iter_mut() {
+			effect.init(self.sample_rate);
+			effect.on_start_processing();
+		}
+		let dt = 1.0 / self.sample_rate as f64;
+		let num_tail_frames = (tail.as_secs_f64() * self.sample_rate as f64).ceil() as usize;
+		let frames = self
+			.frames
+			.iter()
+			.copied()
+			.chain((0..num_tail_frames).map(|_| Frame::ZERO))
+			.map(|frame| {
+				effects
+					.iter_mut()
+					.fold(frame, |frame, effect| effect.process(frame, dt))
+			})
+			.collect();
+		Self {
+			frames: Arc::new(frames),
+			..self.clone()
+		}
+	}
+
+	/// Joins this sound and the given sounds end-to-end into
+	/// new sound data with this sound's settings.
+	///
+	/// All of the sounds must have the same sample rate.
+	pub fn concat(&self, others: &[StaticSoundData]) -> Result<Self, ConcatError> {
+		self.concat_with_crossfade(others, Duration::ZERO)
+	}
+
+	/// Joins this sound and the given sounds end-to-end, overlapping
+	/// each pair of neighboring sounds by `crossfade` to prevent clicks
+	/// at the joins.
+	///
+	/// The end of each sound fades out linearly while the start of the
+	/// next sound fades in, so the result is shorter than the sounds'
+	/// combined length by one crossfade per join. The crossfade is
+	/// shortened at joins where either sound is shorter than it.
+	/// All of the sounds must have the same sample rate.
+	pub fn concat_with_crossfade(
+		&self,
+		others: &[StaticSoundData],
+		crossfade: Duration,
+	) -> Result<Self, ConcatError> {
+		if others
+			.iter()
+			.any(|other| other.sample_rate != self.sample_rate)
+		{
+			return Err(ConcatError::SampleRateMismatch);
+		}
+		let crossfade_frames = (crossfade.as_secs_f64() * self.sample_rate as f64).round() as usize;
+		let mut frames = Vec::with_capacity(
+			self.frames.len() + others.iter().map(|other| other.frames.len()).sum::<usize>(),
+		);
+		frames.extend_from_slice(&self.frames);
+		let mut previous_len = self.frames.len();
+		for other in others {
+			let overlap = crossfade_frames.min(previous_len).min(other.frames.len());
+			let overlap_start = frames.len() - overlap;
+			for (i, (frame, next_frame)) in frames[overlap_start..]
+				.iter_mut()
+				.zip(other.frames.iter())
+				.enumerate()
+			{
+				let amount = (i + 1) as f32 / (overlap + 1) as f32;
+				*frame = *frame * (1.0 - amount) + *next_frame * amount;
+			}
+			frames.extend_from_slice(&other.frames[overlap..]);
+			previous_len = other.frames.len();
+		}
+		Ok(Self {
+			frames: Arc::new(frames),
+			..self.clone()
+		})
+	}
+
+	/// Layers the given sounds on top of this sound, each starting
+	/// at its own offset from the start of this sound, and returns
+	/// the result as new sound data with this sound's settings.
+	///
+	/// The output is long enough to fit every layer. Overlapping
+	/// samples are summed without any limiting, so the result may
+	/// go above full scale; use [`mix_clamped`](Self::mix_clamped)
+	/// to clip the sum instead, or lower the volume of the layers
+	/// to leave headroom. All of the sounds must have the same
+	/// sample rate.
+	pub fn mix(&self, layers: &[(StaticSoundData, Duration)]) -> Result<Self, MixError> {
+		if layers
+			.iter()
+			.any(|(layer, _)| layer.sample_rate != self.sample_rate)
+		{
+			return Err(MixError::SampleRateMismatch);
+		}
+		let offsets = layers
+			.iter()
+			.map(|(_, offset)| (offset.as_secs_f64() * self.sample_rate as f64).round() as usize)
+			.collect::<Vec<_>>();
+		let num_frames = layers
+			.iter()
+			.zip(&offsets)
+			.map(|((layer, _), offset)| offset + layer.frames.len())
+			.fold(self.frames.len(), usize::max);
+		let mut frames = self.frames.to_vec();
+		frames.resize(num_frames, Frame::ZERO);
+		for ((layer, _), offset) in layers.iter().zip(offsets) {
+			for (frame, layer_frame) in frames[offset..].iter_mut().zip(layer.frames.iter()) {
+				*frame += *layer_frame;
+			}
+		}
+		Ok(Self {
+			frames: Arc::new(frames),
+			..self.clone()
+		})
+	}
+
+	/// Like [`mix`](Self::mix), but clamps each sample of the
+	/// result to the range `-1.0..=1.0`.
+	pub fn mix_clamped(&self, layers: &[(StaticSoundData, Duration)]) -> Result<Self, MixError> {
+		let mut mixed = self.mix(layers)?;
+		mixed.frames = Arc::new(
+			mixed
+				.frames
+				.iter()
+				.map(|frame| Frame::new(frame.left.clamp(-1.0, 1.0), frame.right.clamp(-1.0, 1.0)))
+				.collect(),
+		);
+		Ok(mixed)
+	}
+
+	/// Checks that the positions in the settings are
+	/// within the bounds of the audio.
+	///
+	/// Sounds with no audio can still be played from the
+	/// beginning, in which case they finish right away.
+	pub(super) fn validate(&self) -> Result<(), StaticSoundError> {
+		let duration = self.duration().as_secs_f64();
+		let in_bounds = |position: f64| (0.0..duration).contains(&position);
+		let is_empty_sound_start = self.frames.is_empty() && self.settings.start_position == 0.0;
+		if !in_bounds(self.settings.start_position) && !is_empty_sound_start {
+			return Err(StaticSoundError::StartPositionOutOfBounds);
+		}
+		if let Some(LoopBehavior { start_position }) = self.settings.loop_behavior {
+			if !in_bounds(start_position) {
+				return Err(StaticSoundError::LoopStartOutOfBounds);
+			}
+		}
+		Ok(())
+	}
+
 	pub(super) fn split(self) -> (StaticSound, StaticSoundHandle) {
-		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_BUFFER_CAPACITY).split();
-		let sound = StaticSound::new(self, command_consumer);
+		let (command_producer, command_consumer) =
+			RingBuffer::new(self.settings.command_capacity).split();
+		// each queued sound sends back one piece of unused data (either
+		// itself if it's replaced, or the sound it took over from), and
+		// the handle empties this buffer before queueing a sound, so it
+		// only needs room for every sound that can be waiting in the
+		// command queue plus the one that's currently queued
+		let (unused_data_producer, unused_data_consumer) =
+			RingBuffer::new(self.settings.command_capacity + 1).split();
+		let sound = StaticSound::new(self, command_consumer, unused_data_producer);
 		let shared = sound.shared();
 		(
 			sound,
 			StaticSoundHandle {
 				command_producer,
+				unused_data_consumer,
 				shared,
 			},
 		)
@@ -52,13 +363,45 @@ impl StaticSoundData {
 }
 
 impl SoundData for StaticSoundData {
-	type Error = ();
+	type Error = StaticSoundError;
 
 	type Handle = StaticSoundHandle;
 
 	#[allow(clippy::type_complexity)]
 	fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+		self.validate()?;
 		let (sound, handle) = self.split();
 		Ok((Box::new(sound), handle))
 	}
 }
+
+/// Creates a [`StaticSoundData`] from a sample rate and mono samples.
+impl From<(u32, Vec<f32>)> for StaticSoundData {
+	fn from((sample_rate, samples): (u32, Vec<f32>)) -> Self {
+		Self::from_frames(
+			sample_rate,
+			samples.into_iter().map(Frame::from_mono).collect(),
+		)
+	}
+}
+
+/// Creates a [`StaticSoundData`] from a sample rate and mono
+/// 16-bit samples.
+impl From<(u32, Vec<i16>)> for StaticSoundData {
+	fn from((sample_rate, samples): (u32, Vec<i16>)) -> Self {
+		Self::from_frames(
+			sample_rate,
+			samples
+				.into_iter()
+				.map(|sample| Frame::from_mono(i16_to_f32(sample)))
+				.collect(),
+		)
+	}
+}
+
+/// Converts a 16-bit sample to a sample from `-1.0` to `1.0`.
+fn i16_to_f32(sample: i16) -> f32 {
+	// i16::MIN has no positive counterpart, so dividing by its
+	// magnitude keeps every sample in range
+	sample as f32 / 32768.0
+}
@@ -1,35 +1,81 @@
+#[cfg(test)]
+mod test;
+
 use std::{io::Cursor, sync::Arc};
 
 use symphonia::core::{
 	audio::{AudioBuffer, AudioBufferRef, Signal},
 	conv::{FromSample, IntoSample},
+	errors::Error,
+	formats::FormatReader,
 	io::{MediaSource, MediaSourceStream},
+	meta::{StandardTagKey, Tag},
+	probe::ProbedMetadata,
 	sample::Sample,
 };
 
 use crate::{
 	dsp::Frame,
-	sound::{static_sound::StaticSoundSettings, FromFileError},
+	sound::{
+		static_sound::{DecodeErrorPolicy, ReplayGainMode, StaticSoundSettings},
+		FromFileError,
+	},
+	LoopBehavior, Volume,
 };
 
 use super::StaticSoundData;
 
+/// The most frames that will be allocated before decoding a file,
+/// which is about an hour of audio at 48kHz.
+const MAX_PREALLOCATED_FRAMES: u64 = 48_000 * 60 * 60;
+
+/// Information about how loading an audio file went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+#[cfg_attr(
+	docsrs,
+	doc(cfg(any(feature = "mp3", feature = "ogg", feature = "flac", feature = "wav")))
+)]
+pub struct LoadReport {
+	/// The number of corrupt packets that were skipped.
+	///
+	/// This can only be nonzero if the sound was loaded with
+	/// [`DecodeErrorPolicy::SkipPacket`].
+	pub skipped_packets: usize,
+}
+
 impl StaticSoundData {
 	fn from_media_source(
 		media_source: Box<dyn MediaSource>,
-		settings: StaticSoundSettings,
-	) -> Result<Self, FromFileError> {
+		mut settings: StaticSoundSettings,
+	) -> Result<(Self, LoadReport), FromFileError> {
 		let codecs = symphonia::default::get_codecs();
 		let probe = symphonia::default::get_probe();
 		let mss = MediaSourceStream::new(media_source, Default::default());
-		let mut format_reader = probe
-			.format(
-				&Default::default(),
-				mss,
-				&Default::default(),
-				&Default::default(),
-			)?
-			.format;
+		let probe_result = probe.format(
+			&Default::default(),
+			mss,
+			&Default::default(),
+			&Default::default(),
+		)?;
+		let mut format_reader = probe_result.format;
+		let mut probed_metadata = probe_result.metadata;
+		let loop_start = if settings.loop_behavior.is_none() && settings.loop_behavior_from_metadata
+		{
+			find_in_metadata(
+				format_reader.as_mut(),
+				&mut probed_metadata,
+				loop_start_from_tags,
+			)
+		} else {
+			None
+		};
+		let replay_gain = settings.replay_gain;
+		if let Some(gain) = find_in_metadata(format_reader.as_mut(), &mut probed_metadata, |tags| {
+			replay_gain_from_tags(tags, replay_gain)
+		}) {
+			settings.volume = apply_gain(settings.volume, gain);
+		}
 		let codec_params = &format_reader
 			.default_track()
 			.ok_or(FromFileError::NoDefaultTrack)?
@@ -37,30 +83,53 @@ impl StaticSoundData {
 		let sample_rate = codec_params
 			.sample_rate
 			.ok_or(FromFileError::UnknownSampleRate)?;
-		let mut decoder = codecs.make(codec_params, &Default::default())?;
+		// if the container reports how many frames are in the track,
+		// allocate the buffer up front to avoid reallocating as packets
+		// are decoded. the frame count comes from the file, so it can't
+		// be trusted to be reasonable
 		let mut frames = vec![];
+		if let Some(n_frames) = codec_params.n_frames {
+			let n_frames = n_frames.min(MAX_PREALLOCATED_FRAMES) as usize;
+			// if the allocation fails, the buffer will just grow as
+			// the audio is decoded
+			frames.try_reserve_exact(n_frames).ok();
+		}
+		let mut decoder = codecs.make(codec_params, &Default::default())?;
+		let mut skipped_packets = 0;
 		loop {
 			match format_reader.next_packet() {
-				Ok(packet) => {
-					let buffer = decoder.decode(&packet)?;
-					load_frames_from_buffer_ref(&mut frames, &buffer)?;
-				}
+				Ok(packet) => match decoder.decode(&packet) {
+					Ok(buffer) => load_frames_from_buffer_ref(&mut frames, &buffer)?,
+					Err(error) => {
+						if !should_skip_packet(error, settings.decode_error_policy)? {
+							break;
+						}
+						skipped_packets += 1;
+					}
+				},
 				Err(error) => match error {
-					symphonia::core::errors::Error::IoError(error) => {
+					Error::IoError(error) => {
 						if error.kind() == std::io::ErrorKind::UnexpectedEof {
 							break;
 						}
-						return Err(symphonia::core::errors::Error::IoError(error).into());
+						return Err(Error::IoError(error).into());
 					}
 					error => return Err(error.into()),
 				},
 			}
 		}
-		Ok(Self {
-			sample_rate,
-			frames: Arc::new(frames),
-			settings,
-		})
+		if let Some(loop_start) = loop_start {
+			settings.loop_behavior =
+				loop_behavior_from_loop_start(loop_start, sample_rate, frames.len());
+		}
+		Ok((
+			Self {
+				sample_rate,
+				frames: Arc::new(frames),
+				settings,
+			},
+			LoadReport { skipped_packets },
+		))
 	}
 
 	/// Loads an audio file into a [`StaticSoundData`].
@@ -76,6 +145,23 @@ impl StaticSoundData {
 		path: impl AsRef<std::path::Path>,
 		settings: StaticSoundSettings,
 	) -> Result<Self, FromFileError> {
+		Self::from_file_with_report(path, settings).map(|(data, _)| data)
+	}
+
+	/// Loads an audio file into a [`StaticSoundData`] and reports
+	/// any problems that were recovered from while decoding it.
+	#[cfg(not(target_arch = "wasm32"))]
+	#[cfg_attr(
+		docsrs,
+		doc(cfg(all(
+			any(feature = "mp3", feature = "ogg", feature = "flac", feature = "wav"),
+			not(wasm32)
+		)))
+	)]
+	pub fn from_file_with_report(
+		path: impl AsRef<std::path::Path>,
+		settings: StaticSoundSettings,
+	) -> Result<(Self, LoadReport), FromFileError> {
 		Self::from_media_source(Box::new(std::fs::File::open(path)?), settings)
 	}
 
@@ -88,10 +174,128 @@ impl StaticSoundData {
 		cursor: Cursor<T>,
 		settings: StaticSoundSettings,
 	) -> Result<StaticSoundData, FromFileError> {
+		Self::from_cursor_with_report(cursor, settings).map(|(data, _)| data)
+	}
+
+	/// Loads a cursor wrapping audio file data into a [`StaticSoundData`]
+	/// and reports any problems that were recovered from while decoding it.
+	#[cfg_attr(
+		docsrs,
+		doc(cfg(any(feature = "mp3", feature = "ogg", feature = "flac", feature = "wav")))
+	)]
+	pub fn from_cursor_with_report<T: AsRef<[u8]> + Send + Sync + 'static>(
+		cursor: Cursor<T>,
+		settings: StaticSoundSettings,
+	) -> Result<(StaticSoundData, LoadReport), FromFileError> {
 		Self::from_media_source(Box::new(cursor), settings)
 	}
 }
 
+/// Decides what to do after a packet fails to decode.
+///
+/// Returns `Ok(true)` if the packet should be skipped, `Ok(false)`
+/// if loading should stop and keep the audio decoded so far,
+/// or an error if loading should be aborted.
+fn should_skip_packet(error: Error, policy: DecodeErrorPolicy) -> Result<bool, FromFileError> {
+	// only decode errors are caused by corrupt packets. other errors
+	// mean we can't keep reading the file at all
+	match (error, policy) {
+		(Error::DecodeError(_), DecodeErrorPolicy::SkipPacket) => Ok(true),
+		(Error::DecodeError(_), DecodeErrorPolicy::Truncate) => Ok(false),
+		(error, _) => Err(error.into()),
+	}
+}
+
+/// Looks for a value in the tags stored in the container
+/// and the tags found by the prober.
+fn find_in_metadata<T>(
+	format_reader: &mut dyn FormatReader,
+	probed_metadata: &mut ProbedMetadata,
+	find: impl Fn(&[Tag]) -> Option<T>,
+) -> Option<T> {
+	// tags can be found either by the prober (e.g. ID3 tags)
+	// or in the container itself (e.g. Vorbis comments)
+	format_reader
+		.metadata()
+		.current()
+		.and_then(|revision| find(revision.tags()))
+		.or_else(|| {
+			probed_metadata.get().and_then(|metadata| {
+				metadata
+					.current()
+					.and_then(|revision| find(revision.tags()))
+			})
+		})
+}
+
+/// Returns the ReplayGain (in decibels) to use for the given
+/// mode, if the tags have one.
+fn replay_gain_from_tags(tags: &[Tag], mode: ReplayGainMode) -> Option<f64> {
+	let find_gain = |key: &str, std_key: StandardTagKey| {
+		tags.iter()
+			.find(|tag| tag.std_key == Some(std_key) || tag.key.eq_ignore_ascii_case(key))
+			.and_then(|tag| parse_gain(&tag.value.to_string()))
+	};
+	let track_gain = || find_gain("REPLAYGAIN_TRACK_GAIN", StandardTagKey::ReplayGainTrackGain);
+	match mode {
+		ReplayGainMode::Off => None,
+		ReplayGainMode::Track => track_gain(),
+		ReplayGainMode::Album => {
+			find_gain("REPLAYGAIN_ALBUM_GAIN", StandardTagKey::ReplayGainAlbumGain)
+				.or_else(track_gain)
+		}
+	}
+}
+
+/// Parses a gain value like `-6.48 dB`.
+fn parse_gain(value: &str) -> Option<f64> {
+	value
+		.trim()
+		.trim_end_matches(|c: char| c.is_ascii_alphabetic())
+		.trim()
+		.parse()
+		.ok()
+		.filter(|gain: &f64| gain.is_finite())
+}
+
+/// Adds a gain (in decibels) to a volume, keeping the
+/// volume in the same unit.
+fn apply_gain(volume: Volume, gain: f64) -> Volume {
+	match volume {
+		Volume::Amplitude(amplitude) => {
+			Volume::Amplitude(amplitude * Volume::Decibels(gain).as_amplitude())
+		}
+		Volume::Decibels(decibels) => Volume::Decibels(decibels + gain),
+	}
+}
+
+/// Returns the loop start point (in frames) stored in a
+/// `LOOPSTART` tag, if there is one.
+///
+/// Sounds always loop to the end, so `LOOPLENGTH` tags
+/// aren't read.
+fn loop_start_from_tags(tags: &[Tag]) -> Option<u64> {
+	tags.iter()
+		.find(|tag| tag.key.eq_ignore_ascii_case("LOOPSTART"))
+		.and_then(|tag| tag.value.to_string().trim().parse().ok())
+}
+
+/// Converts a loop start point from a file's metadata to a
+/// [`LoopBehavior`], or returns `None` if it's past the end
+/// of the audio.
+fn loop_behavior_from_loop_start(
+	loop_start: u64,
+	sample_rate: u32,
+	num_frames: usize,
+) -> Option<LoopBehavior> {
+	if loop_start >= num_frames as u64 {
+		return None;
+	}
+	Some(LoopBehavior {
+		start_position: loop_start as f64 / sample_rate as f64,
+	})
+}
+
 fn load_frames_from_buffer_ref(
 	frames: &mut Vec<Frame>,
 	buffer: &AudioBufferRef,
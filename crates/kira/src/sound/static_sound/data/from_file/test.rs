@@ -0,0 +1,154 @@
+use symphonia::core::{
+	errors::Error,
+	meta::{Tag, Value},
+};
+
+use crate::{
+	sound::{
+		static_sound::{DecodeErrorPolicy, ReplayGainMode},
+		FromFileError,
+	},
+	LoopBehavior, Volume,
+};
+
+use super::{
+	apply_gain, loop_behavior_from_loop_start, loop_start_from_tags, replay_gain_from_tags,
+	should_skip_packet,
+};
+
+/// Tests that the loop start point is read from the
+/// `LOOPSTART` tag, regardless of case.
+#[test]
+fn reads_loop_start_from_tags() {
+	assert_eq!(
+		loop_start_from_tags(&[
+			Tag::new(None, "TITLE", Value::String("blip".into())),
+			Tag::new(None, "LoopStart", Value::String("44100".into())),
+			Tag::new(None, "LOOPLENGTH", Value::String("1000".into())),
+		]),
+		Some(44100)
+	);
+	assert_eq!(
+		loop_start_from_tags(&[Tag::new(None, "LOOPSTART", Value::UnsignedInt(50))]),
+		Some(50)
+	);
+}
+
+/// Tests that missing, malformed, or negative loop start
+/// tags are ignored.
+#[test]
+fn ignores_missing_or_malformed_loop_tags() {
+	assert_eq!(loop_start_from_tags(&[]), None);
+	assert_eq!(
+		loop_start_from_tags(&[Tag::new(None, "LOOPSTART", Value::String("soon".into()))]),
+		None
+	);
+	assert_eq!(
+		loop_start_from_tags(&[Tag::new(None, "LOOPSTART", Value::SignedInt(-1))]),
+		None
+	);
+}
+
+/// Tests that a loop start from the metadata is converted to
+/// seconds, and ignored if it's past the end of the audio.
+#[test]
+fn ignores_loop_start_past_end_of_audio() {
+	assert_eq!(
+		loop_behavior_from_loop_start(50, 100, 200),
+		Some(LoopBehavior {
+			start_position: 0.5
+		})
+	);
+	assert_eq!(loop_behavior_from_loop_start(200, 100, 200), None);
+	assert_eq!(loop_behavior_from_loop_start(u64::MAX, 100, 200), None);
+}
+
+/// Tests that each decode error policy aborts, skips, or
+/// truncates when a packet is corrupt.
+#[test]
+fn handles_decode_errors_according_to_policy() {
+	assert!(matches!(
+		should_skip_packet(Error::DecodeError("corrupt"), DecodeErrorPolicy::Abort),
+		Err(FromFileError::SymphoniaError(Error::DecodeError(_)))
+	));
+	assert!(matches!(
+		should_skip_packet(Error::DecodeError("corrupt"), DecodeErrorPolicy::SkipPacket),
+		Ok(true)
+	));
+	assert!(matches!(
+		should_skip_packet(Error::DecodeError("corrupt"), DecodeErrorPolicy::Truncate),
+		Ok(false)
+	));
+	// errors that aren't caused by a corrupt packet always abort loading
+	for policy in [DecodeErrorPolicy::SkipPacket, DecodeErrorPolicy::Truncate] {
+		assert!(matches!(
+			should_skip_packet(Error::Unsupported("feature"), policy),
+			Err(FromFileError::SymphoniaError(Error::Unsupported(_)))
+		));
+	}
+}
+
+/// Tests that the ReplayGain tag for the chosen mode is
+/// read, regardless of case.
+#[test]
+fn reads_replay_gain_from_tags() {
+	let tags = [
+		Tag::new(
+			None,
+			"REPLAYGAIN_TRACK_GAIN",
+			Value::String("-6.48 dB".into()),
+		),
+		Tag::new(
+			None,
+			"replaygain_album_gain",
+			Value::String("+1.5 dB".into()),
+		),
+	];
+	assert_eq!(replay_gain_from_tags(&tags, ReplayGainMode::Off), None);
+	assert_eq!(
+		replay_gain_from_tags(&tags, ReplayGainMode::Track),
+		Some(-6.48)
+	);
+	assert_eq!(
+		replay_gain_from_tags(&tags, ReplayGainMode::Album),
+		Some(1.5)
+	);
+	// album mode falls back to the track gain
+	assert_eq!(
+		replay_gain_from_tags(&tags[..1], ReplayGainMode::Album),
+		Some(-6.48)
+	);
+}
+
+/// Tests that missing or malformed ReplayGain tags
+/// are ignored.
+#[test]
+fn ignores_missing_or_malformed_replay_gain_tags() {
+	assert_eq!(replay_gain_from_tags(&[], ReplayGainMode::Track), None);
+	assert_eq!(
+		replay_gain_from_tags(
+			&[Tag::new(
+				None,
+				"REPLAYGAIN_TRACK_GAIN",
+				Value::String("loud".into())
+			)],
+			ReplayGainMode::Track
+		),
+		None
+	);
+}
+
+/// Tests that ReplayGain is added to the volume from
+/// the sound's settings.
+#[test]
+#[allow(clippy::float_cmp)]
+fn applies_gain_on_top_of_volume() {
+	assert_eq!(
+		apply_gain(Volume::Decibels(-6.0), -3.0),
+		Volume::Decibels(-9.0)
+	);
+	assert_eq!(
+		apply_gain(Volume::Amplitude(0.5), Volume::Amplitude(2.0).as_decibels()),
+		Volume::Amplitude(1.0)
+	);
+}
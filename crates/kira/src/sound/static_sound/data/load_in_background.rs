@@ -0,0 +1,84 @@
+use std::{
+	path::PathBuf,
+	sync::mpsc::{Receiver, TryRecvError},
+};
+
+use crate::sound::{static_sound::StaticSoundSettings, FromFileError};
+
+use super::StaticSoundData;
+
+#[cfg(test)]
+mod test;
+
+impl StaticSoundData {
+	/// Starts loading an audio file into a [`StaticSoundData`] on
+	/// a background thread.
+	///
+	/// Unlike [`StaticSoundData::from_file`], this returns immediately.
+	/// The returned [`StaticSoundDataLoader`] can be polled to retrieve
+	/// the loaded sound (or the error that occurred while loading it).
+	#[cfg_attr(
+		docsrs,
+		doc(cfg(all(
+			any(feature = "mp3", feature = "ogg", feature = "flac", feature = "wav"),
+			not(wasm32)
+		)))
+	)]
+	pub fn from_file_in_background(
+		path: impl Into<PathBuf>,
+		settings: StaticSoundSettings,
+	) -> StaticSoundDataLoader {
+		let path = path.into();
+		let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+		std::thread::spawn(move || {
+			sender.send(Self::from_file(path, settings)).ok();
+		});
+		StaticSoundDataLoader {
+			receiver: Some(receiver),
+		}
+	}
+}
+
+/// An audio file that is being loaded into a [`StaticSoundData`]
+/// on a background thread.
+#[cfg_attr(
+	docsrs,
+	doc(cfg(all(
+		any(feature = "mp3", feature = "ogg", feature = "flac", feature = "wav"),
+		not(wasm32)
+	)))
+)]
+pub struct StaticSoundDataLoader {
+	receiver: Option<Receiver<Result<StaticSoundData, FromFileError>>>,
+}
+
+impl StaticSoundDataLoader {
+	/// Returns the result of loading the audio file if it's
+	/// finished loading, or `None` if it's still loading.
+	///
+	/// Once the result has been returned, subsequent calls will
+	/// always return `None`.
+	pub fn poll(&mut self) -> Option<Result<StaticSoundData, FromFileError>> {
+		let result = match self.receiver.as_ref()?.try_recv() {
+			Ok(result) => result,
+			Err(TryRecvError::Empty) => return None,
+			Err(TryRecvError::Disconnected) => Err(FromFileError::LoaderThreadPanicked),
+		};
+		self.receiver = None;
+		Some(result)
+	}
+
+	/// Blocks the current thread until the audio file is finished
+	/// loading and returns the result.
+	///
+	/// # Panics
+	///
+	/// Panics if the result was already retrieved using
+	/// [`poll`](StaticSoundDataLoader::poll).
+	pub fn wait(self) -> Result<StaticSoundData, FromFileError> {
+		self.receiver
+			.expect("The result of loading the sound was already retrieved")
+			.recv()
+			.unwrap_or(Err(FromFileError::LoaderThreadPanicked))
+	}
+}
@@ -0,0 +1,32 @@
+use crate::sound::{static_sound::StaticSoundData, FromFileError};
+
+/// Tests that a sound loaded in the background is the same as
+/// one loaded on the current thread.
+#[test]
+#[cfg(feature = "ogg")]
+fn loads_in_background() {
+	const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../examples/assets/blip.ogg");
+	let mut loader = StaticSoundData::from_file_in_background(PATH, Default::default());
+	let data = loop {
+		if let Some(result) = loader.poll() {
+			break result.unwrap();
+		}
+		std::thread::yield_now();
+	};
+	let expected = StaticSoundData::from_file(PATH, Default::default()).unwrap();
+	assert_eq!(data.sample_rate, expected.sample_rate);
+	assert_eq!(data.frames, expected.frames);
+	// the result should only be returned once
+	assert!(loader.poll().is_none());
+}
+
+/// Tests that errors that occur while loading in the background
+/// are returned from the loader.
+#[test]
+fn reports_errors() {
+	let loader = StaticSoundData::from_file_in_background(
+		concat!(env!("CARGO_MANIFEST_DIR"), "/nonexistent.ogg"),
+		Default::default(),
+	);
+	assert!(matches!(loader.wait(), Err(FromFileError::IoError(_))));
+}
@@ -1,6 +1,14 @@
 use std::{sync::Arc, time::Duration};
 
-use crate::dsp::Frame;
+use crate::{
+	dsp::{Frame, Random},
+	sound::{
+		static_sound::{ConcatError, MixError, StaticSoundError, StaticSoundSettings},
+		SoundData,
+	},
+	track::effect::{volume_control::VolumeControlBuilder, EffectBuilder},
+	LoopBehavior, Volume,
+};
 
 use super::StaticSoundData;
 
@@ -13,3 +21,379 @@ fn duration() {
 	};
 	assert_eq!(static_sound.duration(), Duration::from_secs(4));
 }
+
+#[test]
+fn waveform_peaks() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![
+			Frame::new(0.1, -0.2),
+			Frame::new(0.5, 0.3),
+			Frame::new(-0.8, 0.0),
+			Frame::new(0.2, 0.9),
+			Frame::new(0.0, 0.0),
+			Frame::new(-0.1, 0.1),
+		]),
+		settings: Default::default(),
+	};
+	assert_eq!(
+		static_sound.waveform_peaks(3),
+		vec![(-0.2, 0.5), (-0.8, 0.9), (-0.1, 0.1)]
+	);
+	assert_eq!(static_sound.waveform_peaks(1), vec![(-0.8, 0.9)]);
+	assert_eq!(static_sound.waveform_peaks(0), vec![]);
+	// sounds shorter than the requested number of buckets
+	// should return one bucket per frame
+	assert_eq!(static_sound.waveform_peaks(100).len(), 6);
+}
+
+#[test]
+fn validates_start_position() {
+	let data = |start_position| StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(0.0); 4]),
+		settings: StaticSoundSettings::new().start_position(start_position),
+	};
+	assert!(data(3.5).into_sound().is_ok());
+	assert!(matches!(
+		data(4.0).into_sound(),
+		Err(StaticSoundError::StartPositionOutOfBounds)
+	));
+	assert!(matches!(
+		data(-1.0).into_sound(),
+		Err(StaticSoundError::StartPositionOutOfBounds)
+	));
+	// an empty sound can only start at the beginning
+	let empty_data = |start_position| StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![]),
+		settings: StaticSoundSettings::new().start_position(start_position),
+	};
+	assert!(empty_data(0.0).into_sound().is_ok());
+	assert!(matches!(
+		empty_data(1.0).into_sound(),
+		Err(StaticSoundError::StartPositionOutOfBounds)
+	));
+}
+
+#[test]
+fn validates_loop_start_position() {
+	let data = |start_position| StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(0.0); 4]),
+		settings: StaticSoundSettings::new().loop_behavior(LoopBehavior { start_position }),
+	};
+	assert!(data(2.0).into_sound().is_ok());
+	assert!(matches!(
+		data(5.0).into_sound(),
+		Err(StaticSoundError::LoopStartOutOfBounds)
+	));
+}
+
+/// Tests that settings can be overridden without changing
+/// the original sound data.
+#[test]
+#[allow(clippy::float_cmp)]
+fn with_modified_settings() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(0.0); 4]),
+		settings: StaticSoundSettings::new().panning(0.2),
+	};
+	let modified = static_sound.with_modified_settings(|settings| settings.reverse(true));
+	assert!(Arc::ptr_eq(&static_sound.frames, &modified.frames));
+	assert!(modified.settings.reverse);
+	assert_eq!(modified.settings.panning, 0.2);
+	assert!(!static_sound.settings.reverse);
+}
+
+/// Tests that randomized settings are varied, stay within the
+/// given range, and are reproducible with the same seed.
+#[test]
+fn random_variation() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(0.0); 4]),
+		settings: StaticSoundSettings::new(),
+	};
+	let random_settings = |random: &mut Random| -> Vec<(f64, f64)> {
+		(0..100)
+			.map(|_| {
+				let settings = static_sound
+					.with_modified_settings(|settings| {
+						settings
+							.random_playback_rate(0.8, 1.25, random)
+							.random_volume(0.5, 1.0, random)
+					})
+					.settings;
+				(
+					settings.playback_rate.as_factor(),
+					settings.volume.as_amplitude(),
+				)
+			})
+			.collect()
+	};
+	let values = random_settings(&mut Random::new(1));
+	for (playback_rate, volume) in &values {
+		assert!((0.8 - 1e-9..=1.25 + 1e-9).contains(playback_rate));
+		assert!((0.5 - 1e-9..=1.0 + 1e-9).contains(volume));
+	}
+	assert!(values.iter().any(|value| *value != values[0]));
+	assert_eq!(values, random_settings(&mut Random::new(1)));
+}
+
+/// Tests that random playback rates ignore invalid ranges
+/// and accept the bounds in either order.
+#[test]
+#[allow(clippy::float_cmp)]
+fn random_playback_rate_range() {
+	let mut random = Random::new(1);
+	for (min, max) in [
+		(0.0, 1.0),
+		(-1.0, 1.0),
+		(1.0, f64::INFINITY),
+		(f64::NAN, 1.0),
+	] {
+		let settings = StaticSoundSettings::new()
+			.playback_rate(2.0)
+			.random_playback_rate(min, max, &mut random);
+		assert_eq!(settings.playback_rate.as_factor(), 2.0);
+	}
+	for _ in 0..100 {
+		let settings = StaticSoundSettings::new().random_playback_rate(1.25, 0.8, &mut random);
+		assert!((0.8 - 1e-9..=1.25 + 1e-9).contains(&settings.playback_rate.as_factor()));
+	}
+}
+
+/// Tests that random volumes ignore invalid ranges and
+/// accept the bounds in either order.
+#[test]
+#[allow(clippy::float_cmp)]
+fn random_volume_range() {
+	let mut random = Random::new(1);
+	for (min, max) in [
+		(Volume::Amplitude(f64::INFINITY), Volume::Amplitude(1.0)),
+		(Volume::Decibels(-6.0), Volume::Decibels(f64::INFINITY)),
+		(Volume::Decibels(f64::NAN), Volume::Decibels(0.0)),
+	] {
+		let settings = StaticSoundSettings::new()
+			.volume(0.5)
+			.random_volume(min, max, &mut random);
+		assert_eq!(settings.volume.as_amplitude(), 0.5);
+	}
+	for _ in 0..100 {
+		let settings = StaticSoundSettings::new().random_volume(
+			Volume::Decibels(0.0),
+			Volume::Decibels(-6.0),
+			&mut random,
+		);
+		assert!((-6.0 - 1e-9..=1e-9).contains(&settings.volume.as_decibels()));
+	}
+}
+
+/// Tests that sound data can be rendered through a chain
+/// of effects with a tail.
+#[test]
+fn process_through() {
+	let static_sound = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 3]),
+		settings: StaticSoundSettings::new().volume(0.5),
+	};
+	let (effect, _handle) = VolumeControlBuilder::new(0.5).build();
+	let processed = static_sound.process_through(&mut [effect], Duration::from_secs(2));
+	assert_eq!(
+		*processed.frames,
+		vec![
+			Frame::from_mono(0.5),
+			Frame::from_mono(0.5),
+			Frame::from_mono(0.5),
+			Frame::ZERO,
+			Frame::ZERO,
+		]
+	);
+	assert_eq!(processed.sample_rate, 1);
+	assert_eq!(processed.settings, static_sound.settings);
+}
+
+/// Tests that sounds are joined end-to-end.
+#[test]
+fn concat() {
+	let sound = |num_frames: usize, value: f32| StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(value); num_frames]),
+		settings: StaticSoundSettings::new(),
+	};
+	let joined = sound(2, 1.0)
+		.concat(&[sound(3, 2.0), sound(1, 3.0)])
+		.unwrap();
+	assert_eq!(joined.frames.len(), 6);
+	assert_eq!(
+		*joined.frames,
+		[1.0, 1.0, 2.0, 2.0, 2.0, 3.0]
+			.iter()
+			.map(|value| Frame::from_mono(*value))
+			.collect::<Vec<_>>()
+	);
+}
+
+/// Tests that sounds are overlapped and faded into each other
+/// when concatenating with a crossfade.
+#[test]
+fn concat_with_crossfade() {
+	let sound = |num_frames: usize, value: f32| StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(value); num_frames]),
+		settings: StaticSoundSettings::new(),
+	};
+	let joined = sound(4, 0.0)
+		.concat_with_crossfade(&[sound(4, 3.0)], Duration::from_secs(2))
+		.unwrap();
+	assert_eq!(joined.frames.len(), 6);
+	assert_eq!(
+		*joined.frames,
+		[0.0, 0.0, 1.0, 2.0, 3.0, 3.0]
+			.iter()
+			.map(|value| Frame::from_mono(*value))
+			.collect::<Vec<_>>()
+	);
+	// the crossfade can't be longer than the sounds being joined
+	let joined = sound(4, 0.0)
+		.concat_with_crossfade(&[sound(1, 1.0)], Duration::from_secs(2))
+		.unwrap();
+	assert_eq!(joined.frames.len(), 4);
+}
+
+/// Tests that sounds with different sample rates can't be
+/// concatenated.
+#[test]
+fn concat_sample_rate_mismatch() {
+	let sound = |sample_rate: u32| StaticSoundData {
+		sample_rate,
+		frames: Arc::new(vec![Frame::ZERO; 4]),
+		settings: StaticSoundSettings::new(),
+	};
+	assert!(matches!(
+		sound(1).concat(&[sound(1), sound(2)]),
+		Err(ConcatError::SampleRateMismatch)
+	));
+}
+
+/// Tests that layers are summed at their offsets and the
+/// output is extended to fit the longest layer.
+#[test]
+fn mix() {
+	let sound = |num_frames: usize, value: f32| StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(value); num_frames]),
+		settings: StaticSoundSettings::new(),
+	};
+	let mixed = sound(2, 0.75)
+		.mix(&[(sound(2, 0.75), Duration::ZERO)])
+		.unwrap();
+	assert_eq!(*mixed.frames, vec![Frame::from_mono(1.5); 2]);
+	let mixed = sound(2, 1.0)
+		.mix(&[(sound(2, 2.0), Duration::from_secs(1))])
+		.unwrap();
+	assert_eq!(
+		*mixed.frames,
+		vec![
+			Frame::from_mono(1.0),
+			Frame::from_mono(3.0),
+			Frame::from_mono(2.0)
+		]
+	);
+}
+
+/// Tests that the sum is clipped when mixing with clamping.
+#[test]
+fn mix_clamped() {
+	let sound = |value: f32| StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(value); 2]),
+		settings: StaticSoundSettings::new(),
+	};
+	let mixed = sound(0.75)
+		.mix_clamped(&[(sound(0.75), Duration::ZERO)])
+		.unwrap();
+	assert_eq!(*mixed.frames, vec![Frame::from_mono(1.0); 2]);
+	let mixed = sound(-0.75)
+		.mix_clamped(&[(sound(-0.75), Duration::ZERO)])
+		.unwrap();
+	assert_eq!(*mixed.frames, vec![Frame::from_mono(-1.0); 2]);
+}
+
+/// Tests that sounds with different sample rates can't be
+/// mixed together.
+#[test]
+fn mix_sample_rate_mismatch() {
+	let sound = |sample_rate: u32| StaticSoundData {
+		sample_rate,
+		frames: Arc::new(vec![Frame::ZERO; 4]),
+		settings: StaticSoundSettings::new(),
+	};
+	assert!(matches!(
+		sound(1).mix(&[(sound(2), Duration::ZERO)]),
+		Err(MixError::SampleRateMismatch)
+	));
+}
+
+/// Tests that mono samples are converted to frames.
+#[test]
+fn from_mono_samples() {
+	let data = StaticSoundData::from((10, vec![0.25, 0.5, 0.75]));
+	assert_eq!(data.sample_rate, 10);
+	assert_eq!(
+		*data.frames,
+		vec![
+			Frame::from_mono(0.25),
+			Frame::from_mono(0.5),
+			Frame::from_mono(0.75)
+		]
+	);
+}
+
+/// Tests that interleaved stereo samples are converted
+/// to frames, ignoring a leftover sample.
+#[test]
+fn from_interleaved_stereo() {
+	let data = StaticSoundData::from_interleaved_stereo(10, &[0.1, 0.2, 0.3, 0.4, 0.5]);
+	assert_eq!(data.sample_rate, 10);
+	assert_eq!(
+		*data.frames,
+		vec![Frame::new(0.1, 0.2), Frame::new(0.3, 0.4)]
+	);
+}
+
+/// Tests that interleaved stereo 16-bit samples are
+/// scaled to the -1.0 to 1.0 range.
+#[test]
+#[allow(clippy::float_cmp)]
+fn from_interleaved_stereo_i16() {
+	let data = StaticSoundData::from_interleaved_stereo_i16(10, &[i16::MIN, 0, 16384, i16::MAX]);
+	assert_eq!(data.sample_rate, 10);
+	assert_eq!(
+		*data.frames,
+		vec![
+			Frame::new(-1.0, 0.0),
+			Frame::new(0.5, i16::MAX as f32 / 32768.0)
+		]
+	);
+}
+
+/// Tests that mono 16-bit samples are scaled to the
+/// -1.0 to 1.0 range.
+#[test]
+#[allow(clippy::float_cmp)]
+fn from_mono_i16_samples() {
+	let data = StaticSoundData::from((10, vec![i16::MIN, 0, 16384i16]));
+	assert_eq!(data.sample_rate, 10);
+	assert_eq!(
+		*data.frames,
+		vec![
+			Frame::from_mono(-1.0),
+			Frame::from_mono(0.0),
+			Frame::from_mono(0.5)
+		]
+	);
+}
@@ -0,0 +1,148 @@
+use std::{error::Error, fmt::Display};
+
+use crate::CommandError;
+
+/// Errors that can occur when preparing a static sound for playback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum StaticSoundError {
+	/// The start position of the sound is negative or not
+	/// before the end of the sound.
+	StartPositionOutOfBounds,
+	/// The loop start position of the sound is negative or not
+	/// before the end of the sound.
+	LoopStartOutOfBounds,
+}
+
+impl Display for StaticSoundError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			StaticSoundError::StartPositionOutOfBounds => f.write_str(
+				"The start position of the sound is negative or not before the end of the sound",
+			),
+			StaticSoundError::LoopStartOutOfBounds => f.write_str(
+				"The loop start position of the sound is negative or not before the end of the sound",
+			),
+		}
+	}
+}
+
+impl Error for StaticSoundError {}
+
+/// Errors that can occur when queueing a static sound to play
+/// after another one.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum QueueSoundError {
+	/// The queued sound could not be prepared for playback.
+	StaticSoundError(StaticSoundError),
+	/// An error occurred when sending a command to the audio renderer.
+	CommandError(CommandError),
+}
+
+impl Display for QueueSoundError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			QueueSoundError::StaticSoundError(error) => error.fmt(f),
+			QueueSoundError::CommandError(error) => error.fmt(f),
+		}
+	}
+}
+
+impl Error for QueueSoundError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			QueueSoundError::StaticSoundError(error) => Some(error),
+			QueueSoundError::CommandError(error) => Some(error),
+		}
+	}
+}
+
+impl From<StaticSoundError> for QueueSoundError {
+	fn from(v: StaticSoundError) -> Self {
+		Self::StaticSoundError(v)
+	}
+}
+
+impl From<CommandError> for QueueSoundError {
+	fn from(v: CommandError) -> Self {
+		Self::CommandError(v)
+	}
+}
+
+/// Errors that can occur when changing the loop behavior of a static sound.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SetLoopBehaviorError {
+	/// The loop start position is negative or not before the
+	/// end of the sound.
+	LoopStartOutOfBounds,
+	/// An error occurred when sending a command to the audio renderer.
+	CommandError(CommandError),
+}
+
+impl Display for SetLoopBehaviorError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			SetLoopBehaviorError::LoopStartOutOfBounds => f.write_str(
+				"The loop start position is negative or not before the end of the sound",
+			),
+			SetLoopBehaviorError::CommandError(error) => error.fmt(f),
+		}
+	}
+}
+
+impl Error for SetLoopBehaviorError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			SetLoopBehaviorError::CommandError(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<CommandError> for SetLoopBehaviorError {
+	fn from(v: CommandError) -> Self {
+		Self::CommandError(v)
+	}
+}
+
+/// Errors that can occur when concatenating static sounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ConcatError {
+	/// The sounds don't all have the same sample rate.
+	SampleRateMismatch,
+}
+
+impl Display for ConcatError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ConcatError::SampleRateMismatch => {
+				f.write_str("The sounds don't all have the same sample rate")
+			}
+		}
+	}
+}
+
+impl Error for ConcatError {}
+
+/// Errors that can occur when mixing static sounds together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MixError {
+	/// The sounds don't all have the same sample rate.
+	SampleRateMismatch,
+}
+
+impl Display for MixError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			MixError::SampleRateMismatch => {
+				f.write_str("The sounds don't all have the same sample rate")
+			}
+		}
+	}
+}
+
+impl Error for MixError {}
@@ -1,14 +1,19 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-use ringbuf::Producer;
+use ringbuf::{Consumer, Producer};
 
-use crate::{tween::Tween, CommandError, PlaybackRate, Volume};
+use crate::{tween::Tween, CommandError, LoopBehavior, PlaybackRate, StartTime, Volume};
 
-use super::{sound::Shared, Command, PlaybackState};
+use super::{
+	sound::Shared, Command, PlaybackState, QueueSoundError, SetLoopBehaviorError, StaticSoundData,
+};
 
 /// Controls a static sound.
 pub struct StaticSoundHandle {
 	pub(super) command_producer: Producer<Command>,
+	/// Receives sound data the sound no longer needs so it can
+	/// be dropped on the main thread.
+	pub(super) unused_data_consumer: Consumer<Box<StaticSoundData>>,
 	pub(super) shared: Arc<Shared>,
 }
 
@@ -18,20 +23,71 @@ impl StaticSoundHandle {
 		self.shared.state()
 	}
 
+	/// Returns `false` if the sound has stopped and will be
+	/// (or already has been) removed from the audio renderer.
+	///
+	/// This is only advisory: the sound may finish right after
+	/// this returns `true`.
+	pub fn is_alive(&self) -> bool {
+		self.shared.state() != PlaybackState::Stopped
+	}
+
 	/// Returns the current playback position of the sound (in seconds).
 	pub fn position(&self) -> f64 {
 		self.shared.position()
 	}
 
+	/// Returns an estimate of the current playback position of the
+	/// sound (in seconds) with a higher resolution than
+	/// [`position`](Self::position).
+	///
+	/// [`position`](Self::position) only updates once per batch of
+	/// audio processed by the audio thread, which is typically every
+	/// 5-20ms depending on the backend's buffer size. This method
+	/// takes the last reported position and extrapolates from it
+	/// using the time that has passed since it was reported and the
+	/// current playback rate, which makes it suitable for syncing
+	/// visuals to audio (for example, judging note timing in a
+	/// rhythm game).
+	///
+	/// This is only an estimate between audio callbacks. It's usually
+	/// accurate to within a millisecond or so, but it doesn't account
+	/// for the output latency of the audio device or for jitter in
+	/// when the audio thread runs, and it can briefly go past the end
+	/// of a looping sound before the next update wraps it around. If
+	/// the sound isn't playing, this returns the same value as
+	/// [`position`](Self::position).
+	///
+	/// This method is not available on wasm32 targets, where
+	/// [`std::time::Instant`] is not supported.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn position_precise(&self) -> f64 {
+		self.shared.position_precise()
+	}
+
+	/// Returns how far the sound has played through, from `0.0`
+	/// (the start of the sound) to `1.0` (the end of the sound).
+	pub fn progress(&self) -> f64 {
+		self.shared.progress()
+	}
+
+	/// Returns the amount of time until the sound reaches the end,
+	/// taking into account the playback rate and whether the sound
+	/// is playing in reverse.
+	///
+	/// Returns `None` if the sound will never reach the end, either
+	/// because it's looping or because its playback rate is `0`.
+	pub fn time_remaining(&self) -> Option<Duration> {
+		self.shared.time_remaining()
+	}
+
 	/// Sets the volume of the sound (as a factor of the original volume).
 	pub fn set_volume(
 		&mut self,
 		volume: impl Into<Volume>,
 		tween: Tween,
 	) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetVolume(volume.into(), tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetVolume(volume.into(), tween))
 	}
 
 	/// Sets the playback rate of the sound.
@@ -43,33 +99,36 @@ impl StaticSoundHandle {
 		playback_rate: impl Into<PlaybackRate>,
 		tween: Tween,
 	) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetPlaybackRate(playback_rate.into(), tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetPlaybackRate(playback_rate.into(), tween))
 	}
 
 	/// Sets the panning of the sound, where `0.0` is hard left,
 	/// `0.5` is center, and `1.0` is hard right.
 	pub fn set_panning(&mut self, panning: f64, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetPanning(panning, tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetPanning(panning, tween))
+	}
+
+	/// Starts a sound that was set to wait with
+	/// [`StaticSoundSettings::start_paused`](super::StaticSoundSettings::start_paused).
+	///
+	/// Unlike [`resume`](Self::resume), this begins playback of a sound
+	/// that hasn't produced any output yet. Use a
+	/// [`StartTime::ClockTime`] to start the sound on an exact clock
+	/// tick. If the sound has already been started, this does nothing.
+	pub fn start(&mut self, start_time: impl Into<StartTime>) -> Result<(), CommandError> {
+		self.push_command(Command::Start(start_time.into()))
 	}
 
 	/// Fades out the sound to silence with the given tween and then
 	/// pauses playback.
 	pub fn pause(&mut self, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::Pause(tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::Pause(tween))
 	}
 
 	/// Resumes playback and fades in the sound from silence
 	/// with the given tween.
 	pub fn resume(&mut self, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::Resume(tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::Resume(tween))
 	}
 
 	/// Fades out the sound to silence with the given tween and then
@@ -77,22 +136,105 @@ impl StaticSoundHandle {
 	///
 	/// Once the sound is stopped, it cannot be restarted.
 	pub fn stop(&mut self, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::Stop(tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::Stop(tween))
+	}
+
+	/// Lets the sound play to the end of its current loop and then
+	/// stops it, fading out with the given tween.
+	///
+	/// This turns off looping, so the sound plays until it reaches
+	/// the end. The fade-out starts early enough to finish right as
+	/// the sound ends, and its start time is ignored. If the sound
+	/// isn't looping, this stops it at the end the same way. Any sound
+	/// queued with [`queue_next`](Self::queue_next) won't be played.
+	pub fn stop_after_loop(&mut self, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::StopAfterLoop(tween))
 	}
 
 	/// Sets the playback position to the specified time in seconds.
 	pub fn seek_to(&mut self, position: f64) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SeekTo(position))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SeekTo(position))
 	}
 
 	/// Moves the playback position by the specified amount of time in seconds.
 	pub fn seek_by(&mut self, amount: f64) -> Result<(), CommandError> {
+		self.push_command(Command::SeekBy(amount))
+	}
+
+	/// Sets the playback position to the specified time.
+	pub fn seek_to_duration(&mut self, position: Duration) -> Result<(), CommandError> {
+		self.seek_to(position.as_secs_f64())
+	}
+
+	/// Moves the playback position forward by the specified amount of time.
+	///
+	/// To move the playback position backward, use
+	/// [`seek_by`](Self::seek_by) with a negative amount.
+	pub fn seek_by_duration(&mut self, amount: Duration) -> Result<(), CommandError> {
+		self.seek_by(amount.as_secs_f64())
+	}
+
+	/// Sets the portion of the sound that should be looped, or stops
+	/// the sound from looping if `loop_behavior` is `None`.
+	///
+	/// If playback is already past the new loop start position, the
+	/// sound continues to the end and then wraps around to the loop
+	/// start, the same as when seeking past the end of a looping sound.
+	/// Turning off looping lets the sound play to the end and stop.
+	///
+	/// If a sound queued with [`queue_next`](Self::queue_next) takes
+	/// over before the change is applied and the loop start is past
+	/// the end of that sound, the change is ignored.
+	pub fn set_loop_behavior(
+		&mut self,
+		loop_behavior: impl Into<Option<LoopBehavior>>,
+	) -> Result<(), SetLoopBehaviorError> {
+		let loop_behavior = loop_behavior.into();
+		if let Some(LoopBehavior { start_position }) = loop_behavior {
+			if !(0.0..self.shared.duration()).contains(&start_position) {
+				return Err(SetLoopBehaviorError::LoopStartOutOfBounds);
+			}
+		}
+		self.push_command(Command::SetLoopBehavior(loop_behavior))?;
+		Ok(())
+	}
+
+	/// Queues a sound to start playing the frame after this sound
+	/// reaches the end, with no gap between the two sounds.
+	///
+	/// The queued sound continues playing with this sound's volume,
+	/// playback rate, panning, and mixer track, and this handle controls
+	/// it after the transition. The queued sound's start position,
+	/// looping behavior, and whether it plays in reverse are taken from
+	/// its own settings. If this sound loops, it never reaches the end,
+	/// so the queued sound will never play.
+	///
+	/// Only one sound can be queued at a time. Queueing another sound
+	/// before the transition replaces the previously queued sound.
+	/// The sound has to be queued before this sound reaches its last
+	/// few frames, otherwise it will already be stopped and this will
+	/// return [`CommandError::ResourceRemoved`].
+	///
+	/// Sound data that's no longer needed after a transition is handed
+	/// back to this handle and freed the next time a sound is queued
+	/// or when the handle is dropped.
+	pub fn queue_next(&mut self, data: StaticSoundData) -> Result<(), QueueSoundError> {
+		data.validate()?;
+		// drop any sounds that have been replaced or have
+		// finished playing
+		while self.unused_data_consumer.pop().is_some() {}
+		self.push_command(Command::QueueNext(Box::new(data)))?;
+		Ok(())
+	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		// a stopped sound is removed from the renderer, so there's
+		// nothing left to receive the command
+		if !self.is_alive() {
+			return Err(CommandError::ResourceRemoved);
+		}
 		self.command_producer
-			.push(Command::SeekBy(amount))
+			.push(command)
 			.map_err(|_| CommandError::CommandQueueFull)
 	}
 }
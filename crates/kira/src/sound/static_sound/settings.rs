@@ -1,4 +1,115 @@
-use crate::{track::TrackId, tween::Tween, LoopBehavior, PlaybackRate, StartTime, Volume};
+use std::time::Duration;
+
+use crate::{
+	dsp::Random, track::TrackId, tween::Tween, LoopBehavior, PlaybackRate, StartTime, Volume,
+};
+
+/// What to do when a packet of audio in a file
+/// fails to decode.
+///
+/// This only applies to static sounds loaded from a file.
+/// A streaming sound always stops decoding at a corrupt
+/// packet and reports the error via
+/// `StreamingSoundHandle::pop_error`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum DecodeErrorPolicy {
+	/// Stop loading the file and return the error.
+	#[default]
+	Abort,
+	/// Skip the corrupt packet and continue decoding
+	/// the rest of the file.
+	SkipPacket,
+	/// Stop loading the file, but keep the audio
+	/// that was decoded before the corrupt packet.
+	Truncate,
+}
+
+/// Which ReplayGain tag to apply to a sound's volume when
+/// loading an audio file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum ReplayGainMode {
+	/// Ignore ReplayGain tags.
+	#[default]
+	Off,
+	/// Use the `REPLAYGAIN_TRACK_GAIN` tag, which makes every
+	/// track play at about the same loudness.
+	Track,
+	/// Use the `REPLAYGAIN_ALBUM_GAIN` tag, which keeps the
+	/// loudness differences between tracks on the same album.
+	///
+	/// Falls back to the track gain if the file doesn't have
+	/// an album gain tag.
+	Album,
+}
+
+/// How audio is interpolated when a sound is played back at a
+/// different sample rate than the audio output or at a different
+/// playback rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum ResampleQuality {
+	/// Repeats each frame until the next one with no interpolation.
+	///
+	/// This has the lowest latency (1 frame), but it adds harsh artifacts
+	/// whenever the sound's sample rate doesn't match the output's
+	/// or its playback rate is changed. It's best suited for sounds
+	/// that always play at the output's sample rate.
+	SampleAndHold,
+	/// Interpolates between 2 frames using a straight line.
+	///
+	/// This has 2 frames of latency and sounds slightly duller
+	/// than [`ResampleQuality::Cubic`].
+	Linear,
+	/// Interpolates between 4 frames using a cubic curve.
+	///
+	/// This is cheap and sounds good for most sounds, but it
+	/// can add audible artifacts to high frequencies when the
+	/// sound's sample rate is much lower than the output's
+	/// (for example, a 22,050 Hz sound on a 48,000 Hz device).
+	/// It has 3 frames of latency.
+	#[default]
+	Cubic,
+	/// Interpolates between 16 frames using a windowed sinc
+	/// function.
+	///
+	/// This greatly reduces high frequency artifacts, but it
+	/// uses several times as much CPU time as [`ResampleQuality::Cubic`]
+	/// and has 9 frames of latency.
+	Sinc,
+}
+
+impl ResampleQuality {
+	/// Returns how many frames the sound looks ahead to
+	/// interpolate between them.
+	///
+	/// Interpolating between more frames gives smoother results,
+	/// but changes to the sound (like pausing, seeking, or changing
+	/// the volume) are applied to frames as they enter the buffer,
+	/// so they're heard [`latency_frames`](Self::latency_frames)
+	/// frames later.
+	pub fn buffer_len(self) -> usize {
+		match self {
+			ResampleQuality::SampleAndHold => 1,
+			ResampleQuality::Linear => 2,
+			ResampleQuality::Cubic => 4,
+			ResampleQuality::Sinc => SINC_NUM_FRAMES,
+		}
+	}
+
+	/// Returns how many frames (at the sound's sample rate) it takes
+	/// for changes to the sound to be heard.
+	pub fn latency_frames(self) -> usize {
+		// the current frame is in the middle of the buffer (rounding
+		// down), and new frames are pushed after the current frame
+		// is output, so the current frame also counts toward the latency
+		self.buffer_len() - (self.buffer_len() - 1) / 2
+	}
+}
+
+/// The number of frames used for windowed sinc interpolation.
+const SINC_NUM_FRAMES: usize = 16;
 
 /// Settings for a static sound.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -6,10 +117,42 @@ use crate::{track::TrackId, tween::Tween, LoopBehavior, PlaybackRate, StartTime,
 pub struct StaticSoundSettings {
 	/// When the sound should start playing.
 	pub start_time: StartTime,
+	/// Whether the sound should wait to be started with
+	/// [`StaticSoundHandle::start`](super::StaticSoundHandle::start)
+	/// instead of starting on its own.
+	///
+	/// A sound that starts paused is added to the audio renderer
+	/// right away, but it produces silence until it's started, and
+	/// [`start_time`](Self::start_time) is ignored.
+	pub start_paused: bool,
 	/// The initial playback position of the sound (in seconds).
+	///
+	/// This must be before the end of the sound, or the sound
+	/// will fail to play with
+	/// [`StaticSoundError::StartPositionOutOfBounds`](super::StaticSoundError::StartPositionOutOfBounds).
 	pub start_position: f64,
+	/// If set, the sound starts at this point within its loop
+	/// region instead of at [`start_position`](Self::start_position),
+	/// where `0.0` is the loop start and `1.0` is the end of the sound.
+	///
+	/// If the sound doesn't loop, the whole sound is used as the
+	/// region. The point is always measured from the start of the
+	/// sound, even if it plays in [`reverse`](Self::reverse).
+	///
+	/// This is usually set with
+	/// [`random_start_position`](Self::random_start_position).
+	pub start_position_in_loop: Option<f64>,
 	/// The volume of the sound.
 	pub volume: Volume,
+	/// A fixed gain applied to the sound on top of the
+	/// [`volume`](Self::volume).
+	///
+	/// Unlike the volume, the output gain can't be changed or
+	/// tweened after the sound starts playing. This makes it a good
+	/// place for a per-instance level that's set once when the sound
+	/// is played, like a random variation, leaving the volume free
+	/// for automation from gameplay code.
+	pub output_gain: Volume,
 	/// The playback rate of the sound.
 	///
 	/// Changing the playback rate will change both the speed
@@ -24,11 +167,64 @@ pub struct StaticSoundSettings {
 	/// to the end of the sound.
 	pub reverse: bool,
 	/// The looping behavior of the sound.
+	///
+	/// The loop start position must be before the end of the sound,
+	/// or the sound will fail to play with
+	/// [`StaticSoundError::LoopStartOutOfBounds`](super::StaticSoundError::LoopStartOutOfBounds).
 	pub loop_behavior: Option<LoopBehavior>,
+	/// How long to crossfade across the loop seam, which can hide
+	/// clicks when the end of the loop doesn't line up smoothly with
+	/// the loop start.
+	///
+	/// The end of the loop region fades into the audio following
+	/// the loop start, and when playback wraps around, it picks up
+	/// right after the audio that was faded in. The crossfade is
+	/// shortened to half of the loop region if it's longer than that.
+	/// This has no effect if the sound doesn't loop.
+	pub loop_crossfade: Duration,
+	/// Whether to read the loop start point from the file's
+	/// metadata when loading an audio file.
+	///
+	/// If enabled and [`loop_behavior`](Self::loop_behavior) is `None`,
+	/// a `LOOPSTART` tag (in frames) will be used to set the looping
+	/// behavior of the sound. The sound always loops from the end, so
+	/// `LOOPLENGTH` tags are ignored. If the tag is missing,
+	/// malformed, or past the end of the audio, the sound will
+	/// not loop.
+	pub loop_behavior_from_metadata: bool,
+	/// Which ReplayGain tag to read from the file's metadata
+	/// when loading an audio file.
+	///
+	/// The gain is applied on top of [`volume`](Self::volume)
+	/// rather than replacing it, so an explicit volume is relative
+	/// to the normalized loudness. For example, a volume of -6 dB
+	/// with a track gain of -3 dB results in a volume of -9 dB.
+	/// If the tag is missing or malformed, the volume is left as is.
+	pub replay_gain: ReplayGainMode,
+	/// What to do if part of the audio fails to decode
+	/// when loading an audio file.
+	pub decode_error_policy: DecodeErrorPolicy,
 	/// The mixer track this sound should play on.
 	pub track: TrackId,
 	/// An optional fade-in from silence.
 	pub fade_in_tween: Option<Tween>,
+	/// How the sound is interpolated when its sample rate
+	/// doesn't match the output's or its playback rate is changed.
+	///
+	/// This also controls how many frames the sound buffers ahead
+	/// of the playback position, which delays when changes to the
+	/// sound are heard (see [`ResampleQuality::latency_frames`]).
+	pub resample_quality: ResampleQuality,
+	/// How many commands can be queued up for the sound at once
+	/// before its [`StaticSoundHandle`](super::StaticSoundHandle)
+	/// returns [`CommandError::CommandQueueFull`](crate::CommandError::CommandQueueFull).
+	///
+	/// Sounds that are changed many times per audio batch (for example,
+	/// sounds with volume or panning that is automated every frame) may
+	/// need a larger queue. Each slot takes up a small amount of memory
+	/// for as long as the sound is playing, so one-shot sounds are best
+	/// left with the default capacity of `8`.
+	pub command_capacity: usize,
 }
 
 impl StaticSoundSettings {
@@ -36,14 +232,23 @@ impl StaticSoundSettings {
 	pub fn new() -> Self {
 		Self {
 			start_time: StartTime::default(),
+			start_paused: false,
 			start_position: 0.0,
+			start_position_in_loop: None,
 			volume: Volume::Amplitude(1.0),
+			output_gain: Volume::Amplitude(1.0),
 			playback_rate: PlaybackRate::Factor(1.0),
 			panning: 0.5,
 			reverse: false,
 			loop_behavior: None,
+			loop_crossfade: Duration::ZERO,
+			loop_behavior_from_metadata: false,
+			replay_gain: ReplayGainMode::Off,
+			decode_error_policy: DecodeErrorPolicy::Abort,
 			track: TrackId::Main,
 			fade_in_tween: None,
+			resample_quality: ResampleQuality::default(),
+			command_capacity: 8,
 		}
 	}
 
@@ -55,6 +260,15 @@ impl StaticSoundSettings {
 		}
 	}
 
+	/// Sets whether the sound should wait to be started with
+	/// [`StaticSoundHandle::start`](super::StaticSoundHandle::start).
+	pub fn start_paused(self, start_paused: bool) -> Self {
+		Self {
+			start_paused,
+			..self
+		}
+	}
+
 	/// Sets the initial playback position of the sound (in seconds).
 	pub fn start_position(self, start_position: f64) -> Self {
 		Self {
@@ -63,6 +277,42 @@ impl StaticSoundSettings {
 		}
 	}
 
+	/// Sets the initial playback position of the sound.
+	pub fn start_position_duration(self, start_position: Duration) -> Self {
+		self.start_position(start_position.as_secs_f64())
+	}
+
+	/// Sets the point within the loop region where the sound
+	/// starts, where `0.0` is the loop start and `1.0` is the end
+	/// of the sound.
+	pub fn start_position_in_loop(self, start_position_in_loop: impl Into<Option<f64>>) -> Self {
+		Self {
+			start_position_in_loop: start_position_in_loop.into(),
+			..self
+		}
+	}
+
+	/// Makes the sound start at a random point within its loop region.
+	///
+	/// This keeps multiple copies of an ambient loop, like wind or a
+	/// crowd, from lining up with each other:
+	///
+	/// ```
+	/// # use kira::{dsp::Random, sound::static_sound::StaticSoundSettings, LoopBehavior};
+	/// let mut random = Random::from_entropy();
+	/// let settings = StaticSoundSettings::new()
+	/// 	.loop_behavior(LoopBehavior {
+	/// 		start_position: 0.0,
+	/// 	})
+	/// 	.random_start_position(&mut random);
+	/// ```
+	///
+	/// Use a [`Random`] with a fixed seed to get the same start
+	/// positions every time.
+	pub fn random_start_position(self, random: &mut Random) -> Self {
+		self.start_position_in_loop(random.next_f64())
+	}
+
 	/// Sets the volume of the sound.
 	pub fn volume(self, volume: impl Into<Volume>) -> Self {
 		Self {
@@ -71,6 +321,14 @@ impl StaticSoundSettings {
 		}
 	}
 
+	/// Sets the fixed gain applied on top of the volume.
+	pub fn output_gain(self, output_gain: impl Into<Volume>) -> Self {
+		Self {
+			output_gain: output_gain.into(),
+			..self
+		}
+	}
+
 	/// Sets the playback rate of the sound.
 	///
 	/// Changing the playback rate will change both the speed
@@ -101,6 +359,43 @@ impl StaticSoundSettings {
 		}
 	}
 
+	/// Sets how long to crossfade across the loop seam.
+	pub fn loop_crossfade(self, loop_crossfade: Duration) -> Self {
+		Self {
+			loop_crossfade,
+			..self
+		}
+	}
+
+	/// Sets whether to read the loop start point from the file's
+	/// metadata when loading an audio file.
+	///
+	/// Only the `LOOPSTART` tag is read.
+	pub fn loop_behavior_from_metadata(self, loop_behavior_from_metadata: bool) -> Self {
+		Self {
+			loop_behavior_from_metadata,
+			..self
+		}
+	}
+
+	/// Sets which ReplayGain tag to apply to the volume
+	/// when loading an audio file.
+	pub fn replay_gain(self, replay_gain: ReplayGainMode) -> Self {
+		Self {
+			replay_gain,
+			..self
+		}
+	}
+
+	/// Sets what to do if part of the audio fails to decode
+	/// when loading an audio file.
+	pub fn decode_error_policy(self, decode_error_policy: DecodeErrorPolicy) -> Self {
+		Self {
+			decode_error_policy,
+			..self
+		}
+	}
+
 	/// Sets the mixer track this sound should play on.
 	pub fn track(self, track: impl Into<TrackId>) -> Self {
 		Self {
@@ -116,6 +411,84 @@ impl StaticSoundSettings {
 			..self
 		}
 	}
+
+	/// Sets how the sound is interpolated when its sample rate
+	/// doesn't match the output's or its playback rate is changed.
+	pub fn resample_quality(self, resample_quality: ResampleQuality) -> Self {
+		Self {
+			resample_quality,
+			..self
+		}
+	}
+
+	/// Sets how many commands can be queued up for the sound at once.
+	pub fn command_capacity(self, command_capacity: usize) -> Self {
+		Self {
+			command_capacity,
+			..self
+		}
+	}
+
+	/// Sets the playback rate of the sound to a random value
+	/// between `min` and `max`.
+	///
+	/// The playback rate is chosen evenly in semitones, so a range
+	/// from 0.5 to 2.0 will pick pitches an octave below and above
+	/// the original pitch equally often. This is useful for adding
+	/// variety to sounds that are played often, like footsteps:
+	///
+	/// ```
+	/// # use kira::{dsp::Random, sound::static_sound::StaticSoundSettings};
+	/// let mut random = Random::from_entropy();
+	/// // pick a new playback rate every time the sound is played
+	/// let settings = StaticSoundSettings::new().random_playback_rate(0.9, 1.1, &mut random);
+	/// ```
+	///
+	/// Use a [`Random`] with a fixed seed to get the same sequence
+	/// of playback rates every time.
+	///
+	/// `min` and `max` can be given in either order. Both must be
+	/// positive and finite, otherwise the playback rate is
+	/// left unchanged.
+	pub fn random_playback_rate(
+		self,
+		min: impl Into<PlaybackRate>,
+		max: impl Into<PlaybackRate>,
+		random: &mut Random,
+	) -> Self {
+		// rates that aren't positive have no pitch in semitones,
+		// so this also rules those out
+		let min = min.into().as_semitones();
+		let max = max.into().as_semitones();
+		if !min.is_finite() || !max.is_finite() {
+			return self;
+		}
+		self.playback_rate(PlaybackRate::Semitones(
+			random.range(min.min(max), min.max(max)),
+		))
+	}
+
+	/// Sets the volume of the sound to a random value between
+	/// `min` and `max`.
+	///
+	/// The volume is chosen evenly in decibels, which matches
+	/// how loudness is perceived.
+	///
+	/// `min` and `max` can be given in either order. Both must be
+	/// finite, otherwise the volume is left unchanged.
+	pub fn random_volume(
+		self,
+		min: impl Into<Volume>,
+		max: impl Into<Volume>,
+		random: &mut Random,
+	) -> Self {
+		let min = min.into().as_decibels();
+		let max = max.into().as_decibels();
+		if !min.is_finite() || !max.is_finite() {
+			return self;
+		}
+		self.volume(Volume::Decibels(random.range(min, max)))
+	}
 }
 
 impl Default for StaticSoundSettings {
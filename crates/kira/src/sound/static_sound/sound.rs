@@ -1,16 +1,23 @@
 mod resampler;
 
-use std::sync::{
-	atomic::{AtomicU64, AtomicU8, Ordering},
-	Arc,
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
+		Arc,
+	},
+	time::Duration,
 };
 
-use ringbuf::Consumer;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+use ringbuf::{Consumer, Producer};
 
 use crate::{
 	clock::ClockTime,
 	dsp::Frame,
 	sound::Sound,
+	start_time::count_down_delay,
 	track::TrackId,
 	tween::{Tween, Tweener},
 	LoopBehavior, PlaybackRate, StartTime, Volume,
@@ -43,6 +50,24 @@ pub enum PlaybackState {
 pub(super) struct Shared {
 	state: AtomicU8,
 	position: AtomicU64,
+	/// The current playback rate of the sound, taking
+	/// into account whether it's playing in reverse.
+	playback_rate: AtomicU64,
+	/// The duration of the sound (in seconds).
+	///
+	/// This changes when a queued sound starts playing.
+	duration: AtomicU64,
+	looping: AtomicBool,
+	/// The time [`Shared::position`] was last updated,
+	/// measured in nanoseconds since `epoch`.
+	#[cfg(not(target_arch = "wasm32"))]
+	position_timestamp: AtomicU64,
+	/// Incremented before and after the position and timestamp are
+	/// updated, so it's odd while an update is in progress.
+	#[cfg(not(target_arch = "wasm32"))]
+	position_generation: AtomicU64,
+	#[cfg(not(target_arch = "wasm32"))]
+	epoch: Instant,
 }
 
 impl Shared {
@@ -60,17 +85,118 @@ impl Shared {
 	pub fn position(&self) -> f64 {
 		f64::from_bits(self.position.load(Ordering::SeqCst))
 	}
+
+	/// Records the playback position along with the current time.
+	fn set_position(&self, position: f64) {
+		#[cfg(not(target_arch = "wasm32"))]
+		self.position_generation.fetch_add(1, Ordering::SeqCst);
+		self.position.store(position.to_bits(), Ordering::SeqCst);
+		#[cfg(not(target_arch = "wasm32"))]
+		{
+			self.position_timestamp
+				.store(self.epoch.elapsed().as_nanos() as u64, Ordering::SeqCst);
+			self.position_generation.fetch_add(1, Ordering::SeqCst);
+		}
+	}
+
+	/// Estimates the current playback position by extrapolating from
+	/// the last recorded position using the time that has passed since.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn position_precise(&self) -> f64 {
+		// the position and timestamp are stored separately, so make sure
+		// neither was updated while we were reading them
+		let (position, timestamp) = loop {
+			let generation = self.position_generation.load(Ordering::SeqCst);
+			if generation % 2 == 1 {
+				std::hint::spin_loop();
+				continue;
+			}
+			let position = self.position();
+			let timestamp = self.position_timestamp.load(Ordering::SeqCst);
+			if self.position_generation.load(Ordering::SeqCst) == generation {
+				break (position, timestamp);
+			}
+		};
+		if self.state() != PlaybackState::Playing {
+			return position;
+		}
+		let elapsed = self
+			.epoch
+			.elapsed()
+			.saturating_sub(Duration::from_nanos(timestamp))
+			.as_secs_f64();
+		let playback_rate = f64::from_bits(self.playback_rate.load(Ordering::SeqCst));
+		let position = position + elapsed * playback_rate;
+		if self.looping.load(Ordering::SeqCst) {
+			position
+		} else {
+			position.clamp(0.0, self.duration())
+		}
+	}
+
+	pub fn progress(&self) -> f64 {
+		// an empty sound is over as soon as it starts
+		if self.duration() == 0.0 {
+			return 1.0;
+		}
+		(self.position() / self.duration()).clamp(0.0, 1.0)
+	}
+
+	pub fn duration(&self) -> f64 {
+		f64::from_bits(self.duration.load(Ordering::SeqCst))
+	}
+
+	pub fn time_remaining(&self) -> Option<Duration> {
+		if self.state() == PlaybackState::Stopped {
+			return Some(Duration::ZERO);
+		}
+		if self.looping.load(Ordering::SeqCst) {
+			return None;
+		}
+		let playback_rate = f64::from_bits(self.playback_rate.load(Ordering::SeqCst));
+		let position = self.position();
+		let remaining = if playback_rate > 0.0 {
+			self.duration() - position
+		} else if playback_rate < 0.0 {
+			position
+		} else {
+			// the sound isn't moving, so it will never finish
+			return None;
+		};
+		Some(Duration::from_secs_f64(
+			(remaining / playback_rate.abs()).max(0.0),
+		))
+	}
 }
 
 pub(super) struct StaticSound {
 	command_consumer: Consumer<Command>,
 	data: StaticSoundData,
+	/// A sound to start playing as soon as this one reaches the end.
+	queued_data: Option<Box<StaticSoundData>>,
+	/// Sends sound data that's no longer needed (a queued sound that
+	/// was replaced, or the sound that was playing before a queued
+	/// sound started) back to the handle so it isn't deallocated
+	/// on the audio thread.
+	unused_data_producer: Producer<Box<StaticSoundData>>,
+	/// Whether the sound has been started. This is only `false` for
+	/// sounds that were set to start paused and haven't been started
+	/// by the handle yet.
+	started: bool,
 	start_time: StartTime,
+	/// The time (in seconds) left before the sound starts
+	/// if it was started with a [`StartTime::Delay`].
+	remaining_start_delay: f64,
+	/// The fade-out to use when the sound reaches the end
+	/// after being told to stop at the end of the current loop.
+	stop_after_loop: Option<Tween>,
 	state: PlaybackState,
 	resampler: Resampler,
 	current_sample_index: usize,
 	fractional_position: f64,
 	volume: Tweener<Volume>,
+	/// The amplitude of the fixed output gain.
+	output_gain: f32,
 	playback_rate: Tweener<PlaybackRate>,
 	panning: Tweener,
 	volume_fade: Tweener<Volume>,
@@ -78,29 +204,35 @@ pub(super) struct StaticSound {
 }
 
 impl StaticSound {
-	pub fn new(data: StaticSoundData, command_consumer: Consumer<Command>) -> Self {
+	pub fn new(
+		data: StaticSoundData,
+		command_consumer: Consumer<Command>,
+		unused_data_producer: Producer<Box<StaticSoundData>>,
+	) -> Self {
 		let settings = data.settings;
-		let current_sample_index = if settings.reverse {
-			let position_seconds = data.duration().as_secs_f64() - settings.start_position;
-			(position_seconds * data.sample_rate as f64) as usize - 1
-		} else {
-			(settings.start_position * data.sample_rate as f64) as usize
-		};
+		let current_sample_index = start_index(&data);
 		let position = current_sample_index as f64 / data.sample_rate as f64;
+		let duration = data.duration().as_secs_f64();
 		let mut sound = Self {
 			command_consumer,
 			data,
+			queued_data: None,
+			unused_data_producer,
+			started: !settings.start_paused,
 			start_time: settings.start_time,
+			remaining_start_delay: settings.start_time.delay().unwrap_or(0.0),
+			stop_after_loop: None,
 			state: PlaybackState::Playing,
-			resampler: Resampler::new(),
+			resampler: Resampler::new(settings.resample_quality),
 			current_sample_index,
 			fractional_position: 0.0,
 			volume: Tweener::new(settings.volume),
+			output_gain: settings.output_gain.as_amplitude() as f32,
 			playback_rate: Tweener::new(settings.playback_rate),
 			panning: Tweener::new(settings.panning),
 			volume_fade: if let Some(tween) = settings.fade_in_tween {
-				let mut tweenable = Tweener::new(Volume::Decibels(Volume::MIN_DECIBELS));
-				tweenable.set(Volume::Decibels(0.0), tween);
+				let mut tweenable = Tweener::new(tween.fade_curve.silent_volume());
+				tweenable.set(tween.fade_curve.full_volume(), tween);
 				tweenable
 			} else {
 				Tweener::new(Volume::Decibels(0.0))
@@ -108,11 +240,27 @@ impl StaticSound {
 			shared: Arc::new(Shared {
 				state: AtomicU8::new(PlaybackState::Playing as u8),
 				position: AtomicU64::new(position.to_bits()),
+				playback_rate: AtomicU64::new(
+					if settings.reverse {
+						-settings.playback_rate.as_factor()
+					} else {
+						settings.playback_rate.as_factor()
+					}
+					.to_bits(),
+				),
+				duration: AtomicU64::new(duration.to_bits()),
+				looping: AtomicBool::new(settings.loop_behavior.is_some()),
+				#[cfg(not(target_arch = "wasm32"))]
+				position_timestamp: AtomicU64::new(0),
+				#[cfg(not(target_arch = "wasm32"))]
+				position_generation: AtomicU64::new(0),
+				#[cfg(not(target_arch = "wasm32"))]
+				epoch: Instant::now(),
 			}),
 		};
-		// fill the resample buffer with 3 samples so playback can
-		// start immediately
-		for _ in 0..3 {
+		// fill the resample buffer with the upcoming samples so
+		// playback can start immediately
+		for _ in 0..sound.resampler.num_upcoming_frames() {
 			sound.update_position();
 		}
 		sound
@@ -127,23 +275,6 @@ impl StaticSound {
 		self.shared.state.store(state as u8, Ordering::SeqCst);
 	}
 
-	fn pause(&mut self, fade_out_tween: Tween) {
-		self.set_state(PlaybackState::Pausing);
-		self.volume_fade
-			.set(Volume::Decibels(Volume::MIN_DECIBELS), fade_out_tween);
-	}
-
-	fn resume(&mut self, fade_in_tween: Tween) {
-		self.set_state(PlaybackState::Playing);
-		self.volume_fade.set(Volume::Decibels(0.0), fade_in_tween);
-	}
-
-	fn stop(&mut self, fade_out_tween: Tween) {
-		self.set_state(PlaybackState::Stopping);
-		self.volume_fade
-			.set(Volume::Decibels(Volume::MIN_DECIBELS), fade_out_tween);
-	}
-
 	fn playback_rate(&self) -> f64 {
 		if self.data.settings.reverse {
 			-self.playback_rate.value().as_factor()
@@ -155,10 +286,9 @@ impl StaticSound {
 	/// Increments the playback position by 1 sample. Returns `true` if the end
 	/// of the sound was reached.
 	fn increment_position(&mut self) -> bool {
-		if let Some(LoopBehavior { start_position }) = self.data.settings.loop_behavior {
-			let start_position = (start_position * self.data.sample_rate as f64) as usize;
+		if let Some(restart_index) = self.loop_restart_index() {
 			if self.current_sample_index >= self.data.frames.len() - 1 {
-				self.current_sample_index = start_position;
+				self.current_sample_index = restart_index;
 			} else {
 				self.current_sample_index += 1;
 			}
@@ -175,9 +305,8 @@ impl StaticSound {
 	/// Decrements the playback position by 1 sample. Returns `true` if the end
 	/// of the sound was reached (which in this case would be sample -1).
 	fn decrement_position(&mut self) -> bool {
-		if let Some(LoopBehavior { start_position }) = self.data.settings.loop_behavior {
-			let start_position = (start_position * self.data.sample_rate as f64) as usize;
-			if self.current_sample_index <= start_position {
+		if let Some(restart_index) = self.loop_restart_index() {
+			if self.current_sample_index <= restart_index {
 				self.current_sample_index = self.data.frames.len() - 1;
 			} else {
 				self.current_sample_index -= 1;
@@ -192,6 +321,51 @@ impl StaticSound {
 		false
 	}
 
+	/// Returns the index of the loop start and the number of frames
+	/// at the end of the loop that crossfade into the frames after
+	/// the loop start, or `None` if the sound doesn't loop.
+	fn loop_points(&self) -> Option<(usize, usize)> {
+		let LoopBehavior { start_position } = self.data.settings.loop_behavior?;
+		let loop_start = (start_position * self.data.sample_rate as f64) as usize;
+		// the crossfade covers the frames after the loop start and the
+		// frames at the end of the sound, so it can be at most half of
+		// the loop region
+		let crossfade_frames = ((self.data.settings.loop_crossfade.as_secs_f64()
+			* self.data.sample_rate as f64) as usize)
+			.min((self.data.frames.len() - loop_start) / 2);
+		Some((loop_start, crossfade_frames))
+	}
+
+	/// Returns the index playback wraps around to when it reaches the
+	/// end of the loop, or `None` if the sound doesn't loop.
+	///
+	/// The frames after the loop start that were already faded in at
+	/// the end of the loop are skipped.
+	fn loop_restart_index(&self) -> Option<usize> {
+		self.loop_points()
+			.map(|(loop_start, crossfade_frames)| loop_start + crossfade_frames)
+	}
+
+	/// Returns the frame at the given index, crossfading the end of
+	/// the loop region into the audio after the loop start if needed.
+	fn frame_at_index(&self, index: usize) -> Frame {
+		let frame = self.data.frames[index];
+		let (loop_start, crossfade_frames) = match self.loop_points() {
+			Some(loop_points) => loop_points,
+			None => return frame,
+		};
+		let crossfade_start = self.data.frames.len() - crossfade_frames;
+		if index < crossfade_start {
+			return frame;
+		}
+		// by the last frame of the loop, this reaches the frame right
+		// before the restart index, so the wrap-around is seamless
+		let offset = index - crossfade_start;
+		let amount = (offset + 1) as f32 / crossfade_frames as f32;
+		let lead_in_frame = self.data.frames[loop_start + offset];
+		frame * (1.0 - amount) + lead_in_frame * amount
+	}
+
 	/// Updates the playback position and pushes a new sample to the resampler.
 	fn update_position(&mut self) {
 		let playback_rate = self.playback_rate();
@@ -199,10 +373,17 @@ impl StaticSound {
 			self.resampler.push_frame(Frame::ZERO, None);
 			return;
 		}
-		let out = self.data.frames[self.current_sample_index];
+		// a sound with no audio finishes right away
+		if self.data.frames.is_empty() {
+			self.set_state(PlaybackState::Stopped);
+			self.resampler.push_frame(Frame::ZERO, None);
+			return;
+		}
+		let out = self.frame_at_index(self.current_sample_index);
 		let out = (out
 			* self.volume_fade.value().as_amplitude() as f32
-			* self.volume.value().as_amplitude() as f32)
+			* self.volume.value().as_amplitude() as f32
+			* self.output_gain)
 			.panned(self.panning.value() as f32);
 		self.resampler.push_frame(out, self.current_sample_index);
 		let reached_end_of_sound = if playback_rate.is_sign_negative() {
@@ -211,7 +392,64 @@ impl StaticSound {
 			self.increment_position()
 		};
 		if reached_end_of_sound {
+			if self.stop_after_loop.is_none() {
+				if let Some(queued_data) = self.queued_data.take() {
+					self.start_queued_sound(queued_data);
+					return;
+				}
+			}
 			self.set_state(PlaybackState::Stopped);
+		} else if let Some(fade_out_tween) = self.stop_after_loop {
+			self.start_stop_after_loop_fade(fade_out_tween, playback_rate);
+		}
+	}
+
+	/// Starts fading out a sound that's stopping after the current
+	/// loop once it's close enough to the end for the fade to finish
+	/// right as the sound ends.
+	fn start_stop_after_loop_fade(&mut self, fade_out_tween: Tween, playback_rate: f64) {
+		if self.state == PlaybackState::Stopping {
+			return;
+		}
+		let remaining_frames = if playback_rate.is_sign_negative() {
+			self.current_sample_index + 1
+		} else {
+			self.data.frames.len() - self.current_sample_index
+		};
+		let remaining_time =
+			remaining_frames as f64 / self.data.sample_rate as f64 / playback_rate.abs();
+		if remaining_time <= fade_out_tween.duration.as_secs_f64() {
+			self.stop(Tween {
+				start_time: StartTime::Immediate,
+				..fade_out_tween
+			});
+		}
+	}
+
+	/// Switches to playing the queued sound. The first frame of the
+	/// queued sound will be the next frame pushed to the resampler,
+	/// so there's no gap between the two sounds.
+	fn start_queued_sound(&mut self, mut queued_data: Box<StaticSoundData>) {
+		self.current_sample_index = start_index(&queued_data);
+		self.shared.duration.store(
+			queued_data.duration().as_secs_f64().to_bits(),
+			Ordering::SeqCst,
+		);
+		self.shared.looping.store(
+			queued_data.settings.loop_behavior.is_some(),
+			Ordering::SeqCst,
+		);
+		// reuse the queued sound's box to send the previous sound
+		// back to the handle
+		std::mem::swap(&mut self.data, &mut queued_data);
+		self.discard_data(queued_data);
+	}
+
+	/// Sends sound data back to the handle to be dropped on the
+	/// main thread.
+	fn discard_data(&mut self, data: Box<StaticSoundData>) {
+		if self.unused_data_producer.push(data).is_err() {
+			panic!("The unused data producer should not be full because it has room for every sound that can be queued before the handle empties it");
 		}
 	}
 
@@ -219,10 +457,9 @@ impl StaticSound {
 		self.current_sample_index = index;
 		// if the seek index is past the end of the sound and the sound is
 		// looping, wrap the seek point back into the sound
-		if let Some(LoopBehavior { start_position }) = self.data.settings.loop_behavior {
-			let start_position = (start_position * self.data.sample_rate as f64) as usize;
+		if let Some(restart_index) = self.loop_restart_index() {
 			while self.current_sample_index >= self.data.frames.len() {
-				self.current_sample_index -= self.data.frames.len() - start_position;
+				self.current_sample_index -= self.data.frames.len() - restart_index;
 			}
 		// otherwise, stop the sound
 		} else if self.current_sample_index >= self.data.frames.len() {
@@ -233,10 +470,11 @@ impl StaticSound {
 		if matches!(self.state, PlaybackState::Paused | PlaybackState::Stopped) {
 			return;
 		}
-		let out = self.data.frames[self.current_sample_index];
+		let out = self.frame_at_index(self.current_sample_index);
 		let out = (out
 			* self.volume_fade.value().as_amplitude() as f32
-			* self.volume.value().as_amplitude() as f32)
+			* self.volume.value().as_amplitude() as f32
+			* self.output_gain)
 			.panned(self.panning.value() as f32);
 		self.resampler.push_frame(out, self.current_sample_index);
 	}
@@ -252,12 +490,20 @@ impl Sound for StaticSound {
 			.resampler
 			.position()
 			.expect("The resampler has not received any frames yet");
-		self.shared.position.store(
-			(last_played_frame_position as f64 / self.data.sample_rate as f64).to_bits(),
-			Ordering::SeqCst,
-		);
+		self.shared
+			.set_position(last_played_frame_position as f64 / self.data.sample_rate as f64);
+		self.shared
+			.playback_rate
+			.store(self.playback_rate().to_bits(), Ordering::SeqCst);
 		while let Some(command) = self.command_consumer.pop() {
 			match command {
+				Command::Start(start_time) => {
+					if !self.started {
+						self.started = true;
+						self.start_time = start_time;
+						self.remaining_start_delay = start_time.delay().unwrap_or(0.0);
+					}
+				}
 				Command::SetVolume(volume, tween) => self.volume.set(volume, tween),
 				Command::SetPlaybackRate(playback_rate, tween) => {
 					self.playback_rate.set(playback_rate, tween)
@@ -266,6 +512,11 @@ impl Sound for StaticSound {
 				Command::Pause(tween) => self.pause(tween),
 				Command::Resume(tween) => self.resume(tween),
 				Command::Stop(tween) => self.stop(tween),
+				Command::StopAfterLoop(tween) => {
+					self.stop_after_loop = Some(tween);
+					self.data.settings.loop_behavior = None;
+					self.shared.looping.store(false, Ordering::SeqCst);
+				}
 				Command::SeekBy(amount) => {
 					let current_position =
 						self.current_sample_index as f64 / self.data.sample_rate as f64;
@@ -273,10 +524,33 @@ impl Sound for StaticSound {
 					let index = (position * self.data.sample_rate as f64) as usize;
 					self.seek_to_index(index);
 				}
+				Command::QueueNext(data) => {
+					if let Some(replaced_data) = self.queued_data.replace(data) {
+						self.discard_data(replaced_data);
+					}
+				}
 				Command::SeekTo(position) => {
 					let index = (position * self.data.sample_rate as f64) as usize;
 					self.seek_to_index(index);
 				}
+				Command::SetLoopBehavior(loop_behavior) => {
+					// the handle checks the loop start against the sound
+					// that was playing when the command was sent, but a
+					// shorter queued sound may have taken over since then
+					if let Some(LoopBehavior { start_position }) = loop_behavior {
+						let loop_start = (start_position * self.data.sample_rate as f64) as usize;
+						if loop_start >= self.data.frames.len() {
+							continue;
+						}
+					}
+					// if playback is already past the new loop start, it
+					// continues to the end of the sound and then wraps
+					// around, just like when seeking past the end
+					self.data.settings.loop_behavior = loop_behavior;
+					self.shared
+						.looping
+						.store(loop_behavior.is_some(), Ordering::SeqCst);
+				}
 			}
 		}
 	}
@@ -292,11 +566,18 @@ impl Sound for StaticSound {
 				_ => {}
 			}
 		}
-		if matches!(self.start_time, StartTime::ClockTime(..)) {
+		if !self.started || matches!(self.start_time, StartTime::ClockTime(..)) {
 			return Frame::ZERO;
 		}
+		// if the delay ends partway through this frame, only advance
+		// the playback position by the time after the delay
+		let playback_dt = match count_down_delay(&mut self.remaining_start_delay, dt) {
+			Some(playback_dt) => playback_dt,
+			None => return Frame::ZERO,
+		};
 		let out = self.resampler.get(self.fractional_position as f32);
-		self.fractional_position += self.data.sample_rate as f64 * self.playback_rate().abs() * dt;
+		self.fractional_position +=
+			self.data.sample_rate as f64 * self.playback_rate().abs() * playback_dt;
 		while self.fractional_position >= 1.0 {
 			self.fractional_position -= 1.0;
 			self.update_position();
@@ -309,6 +590,9 @@ impl Sound for StaticSound {
 		self.playback_rate.on_clock_tick(time);
 		self.panning.on_clock_tick(time);
 		self.volume_fade.on_clock_tick(time);
+		if !self.started {
+			return;
+		}
 		if let StartTime::ClockTime(ClockTime { clock, ticks }) = self.start_time {
 			if time.clock == clock && time.ticks >= ticks {
 				self.start_time = StartTime::Immediate;
@@ -316,7 +600,62 @@ impl Sound for StaticSound {
 		}
 	}
 
+	fn pause(&mut self, fade_out_tween: Tween) {
+		// a sound that's stopping can't be paused, or it
+		// would never finish
+		if matches!(self.state, PlaybackState::Stopping | PlaybackState::Stopped) {
+			return;
+		}
+		self.set_state(PlaybackState::Pausing);
+		self.volume_fade
+			.set(fade_out_tween.fade_curve.silent_volume(), fade_out_tween);
+	}
+
+	fn resume(&mut self, fade_in_tween: Tween) {
+		if matches!(self.state, PlaybackState::Stopping | PlaybackState::Stopped) {
+			return;
+		}
+		self.set_state(PlaybackState::Playing);
+		self.volume_fade
+			.set(fade_in_tween.fade_curve.full_volume(), fade_in_tween);
+	}
+
+	fn stop(&mut self, fade_out_tween: Tween) {
+		self.set_state(PlaybackState::Stopping);
+		self.volume_fade
+			.set(fade_out_tween.fade_curve.silent_volume(), fade_out_tween);
+	}
+
 	fn finished(&self) -> bool {
 		self.state == PlaybackState::Stopped && self.resampler.is_empty()
 	}
 }
+
+/// Returns the index of the frame a sound should start playing from.
+fn start_index(data: &StaticSoundData) -> usize {
+	let settings = data.settings;
+	if data.frames.is_empty() {
+		return 0;
+	}
+	if let Some(start_position_in_loop) = settings.start_position_in_loop {
+		let loop_start_index = settings
+			.loop_behavior
+			.map_or(0, |LoopBehavior { start_position }| {
+				(start_position * data.sample_rate as f64) as usize
+			});
+		let num_loop_frames = data.frames.len() - loop_start_index;
+		let offset = (start_position_in_loop.clamp(0.0, 1.0) * num_loop_frames as f64) as usize;
+		return loop_start_index + offset.min(num_loop_frames - 1);
+	}
+	if settings.reverse {
+		// the first frame to play is the one that contains the
+		// position, so a start position inside the last frame
+		// starts on the last frame
+		let position_seconds = data.duration().as_secs_f64() - settings.start_position;
+		((position_seconds * data.sample_rate as f64).ceil() as usize)
+			.saturating_sub(1)
+			.min(data.frames.len() - 1)
+	} else {
+		(settings.start_position * data.sample_rate as f64) as usize
+	}
+}
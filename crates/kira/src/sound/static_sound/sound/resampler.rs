@@ -1,4 +1,9 @@
-use crate::dsp::{interpolate_frame, Frame};
+use std::f32::consts::PI;
+
+use crate::{
+	dsp::{interpolate_frame, Frame},
+	sound::static_sound::ResampleQuality,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct BufferedFrame {
@@ -7,42 +12,63 @@ struct BufferedFrame {
 }
 
 pub(super) struct Resampler {
-	frames: [BufferedFrame; 4],
+	quality: ResampleQuality,
+	/// The frames surrounding the current playback position.
+	/// The current frame is at `current_index`, and frames after
+	/// that are the upcoming frames.
+	frames: Vec<BufferedFrame>,
 	last_frame_position: Option<usize>,
 }
 
 impl Resampler {
-	pub fn new() -> Self {
+	pub fn new(quality: ResampleQuality) -> Self {
+		let num_frames = quality.buffer_len();
 		Self {
-			frames: [BufferedFrame {
-				frame: Frame::ZERO,
-				position: None,
-			}; 4],
+			quality,
+			frames: vec![
+				BufferedFrame {
+					frame: Frame::ZERO,
+					position: None,
+				};
+				num_frames
+			],
 			last_frame_position: None,
 		}
 	}
 
+	/// The number of frames that need to be pushed before the
+	/// first frame of a sound is the current frame.
+	pub fn num_upcoming_frames(&self) -> usize {
+		self.frames.len() - self.current_index()
+	}
+
 	pub fn push_frame(&mut self, frame: Frame, position: impl Into<Option<usize>>) {
-		for i in 0..self.frames.len() - 1 {
-			self.frames[i] = self.frames[i + 1];
-		}
-		self.frames[self.frames.len() - 1] = BufferedFrame {
+		self.frames.rotate_left(1);
+		*self.frames.last_mut().unwrap() = BufferedFrame {
 			frame,
 			position: position.into(),
 		};
-		if let Some(position) = self.frames[1].position {
+		if let Some(position) = self.frames[self.current_index()].position {
 			self.last_frame_position = Some(position);
 		}
 	}
 
 	pub fn get(&self, fractional_position: f32) -> Frame {
-		interpolate_frame(
-			self.frames[0].frame,
-			self.frames[1].frame,
-			self.frames[2].frame,
-			self.frames[3].frame,
-			fractional_position,
-		)
+		match self.quality {
+			ResampleQuality::SampleAndHold => self.frames[0].frame,
+			ResampleQuality::Linear => {
+				self.frames[0].frame
+					+ (self.frames[1].frame - self.frames[0].frame) * fractional_position
+			}
+			ResampleQuality::Cubic => interpolate_frame(
+				self.frames[0].frame,
+				self.frames[1].frame,
+				self.frames[2].frame,
+				self.frames[3].frame,
+				fractional_position,
+			),
+			ResampleQuality::Sinc => self.get_sinc(fractional_position),
+		}
 	}
 
 	pub fn position(&self) -> Option<usize> {
@@ -54,4 +80,42 @@ impl Resampler {
 			.iter()
 			.all(|BufferedFrame { frame, .. }| *frame == Frame::ZERO)
 	}
+
+	fn current_index(&self) -> usize {
+		(self.frames.len() - 1) / 2
+	}
+
+	/// Interpolates between frames using a sinc function with a
+	/// Blackman window.
+	fn get_sinc(&self, fractional_position: f32) -> Frame {
+		// the sinc function can't be evaluated exactly on a frame,
+		// but it would just return that frame anyway. the fractional
+		// position can be rounded up to 1.0 when it's converted to an f32.
+		if fractional_position <= 0.0 {
+			return self.frames[self.current_index()].frame;
+		}
+		if fractional_position >= 1.0 {
+			return self.frames[self.current_index() + 1].frame;
+		}
+		let half_width = (self.frames.len() / 2) as f32;
+		// sin(pi * (x - i)) only changes sign for each frame, so it
+		// only needs to be calculated once
+		let sin = (PI * fractional_position).sin();
+		let mut out = Frame::ZERO;
+		let mut total_weight = 0.0;
+		for (i, BufferedFrame { frame, .. }) in self.frames.iter().enumerate() {
+			let offset = i as f32 - self.current_index() as f32;
+			let x = fractional_position - offset;
+			let sign = if offset as i32 % 2 == 0 { 1.0 } else { -1.0 };
+			let sinc = sign * sin / (PI * x);
+			let window =
+				0.42 + 0.5 * (PI * x / half_width).cos() + 0.08 * (2.0 * PI * x / half_width).cos();
+			let weight = sinc * window;
+			out += *frame * weight;
+			total_weight += weight;
+		}
+		// make sure the weights add up to 1 so constant signals
+		// keep the same volume
+		out / total_weight
+	}
 }
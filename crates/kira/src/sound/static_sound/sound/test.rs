@@ -2,14 +2,17 @@ use std::{sync::Arc, time::Duration};
 
 use crate::{
 	clock::ClockTime,
-	dsp::Frame,
+	dsp::{Frame, Random},
 	manager::{backend::mock::MockBackend, AudioManager},
 	sound::{
-		static_sound::{PlaybackState, StaticSoundData, StaticSoundSettings},
+		static_sound::{
+			PlaybackState, ResampleQuality, SetLoopBehaviorError, StaticSoundData,
+			StaticSoundSettings,
+		},
 		Sound,
 	},
-	tween::Tween,
-	ClockSpeed, LoopBehavior, StartTime, Volume,
+	tween::{FadeCurve, Tween},
+	ClockSpeed, CommandError, LoopBehavior, PlaybackRate, StartTime, Volume,
 };
 
 use super::StaticSound;
@@ -46,6 +49,23 @@ fn plays_all_samples() {
 	assert_eq!(sound.state, PlaybackState::Stopped);
 }
 
+/// Tests that a `StaticSound` with no audio finishes right away,
+/// whichever direction it's played in.
+#[test]
+fn empty_sound_finishes() {
+	for reverse in [false, true] {
+		let data = StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new(vec![]),
+			settings: StaticSoundSettings::new().reverse(reverse),
+		};
+		let (mut sound, _) = data.split();
+		assert_eq!(sound.state, PlaybackState::Stopped);
+		assert_eq!(sound.process(1.0), Frame::ZERO);
+		assert!(sound.finished());
+	}
+}
+
 /// Tests that a `StaticSound` correctly reports its playback state
 /// to be queried by StaticSoundHandle::state.
 #[test]
@@ -85,6 +105,32 @@ fn reports_playback_position() {
 	}
 }
 
+/// Tests that `StaticSoundHandle::position_precise` extrapolates
+/// from the last reported position while the sound is playing.
+#[test]
+#[allow(clippy::float_cmp)]
+fn estimates_precise_playback_position() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(0.0); 100]),
+		settings: StaticSoundSettings::new(),
+	};
+	let (mut sound, handle) = data.split();
+	sound.process(1.0);
+	sound.on_start_processing();
+	let position = handle.position();
+	std::thread::sleep(Duration::from_millis(20));
+	let estimate = handle.position_precise();
+	assert!(estimate >= position + 0.02);
+	assert!(estimate < position + 1.0);
+
+	// when the sound is paused, the estimate should stop moving
+	sound.pause(Tween::default());
+	sound.process(1.0);
+	sound.on_start_processing();
+	assert_eq!(handle.position_precise(), handle.position());
+}
+
 /// Tests that a `StaticSound` fades out fully before pausing
 /// and fades back in when resuming.
 #[test]
@@ -214,6 +260,117 @@ fn stops_with_fade_out() {
 	}
 }
 
+/// Tests that a `StaticSound` fades linearly in amplitude when pausing
+/// and resuming with `FadeCurve::Amplitude`.
+#[test]
+#[allow(clippy::float_cmp)]
+fn fades_with_amplitude_curve() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 100]),
+		settings: StaticSoundSettings::new(),
+	};
+	let (mut sound, mut handle) = data.split();
+	let tween = Tween {
+		duration: Duration::from_secs(4),
+		fade_curve: FadeCurve::Amplitude,
+		..Default::default()
+	};
+
+	sound.process(1.0);
+	handle.pause(tween).unwrap();
+	sound.on_start_processing();
+	// allow for a few samples of delay because of the resampling
+	expect_frame_soon(Frame::from_mono(0.75).panned(0.5), &mut sound);
+	for amplitude in [0.5, 0.25, 0.0] {
+		assert_eq!(sound.process(1.0), Frame::from_mono(amplitude).panned(0.5));
+	}
+	assert_eq!(sound.state, PlaybackState::Paused);
+
+	handle.resume(tween).unwrap();
+	sound.on_start_processing();
+	expect_frame_soon(Frame::from_mono(0.25).panned(0.5), &mut sound);
+	for amplitude in [0.5, 0.75, 1.0] {
+		assert_eq!(sound.process(1.0), Frame::from_mono(amplitude).panned(0.5));
+	}
+}
+
+/// Tests that a `StaticSound` fades linearly in decibels when stopping
+/// with `FadeCurve::Decibels`.
+#[test]
+#[allow(clippy::float_cmp)]
+fn fades_with_decibel_curve() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 100]),
+		settings: StaticSoundSettings::new(),
+	};
+	let (mut sound, mut handle) = data.split();
+
+	sound.process(1.0);
+	handle
+		.stop(Tween {
+			duration: Duration::from_secs(4),
+			fade_curve: FadeCurve::Decibels,
+			..Default::default()
+		})
+		.unwrap();
+	sound.on_start_processing();
+	// allow for a few samples of delay because of the resampling
+	expect_frame_soon(
+		Frame::from_mono(Volume::Decibels(-15.0).as_amplitude() as f32).panned(0.5),
+		&mut sound,
+	);
+	for decibels in [-30.0, -45.0] {
+		assert_eq!(
+			sound.process(1.0),
+			Frame::from_mono(Volume::Decibels(decibels).as_amplitude() as f32).panned(0.5)
+		);
+	}
+	sound.process(1.0);
+	assert!(sound.finished());
+}
+
+/// Tests that a looping `StaticSound` told to stop after the current
+/// loop keeps playing to the end of the loop, fading out as it goes.
+#[test]
+#[allow(clippy::float_cmp)]
+fn stops_after_loop() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new().loop_behavior(LoopBehavior {
+			start_position: 0.0,
+		}),
+	};
+	let (mut sound, mut handle) = data.split();
+
+	sound.process(1.0);
+	handle
+		.stop_after_loop(Tween {
+			duration: Duration::from_secs(4),
+			fade_curve: FadeCurve::Amplitude,
+			..Default::default()
+		})
+		.unwrap();
+	sound.on_start_processing();
+	assert_eq!(sound.state, PlaybackState::Playing);
+	assert!(handle.time_remaining().is_some());
+
+	let mut output = vec![];
+	while !sound.finished() {
+		output.push(sound.process(1.0).left / Frame::from_mono(1.0).panned(0.5).left);
+		assert!(
+			output.len() < 20,
+			"the sound didn't stop at the end of the loop"
+		);
+	}
+	// the rest of the loop plays before the sound stops, fading out
+	// over the last few frames
+	assert_eq!(output, vec![1.0, 1.0, 1.0, 1.0, 1.0, 0.75, 0.5, 0.25, 0.0]);
+	assert_eq!(handle.state(), PlaybackState::Stopped);
+}
+
 /// Tests that a `StaticSound` can be paused and resumed on a clock tick.
 #[test]
 fn pauses_resumes_and_stops_on_clock_tick() {
@@ -351,6 +508,36 @@ fn waits_for_start_time() {
 	}
 }
 
+/// Tests that a `StaticSound` with a delayed start time starts
+/// playing on the exact sample the delay ends.
+#[test]
+#[allow(clippy::float_cmp)]
+fn waits_for_start_delay() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((1..100).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new().start_time(Duration::from_secs(3)),
+	};
+	let (mut sound, _) = data.split();
+
+	let first_nonzero_frame = (0..10)
+		.map(|_| sound.process(1.0))
+		.position(|frame| frame != Frame::ZERO);
+	assert_eq!(first_nonzero_frame, Some(3));
+
+	// if the delay ends partway through a sample, the playback
+	// position should be offset by the remainder
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((1..100).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new().start_time(Duration::from_secs_f64(1.5)),
+	};
+	let (mut sound, _) = data.split();
+	assert_eq!(sound.process(1.0), Frame::ZERO);
+	assert_eq!(sound.process(1.0), Frame::from_mono(1.0).panned(0.5));
+	assert_eq!(sound.process(1.0), Frame::from_mono(1.5).panned(0.5));
+}
+
 /// Tests that a `StaticSound` can be paused and resumed immediately
 /// even if playback is waiting for a clock time to start.
 #[test]
@@ -413,6 +600,115 @@ fn immediate_stop_with_clock_start_time() {
 	assert!(sound.state == PlaybackState::Stopped);
 }
 
+/// Tests that a `StaticSound` can be paused and resumed immediately
+/// even if playback is waiting for a start delay to elapse.
+#[test]
+fn immediate_pause_and_resume_with_delayed_start_time() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((1..100).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new().start_time(Duration::from_secs(5)),
+	};
+	let (mut sound, _) = data.split();
+
+	sound.pause(Tween {
+		duration: Duration::from_secs(0),
+		..Default::default()
+	});
+	sound.process(1.0);
+	assert!(sound.state == PlaybackState::Paused);
+	sound.resume(Tween {
+		duration: Duration::from_secs(0),
+		..Default::default()
+	});
+	sound.process(1.0);
+	assert!(sound.state == PlaybackState::Playing);
+}
+
+/// Tests that a `StaticSound` can be stopped immediately even if playback
+/// is waiting for a start delay to elapse.
+#[test]
+fn immediate_stop_with_delayed_start_time() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((1..100).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new().start_time(Duration::from_secs(5)),
+	};
+	let (mut sound, _) = data.split();
+
+	sound.stop(Tween {
+		duration: Duration::from_secs(0),
+		..Default::default()
+	});
+	sound.process(1.0);
+	assert!(sound.state == PlaybackState::Stopped);
+}
+
+/// Tests that a `StaticSound` set to start paused doesn't produce
+/// any output until it's started.
+#[test]
+#[allow(clippy::float_cmp)]
+fn waits_for_start_command() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((1..10).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new().start_paused(true),
+	};
+	let (mut sound, mut handle) = data.split();
+
+	for _ in 0..5 {
+		sound.on_start_processing();
+		assert_eq!(sound.process(1.0), Frame::from_mono(0.0).panned(0.5));
+		assert_eq!(handle.position(), 0.0);
+	}
+
+	handle.start(StartTime::Immediate).unwrap();
+	sound.on_start_processing();
+	for i in 1..=3 {
+		assert_eq!(sound.process(1.0), Frame::from_mono(i as f32).panned(0.5));
+	}
+}
+
+/// Tests that a `StaticSound` set to start paused can be started
+/// on a clock tick.
+#[test]
+#[allow(clippy::float_cmp)]
+fn starts_on_clock_tick() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((1..10).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new().start_paused(true),
+	};
+	// create a fake ClockId
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let clock_id = manager
+		.add_clock(ClockSpeed::TicksPerSecond(1.0))
+		.unwrap()
+		.id();
+	let (mut sound, mut handle) = data.split();
+
+	// clock ticks before the sound is started shouldn't start it
+	sound.on_clock_tick(ClockTime {
+		clock: clock_id,
+		ticks: 2,
+	});
+	assert_eq!(sound.process(1.0), Frame::from_mono(0.0).panned(0.5));
+
+	handle
+		.start(ClockTime {
+			clock: clock_id,
+			ticks: 2,
+		})
+		.unwrap();
+	sound.on_start_processing();
+	assert_eq!(sound.process(1.0), Frame::from_mono(0.0).panned(0.5));
+	sound.on_clock_tick(ClockTime {
+		clock: clock_id,
+		ticks: 2,
+	});
+	assert_eq!(sound.process(1.0), Frame::from_mono(1.0).panned(0.5));
+}
+
 /// Tests that a `StaticSound` can be started partway through the sound.
 #[test]
 #[allow(clippy::float_cmp)]
@@ -428,6 +724,99 @@ fn start_position() {
 	assert_eq!(sound.process(1.0), Frame::from_mono(3.0).panned(0.5));
 }
 
+/// Tests that copies of a `StaticSound` with random start positions
+/// start at different points within the loop region.
+#[test]
+fn random_start_position() {
+	let first_frame = |seed| {
+		let data = StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new((0..100).map(|i| Frame::from_mono(i as f32)).collect()),
+			settings: StaticSoundSettings::new()
+				.loop_behavior(LoopBehavior {
+					start_position: 50.0,
+				})
+				.random_start_position(&mut Random::new(seed)),
+		};
+		let (mut sound, _) = data.split();
+		sound.process(1.0)
+	};
+	let first = first_frame(1);
+	let second = first_frame(2);
+	assert_ne!(first, second);
+	for frame in [first, second] {
+		assert!(frame.left >= Frame::from_mono(50.0).panned(0.5).left);
+	}
+	// the same seed should always pick the same position
+	assert_eq!(first_frame(1), first);
+}
+
+/// Tests that a `StaticSound` can start at a specific point
+/// within its loop region.
+#[test]
+#[allow(clippy::float_cmp)]
+fn start_position_in_loop() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((0..10).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new()
+			.loop_behavior(LoopBehavior {
+				start_position: 6.0,
+			})
+			.start_position_in_loop(0.5),
+	};
+	let (mut sound, handle) = data.split();
+	assert_eq!(handle.position(), 8.0);
+	assert_eq!(sound.process(1.0), Frame::from_mono(8.0).panned(0.5));
+}
+
+/// Tests that a `StaticSound` with a loop crossfade fades the end
+/// of the loop into the audio after the loop start, so there's
+/// no jump at the seam.
+#[test]
+#[allow(clippy::float_cmp)]
+fn loop_crossfade() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((0..10).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new()
+			.loop_behavior(LoopBehavior {
+				start_position: 4.0,
+			})
+			.loop_crossfade(Duration::from_secs(2))
+			.start_position(6.0),
+	};
+	let (mut sound, _) = data.split();
+	let output: Vec<f32> = (0..8)
+		.map(|_| sound.process(1.0).left / Frame::from_mono(1.0).panned(0.5).left)
+		.collect();
+	// without the crossfade, playback would jump from 9 to 4
+	assert_eq!(output, vec![6.0, 7.0, 6.0, 5.0, 6.0, 7.0, 6.0, 5.0]);
+}
+
+/// Tests that a loop crossfade works when the loop starts at
+/// the beginning of the sound.
+#[test]
+#[allow(clippy::float_cmp)]
+fn loop_crossfade_from_start() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((0..6).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new()
+			.loop_behavior(LoopBehavior {
+				start_position: 0.0,
+			})
+			.loop_crossfade(Duration::from_secs(2)),
+	};
+	let (mut sound, _) = data.split();
+	let output: Vec<f32> = (0..8)
+		.map(|_| sound.process(1.0).left / Frame::from_mono(1.0).panned(0.5).left)
+		.collect();
+	// the last two frames fade into frames 0 and 1, and playback
+	// continues from frame 2
+	assert_eq!(output, vec![0.0, 1.0, 2.0, 3.0, 2.0, 1.0, 2.0, 3.0]);
+}
+
 /// Tests that a `StaticSound` can be played backwards.
 #[test]
 #[allow(clippy::float_cmp)]
@@ -445,6 +834,20 @@ fn reverse() {
 	}
 }
 
+/// Tests that a reversed `StaticSound` whose start position is
+/// inside the last frame starts playing from the last frame.
+#[test]
+#[allow(clippy::float_cmp)]
+fn reverse_from_inside_last_frame() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((0..4).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new().reverse(true).start_position(3.5),
+	};
+	let (mut sound, _) = data.split();
+	assert_eq!(sound.process(1.0), Frame::from_mono(0.0).panned(0.5));
+}
+
 /// Tests that a `StaticSound` properly obeys looping behavior when
 /// playing forward.
 #[test]
@@ -508,6 +911,32 @@ fn volume() {
 	assert_eq!(sound.process(1.0), Frame::from_mono(0.5).panned(0.5));
 }
 
+/// Tests that the output gain of a `StaticSound` is multiplied
+/// with its volume, and changing the volume keeps the gain.
+#[test]
+#[allow(clippy::float_cmp)]
+fn output_gain() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new().volume(0.5).output_gain(0.5),
+	};
+	let (mut sound, mut handle) = data.split();
+
+	assert_eq!(sound.process(1.0), Frame::from_mono(0.25).panned(0.5));
+	handle
+		.set_volume(
+			2.0,
+			Tween {
+				duration: Duration::ZERO,
+				..Default::default()
+			},
+		)
+		.unwrap();
+	sound.on_start_processing();
+	expect_frame_soon(Frame::from_mono(1.0).panned(0.5), &mut sound);
+}
+
 /// Tests that the volume of a `StaticSound` can be changed
 /// after the sound is started.
 #[test]
@@ -655,6 +1084,23 @@ fn playback_rate() {
 	assert_eq!(sound.process(1.0), Frame::from_mono(2.0).panned(0.5));
 }
 
+/// Tests that a playback rate of +12 semitones plays a `StaticSound`
+/// twice as fast.
+#[test]
+#[allow(clippy::float_cmp)]
+fn playback_rate_in_semitones() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((0..10).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new().playback_rate(PlaybackRate::Semitones(12.0)),
+	};
+	let (mut sound, _) = data.split();
+
+	assert_eq!(sound.process(1.0), Frame::from_mono(0.0).panned(0.5));
+	assert_eq!(sound.process(1.0), Frame::from_mono(2.0).panned(0.5));
+	assert_eq!(sound.process(1.0), Frame::from_mono(4.0).panned(0.5));
+}
+
 /// Tests that the playback rate of a `StaticSound` can be adjusted after
 /// it's started.
 #[test]
@@ -802,6 +1248,116 @@ fn seek_to_while_looping() {
 	expect_frame_soon(Frame::from_mono(25.0).panned(0.5), &mut sound);
 }
 
+/// Tests that a `StaticSound` can start looping after playback
+/// has already started.
+#[test]
+#[allow(clippy::float_cmp)]
+fn set_loop_behavior() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((0..10).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new(),
+	};
+	let (mut sound, mut handle) = data.split();
+	sound.on_start_processing();
+	let mut output = vec![];
+	for _ in 0..3 {
+		output.push(sound.process(1.0).left);
+	}
+	handle
+		.set_loop_behavior(LoopBehavior {
+			start_position: 5.0,
+		})
+		.unwrap();
+	sound.on_start_processing();
+	for _ in 0..17 {
+		output.push(sound.process(1.0).left);
+	}
+	let expected: Vec<f32> = (0..10)
+		.chain(5..10)
+		.chain(5..10)
+		.take(20)
+		.map(|i| Frame::from_mono(i as f32).panned(0.5).left)
+		.collect();
+	assert_eq!(output, expected);
+	assert!(!sound.finished());
+	assert_eq!(handle.time_remaining(), None);
+}
+
+/// Tests that when looping is turned on after playback has passed
+/// the loop start, the sound plays to the end before wrapping around.
+#[test]
+#[allow(clippy::float_cmp)]
+fn set_loop_behavior_after_loop_start() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((0..10).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new(),
+	};
+	let (mut sound, mut handle) = data.split();
+	sound.on_start_processing();
+	let mut output = vec![];
+	for _ in 0..6 {
+		output.push(sound.process(1.0).left);
+	}
+	handle
+		.set_loop_behavior(LoopBehavior {
+			start_position: 2.0,
+		})
+		.unwrap();
+	sound.on_start_processing();
+	for _ in 0..10 {
+		output.push(sound.process(1.0).left);
+	}
+	let expected: Vec<f32> = (0..10)
+		.chain(2..8)
+		.map(|i| Frame::from_mono(i as f32).panned(0.5).left)
+		.collect();
+	assert_eq!(output, expected);
+}
+
+/// Tests that a looping `StaticSound` plays to the end and stops
+/// after looping is turned off.
+#[test]
+fn turn_off_looping() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((0..10).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new().loop_behavior(LoopBehavior {
+			start_position: 0.0,
+		}),
+	};
+	let (mut sound, mut handle) = data.split();
+	sound.on_start_processing();
+	for _ in 0..25 {
+		sound.process(1.0);
+	}
+	assert!(!sound.finished());
+	handle.set_loop_behavior(None).unwrap();
+	sound.on_start_processing();
+	for _ in 0..20 {
+		sound.process(1.0);
+	}
+	assert!(sound.finished());
+}
+
+/// Tests that loop start positions outside of the sound are rejected.
+#[test]
+fn set_loop_behavior_out_of_bounds() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((0..10).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new(),
+	};
+	let (_, mut handle) = data.split();
+	for start_position in [-1.0, 10.0] {
+		assert!(matches!(
+			handle.set_loop_behavior(LoopBehavior { start_position }),
+			Err(SetLoopBehaviorError::LoopStartOutOfBounds)
+		));
+	}
+}
+
 /// Tests that a `StaticSound` can seek by an amount of time.
 #[test]
 fn seek_by() {
@@ -819,6 +1375,23 @@ fn seek_by() {
 	expect_frame_soon(Frame::from_mono(20.0).panned(0.5), &mut sound);
 }
 
+/// Tests that a `StaticSound` can seek using `Duration`s.
+#[test]
+fn seek_using_durations() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((0..100).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new().start_position_duration(Duration::from_secs(10)),
+	};
+	let (mut sound, mut handle) = data.split();
+	handle.seek_by_duration(Duration::from_secs(5)).unwrap();
+	sound.on_start_processing();
+	expect_frame_soon(Frame::from_mono(20.0).panned(0.5), &mut sound);
+	handle.seek_to_duration(Duration::from_secs(50)).unwrap();
+	sound.on_start_processing();
+	expect_frame_soon(Frame::from_mono(50.0).panned(0.5), &mut sound);
+}
+
 fn expect_frame_soon(expected_frame: Frame, sound: &mut StaticSound) {
 	const NUM_SAMPLES_TO_WAIT: usize = 10;
 	for _ in 0..NUM_SAMPLES_TO_WAIT {
@@ -832,3 +1405,247 @@ fn expect_frame_soon(expected_frame: Frame, sound: &mut StaticSound) {
 		expected_frame, NUM_SAMPLES_TO_WAIT
 	);
 }
+
+/// Tests that a `StaticSoundHandle` reports how much of the sound
+/// has played and how much time is left.
+#[test]
+#[allow(clippy::float_cmp)]
+fn reports_progress_and_time_remaining() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new().playback_rate(2.0),
+	};
+	let (mut sound, handle) = data.split();
+	sound.on_start_processing();
+	assert_eq!(handle.progress(), 0.0);
+	assert_eq!(handle.time_remaining(), Some(Duration::from_secs(5)));
+	while handle.position() < 5.0 {
+		sound.process(0.5);
+		sound.on_start_processing();
+	}
+	assert_eq!(handle.progress(), 0.5);
+	assert_eq!(handle.time_remaining(), Some(Duration::from_millis(2500)));
+	while !sound.finished() {
+		sound.process(0.5);
+	}
+	sound.on_start_processing();
+	assert_eq!(handle.time_remaining(), Some(Duration::ZERO));
+}
+
+/// Tests that a looping `StaticSound` never reports the time
+/// remaining.
+#[test]
+fn no_time_remaining_when_looping() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new().loop_behavior(LoopBehavior {
+			start_position: 0.0,
+		}),
+	};
+	let (mut sound, handle) = data.split();
+	sound.on_start_processing();
+	assert_eq!(handle.time_remaining(), None);
+}
+
+/// Plays a sine wave at 0.4 times the sample rate of the sound
+/// and returns how loud the mirror image of the sine wave created
+/// by upsampling is compared to the sine wave itself.
+fn upsampling_image_level(resample_quality: ResampleQuality) -> f64 {
+	const SINE_FREQUENCY: f64 = 0.4;
+	const IMAGE_FREQUENCY: f64 = 1.0 - SINE_FREQUENCY;
+	const DT: f64 = 0.45;
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(
+			(0..5000)
+				.map(|i| {
+					Frame::from_mono(
+						(i as f64 * SINE_FREQUENCY * std::f64::consts::TAU).sin() as f32
+					)
+				})
+				.collect(),
+		),
+		settings: StaticSoundSettings::new().resample_quality(resample_quality),
+	};
+	let (mut sound, _) = data.split();
+	let output: Vec<f64> = (0..10000)
+		.map(|_| sound.process(DT).left as f64)
+		// skip the beginning of the sound, where the resample
+		// buffer is still filling up
+		.skip(100)
+		.collect();
+	let magnitude = |frequency: f64| {
+		let (mut real, mut imaginary) = (0.0, 0.0);
+		for (i, sample) in output.iter().enumerate() {
+			let phase = i as f64 * DT * frequency * std::f64::consts::TAU;
+			real += sample * phase.cos();
+			imaginary += sample * phase.sin();
+		}
+		(real * real + imaginary * imaginary).sqrt()
+	};
+	magnitude(IMAGE_FREQUENCY) / magnitude(SINE_FREQUENCY)
+}
+
+/// Tests that sinc resampling creates fewer artifacts than
+/// cubic resampling when upsampling high frequencies.
+#[test]
+fn sinc_resampling_reduces_imaging() {
+	let cubic_image_level = upsampling_image_level(ResampleQuality::Cubic);
+	let sinc_image_level = upsampling_image_level(ResampleQuality::Sinc);
+	assert!(sinc_image_level < cubic_image_level / 5.0);
+}
+
+/// Tests that the number of commands that can be queued for
+/// a sound can be configured.
+#[test]
+fn custom_command_capacity() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new().command_capacity(16),
+	};
+	let (_sound, mut handle) = data.split();
+	for _ in 0..16 {
+		handle.set_volume(0.5, Tween::default()).unwrap();
+	}
+	assert!(matches!(
+		handle.set_volume(0.5, Tween::default()),
+		Err(CommandError::CommandQueueFull)
+	));
+}
+
+/// Tests that a queued sound starts playing right after
+/// the current sound ends, with no gap.
+#[test]
+#[allow(clippy::float_cmp)]
+fn plays_queued_sound_without_gap() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((1..=10).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new(),
+	};
+	let next_data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new((11..=13).map(|i| Frame::from_mono(i as f32)).collect()),
+		settings: StaticSoundSettings::new(),
+	};
+	let (mut sound, mut handle) = data.split();
+	handle.queue_next(next_data).unwrap();
+	sound.on_start_processing();
+
+	for i in 1..=13 {
+		assert_eq!(sound.process(1.0), Frame::from_mono(i as f32).panned(0.5));
+		assert!(!sound.finished());
+	}
+
+	for _ in 0..10 {
+		assert_eq!(sound.process(1.0), Frame::from_mono(0.0).panned(0.5));
+	}
+	assert!(sound.finished());
+	sound.on_start_processing();
+	assert_eq!(handle.time_remaining(), Some(Duration::ZERO));
+}
+
+/// Tests that a loop start that's past the end of a queued sound
+/// that took over before the loop behavior was applied is ignored.
+#[test]
+#[allow(clippy::float_cmp)]
+fn ignores_loop_start_past_end_of_queued_sound() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new(),
+	};
+	let next_data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 3]),
+		settings: StaticSoundSettings::new(),
+	};
+	let (mut sound, mut handle) = data.split();
+	handle.queue_next(next_data).unwrap();
+	sound.on_start_processing();
+	// this loop start is valid for the first sound, but not
+	// for the queued sound that takes over before it's applied
+	handle
+		.set_loop_behavior(LoopBehavior {
+			start_position: 5.0,
+		})
+		.unwrap();
+	// frames are read a few calls ahead of time, so the queued
+	// sound takes over before the first sound is done outputting
+	for _ in 0..9 {
+		sound.process(1.0);
+	}
+	assert_eq!(sound.shared.duration(), 3.0);
+	sound.on_start_processing();
+	for _ in 0..20 {
+		sound.process(1.0);
+	}
+	assert!(sound.finished());
+}
+
+/// Tests that a `StaticSound` sends replaced and finished sound
+/// data back to the handle instead of dropping it.
+#[test]
+fn returns_unused_queued_data() {
+	let data = StaticSoundData {
+		sample_rate: 1,
+		frames: Arc::new(vec![Frame::from_mono(1.0); 10]),
+		settings: StaticSoundSettings::new(),
+	};
+	let (mut sound, mut handle) = data.clone().split();
+	// the second queued sound replaces the first one
+	handle.queue_next(data.clone()).unwrap();
+	handle.queue_next(data.clone()).unwrap();
+	sound.on_start_processing();
+	assert_eq!(handle.unused_data_consumer.len(), 1);
+	for _ in 0..11 {
+		sound.process(1.0);
+	}
+	// the sound that was playing before the transition
+	assert_eq!(handle.unused_data_consumer.len(), 2);
+	assert_eq!(Arc::strong_count(&data.frames), 4);
+	// queueing another sound drops the unused data on this thread
+	handle.queue_next(data.clone()).unwrap();
+	assert_eq!(handle.unused_data_consumer.len(), 0);
+	assert_eq!(Arc::strong_count(&data.frames), 3);
+}
+
+/// Tests that changes to a `StaticSound` are heard after the
+/// number of frames of latency reported by its resample quality.
+#[test]
+fn resample_latency() {
+	for quality in [
+		ResampleQuality::SampleAndHold,
+		ResampleQuality::Linear,
+		ResampleQuality::Cubic,
+		ResampleQuality::Sinc,
+	] {
+		let data = StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new(vec![Frame::from_mono(1.0); 100]),
+			settings: StaticSoundSettings::new().resample_quality(quality),
+		};
+		let (mut sound, mut handle) = data.split();
+		sound.on_start_processing();
+		for _ in 0..20 {
+			assert_ne!(sound.process(1.0), Frame::ZERO);
+		}
+		handle
+			.set_volume(
+				Volume::Amplitude(0.0),
+				Tween {
+					duration: Duration::ZERO,
+					..Default::default()
+				},
+			)
+			.unwrap();
+		sound.on_start_processing();
+		let latency = (0..20)
+			.take_while(|_| sound.process(1.0) != Frame::ZERO)
+			.count();
+		assert_eq!(latency, quality.latency_frames(), "{:?}", quality);
+	}
+}
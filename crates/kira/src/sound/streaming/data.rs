@@ -5,7 +5,9 @@ use std::path::Path;
 use crate::sound::FromFileError;
 use crate::sound::SoundData;
 use ringbuf::RingBuffer;
+use symphonia::core::io::MediaSource;
 
+use super::decoder::prebuffered::PrebufferedDecoder;
 use super::decoder::symphonia::SymphoniaDecoder;
 use super::{StreamingSoundHandle, StreamingSoundSettings};
 
@@ -46,9 +48,56 @@ impl StreamingSoundData<FromFileError> {
 			settings,
 		})
 	}
+
+	/// Creates a [`StreamingSoundData`] for any source of audio file data,
+	/// such as a file inside an archive or a custom virtual file system.
+	///
+	/// Seeking (including looping) requires the source to be seekable,
+	/// as reported by [`MediaSource::is_seekable`]. If the source isn't
+	/// seekable, seeking will stop decoding and report a
+	/// [`FromFileError::SymphoniaError`] via
+	/// [`StreamingSoundHandle::pop_error`].
+	pub fn from_media_source(
+		media_source: impl MediaSource + 'static,
+		settings: StreamingSoundSettings,
+	) -> Result<StreamingSoundData<FromFileError>, FromFileError> {
+		Ok(StreamingSoundData {
+			decoder: Box::new(SymphoniaDecoder::new(Box::new(media_source))?),
+			settings,
+		})
+	}
 }
 
 impl<Error: Send + 'static> StreamingSoundData<Error> {
+	/// Decodes the beginning of the sound (starting from the
+	/// [`start_position`](StreamingSoundSettings::start_position))
+	/// ahead of time so playback can start without waiting for
+	/// the decode thread.
+	///
+	/// Up to [`buffer_size`](StreamingSoundSettings::buffer_size)
+	/// frames are decoded on the current thread, so this blocks for
+	/// as long as decoding that much audio takes, and the decoded
+	/// audio is kept in memory (16 bytes per frame, or 256KB for the
+	/// default buffer size) until the sound is played. The settings
+	/// should be set before calling this, since changing the start
+	/// position afterwards discards the prebuffered audio.
+	///
+	/// The decode thread isn't started until the sound is played,
+	/// so dropping a prebuffered sound without playing it just frees
+	/// the decoded audio.
+	pub fn prebuffer(self) -> Result<Self, Error> {
+		let index =
+			(self.settings.start_position * self.decoder.sample_rate() as f64).round() as u64;
+		Ok(Self {
+			decoder: Box::new(PrebufferedDecoder::new(
+				self.decoder,
+				index,
+				self.settings.buffer_size,
+			)?),
+			settings: self.settings,
+		})
+	}
+
 	pub(crate) fn split(
 		self,
 	) -> Result<
@@ -75,6 +124,7 @@ impl<Error: Send + 'static> StreamingSoundData<Error> {
 			shared: sound.shared(),
 			command_producer,
 			error_consumer,
+			seek_generation: 0,
 		};
 		Ok((sound, handle, scheduler))
 	}
@@ -1,3 +1,4 @@
+pub(crate) mod prebuffered;
 pub(crate) mod symphonia;
 
 use std::collections::VecDeque;
@@ -12,4 +13,11 @@ pub(crate) trait Decoder: Send {
 	fn decode(&mut self, frames: &mut VecDeque<Frame>) -> Result<bool, Self::Error>;
 
 	fn seek(&mut self, index: u64) -> Result<u64, Self::Error>;
+
+	/// Returns `true` if the decoder has frames that were decoded
+	/// ahead of time and can be returned by [`decode`](Decoder::decode)
+	/// without doing any more work.
+	fn has_prebuffered_frames(&self) -> bool {
+		false
+	}
 }
@@ -0,0 +1,70 @@
+use std::collections::VecDeque;
+
+use crate::dsp::Frame;
+
+use super::Decoder;
+
+/// Wraps a decoder with frames that were decoded ahead of time,
+/// starting at the sound's start position.
+pub(crate) struct PrebufferedDecoder<Error> {
+	decoder: Box<dyn Decoder<Error = Error>>,
+	/// The index that was requested when the frames were decoded.
+	requested_index: u64,
+	/// The index of the first prebuffered frame.
+	start_index: u64,
+	frames: VecDeque<Frame>,
+}
+
+impl<Error> PrebufferedDecoder<Error> {
+	/// Decodes at least `num_frames` frames (or until the end of the
+	/// audio) starting at `index`.
+	pub fn new(
+		mut decoder: Box<dyn Decoder<Error = Error>>,
+		index: u64,
+		num_frames: usize,
+	) -> Result<Self, Error> {
+		let start_index = decoder.seek(index)?;
+		let mut frames = VecDeque::with_capacity(num_frames);
+		while frames.len() < num_frames {
+			if decoder.decode(&mut frames)? {
+				break;
+			}
+		}
+		Ok(Self {
+			decoder,
+			requested_index: index,
+			start_index,
+			frames,
+		})
+	}
+}
+
+impl<Error> Decoder for PrebufferedDecoder<Error> {
+	type Error = Error;
+
+	fn sample_rate(&self) -> u32 {
+		self.decoder.sample_rate()
+	}
+
+	fn decode(&mut self, frames: &mut VecDeque<Frame>) -> Result<bool, Self::Error> {
+		if self.frames.is_empty() {
+			return self.decoder.decode(frames);
+		}
+		frames.append(&mut self.frames);
+		Ok(false)
+	}
+
+	fn seek(&mut self, index: u64) -> Result<u64, Self::Error> {
+		// seeking to the position the frames were decoded from
+		// keeps them, so they're ready as soon as playback starts
+		if !self.frames.is_empty() && index == self.requested_index {
+			return Ok(self.start_index);
+		}
+		self.frames.clear();
+		self.decoder.seek(index)
+	}
+
+	fn has_prebuffered_frames(&self) -> bool {
+		!self.frames.is_empty()
+	}
+}
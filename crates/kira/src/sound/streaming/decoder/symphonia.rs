@@ -5,6 +5,7 @@ use symphonia::core::{
 	audio::{AudioBuffer, AudioBufferRef, Signal},
 	codecs::Decoder,
 	conv::{FromSample, IntoSample},
+	errors::{Error, SeekErrorKind},
 	formats::{FormatReader, SeekMode, SeekTo},
 	io::{MediaSource, MediaSourceStream},
 	probe::Hint,
@@ -16,12 +17,15 @@ pub(crate) struct SymphoniaDecoder {
 	decoder: Box<dyn Decoder>,
 	sample_rate: u32,
 	track_id: u32,
+	seekable: bool,
+	started: bool,
 }
 
 impl SymphoniaDecoder {
 	pub(crate) fn new(media_source: Box<dyn MediaSource>) -> Result<Self, FromFileError> {
 		let codecs = symphonia::default::get_codecs();
 		let probe = symphonia::default::get_probe();
+		let seekable = media_source.is_seekable();
 		let mss = MediaSourceStream::new(media_source, Default::default());
 		let format_reader = probe
 			.format(
@@ -45,6 +49,8 @@ impl SymphoniaDecoder {
 			decoder,
 			sample_rate,
 			track_id,
+			seekable,
+			started: false,
 		})
 	}
 }
@@ -57,17 +63,18 @@ impl super::Decoder for SymphoniaDecoder {
 	}
 
 	fn decode(&mut self, frames: &mut VecDeque<Frame>) -> Result<bool, Self::Error> {
+		self.started = true;
 		match self.format_reader.next_packet() {
 			Ok(packet) => {
 				let buffer = self.decoder.decode(&packet)?;
 				load_frames_from_buffer_ref(frames, &buffer)?;
 			}
 			Err(error) => match error {
-				symphonia::core::errors::Error::IoError(error) => {
+				Error::IoError(error) => {
 					if error.kind() == std::io::ErrorKind::UnexpectedEof {
 						return Ok(true);
 					}
-					return Err(symphonia::core::errors::Error::IoError(error).into());
+					return Err(Error::IoError(error).into());
 				}
 				error => return Err(error.into()),
 			},
@@ -76,6 +83,15 @@ impl super::Decoder for SymphoniaDecoder {
 	}
 
 	fn seek(&mut self, index: u64) -> Result<u64, Self::Error> {
+		if !self.seekable {
+			// the scheduler always seeks to the start position when
+			// it's created, which we can allow for unseekable sources
+			// as long as nothing has been decoded yet
+			if index == 0 && !self.started {
+				return Ok(0);
+			}
+			return Err(Error::SeekError(SeekErrorKind::Unseekable).into());
+		}
 		let seeked_to = self.format_reader.seek(
 			SeekMode::Accurate,
 			SeekTo::TimeStamp {
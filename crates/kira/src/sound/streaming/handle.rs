@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use crate::{sound::static_sound::PlaybackState, tween::Tween, CommandError, PlaybackRate, Volume};
 use ringbuf::{Consumer, Producer};
@@ -10,6 +10,8 @@ pub struct StreamingSoundHandle<Error> {
 	pub(crate) shared: Arc<Shared>,
 	pub(crate) command_producer: Producer<Command>,
 	pub(crate) error_consumer: Consumer<Error>,
+	/// The number of seeks that have been requested.
+	pub(crate) seek_generation: u64,
 }
 
 impl<Error> StreamingSoundHandle<Error> {
@@ -18,20 +20,37 @@ impl<Error> StreamingSoundHandle<Error> {
 		self.shared.state()
 	}
 
+	/// Returns `false` if the sound has stopped and will be
+	/// (or already has been) removed from the audio renderer.
+	///
+	/// This is only advisory: the sound may finish right after
+	/// this returns `true`.
+	pub fn is_alive(&self) -> bool {
+		self.shared.state() != PlaybackState::Stopped
+	}
+
 	/// Returns the current playback position of the sound (in seconds).
 	pub fn position(&self) -> f64 {
 		self.shared.position()
 	}
 
+	/// Returns the number of times playback has stalled because
+	/// the decoder couldn't provide audio data in time.
+	///
+	/// This count only ever increases. Each stall is counted once,
+	/// no matter how long it lasts, and waiting for the first
+	/// audio data when the sound starts is not counted.
+	pub fn underrun_count(&self) -> u64 {
+		self.shared.underrun_count()
+	}
+
 	/// Sets the volume of the sound (as a factor of the original volume).
 	pub fn set_volume(
 		&mut self,
 		volume: impl Into<Volume>,
 		tween: Tween,
 	) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetVolume(volume.into(), tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetVolume(volume.into(), tween))
 	}
 
 	/// Sets the playback rate of the sound.
@@ -43,33 +62,25 @@ impl<Error> StreamingSoundHandle<Error> {
 		playback_rate: impl Into<PlaybackRate>,
 		tween: Tween,
 	) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetPlaybackRate(playback_rate.into(), tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetPlaybackRate(playback_rate.into(), tween))
 	}
 
 	/// Sets the panning of the sound, where `0.0` is hard left,
 	/// `0.5` is center, and `1.0` is hard right.
 	pub fn set_panning(&mut self, panning: f64, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetPanning(panning, tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetPanning(panning, tween))
 	}
 
 	/// Fades out the sound to silence with the given tween and then
 	/// pauses playback.
 	pub fn pause(&mut self, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::Pause(tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::Pause(tween))
 	}
 
 	/// Resumes playback and fades in the sound from silence
 	/// with the given tween.
 	pub fn resume(&mut self, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::Resume(tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::Resume(tween))
 	}
 
 	/// Fades out the sound to silence with the given tween and then
@@ -77,27 +88,73 @@ impl<Error> StreamingSoundHandle<Error> {
 	///
 	/// Once the sound is stopped, it cannot be restarted.
 	pub fn stop(&mut self, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::Stop(tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::Stop(tween))
 	}
 
 	/// Sets the playback position to the specified time in seconds.
+	///
+	/// The seek happens on the decoding thread, so it may take a
+	/// moment to complete. Use [`is_seeking`](Self::is_seeking) to
+	/// check whether it has.
 	pub fn seek_to(&mut self, position: f64) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SeekTo(position))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SeekTo(position))?;
+		self.seek_generation += 1;
+		Ok(())
 	}
 
 	/// Moves the playback position by the specified amount of time in seconds.
+	///
+	/// The seek happens on the decoding thread, so it may take a
+	/// moment to complete. Use [`is_seeking`](Self::is_seeking) to
+	/// check whether it has.
 	pub fn seek_by(&mut self, amount: f64) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SeekBy(amount))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SeekBy(amount))?;
+		self.seek_generation += 1;
+		Ok(())
+	}
+
+	/// Sets the playback position to the specified time.
+	pub fn seek_to_duration(&mut self, position: Duration) -> Result<(), CommandError> {
+		self.seek_to(position.as_secs_f64())
+	}
+
+	/// Moves the playback position forward by the specified amount of time.
+	///
+	/// To move the playback position backward, use
+	/// [`seek_by`](Self::seek_by) with a negative amount.
+	pub fn seek_by_duration(&mut self, amount: Duration) -> Result<(), CommandError> {
+		self.seek_by(amount.as_secs_f64())
+	}
+
+	/// Returns `true` if a seek requested with this handle hasn't
+	/// been completed by the decoder yet.
+	///
+	/// This is useful for showing a loading indicator while the
+	/// decoder jumps to the new position. Once the seek is complete,
+	/// any audio that was already buffered from the old position
+	/// still plays (at most
+	/// [`buffer_size`](super::StreamingSoundSettings::buffer_size)
+	/// frames) before the new audio is heard and
+	/// [`position`](Self::position) reflects the new location.
+	/// If the sound stops before the seek is complete, this will
+	/// keep returning `true`.
+	pub fn is_seeking(&self) -> bool {
+		self.shared.completed_seek_generation() < self.seek_generation
 	}
 
 	/// Returns an error that occurred while decoding audio, if any.
 	pub fn pop_error(&mut self) -> Option<Error> {
 		self.error_consumer.pop()
 	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		// a stopped sound is removed from the renderer, so there's
+		// nothing left to receive the command
+		if !self.is_alive() {
+			return Err(CommandError::ResourceRemoved);
+		}
+		self.command_producer
+			.push(command)
+			.map_err(|_| CommandError::CommandQueueFull)
+	}
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::{track::TrackId, tween::Tween, LoopBehavior, PlaybackRate, StartTime, Volume};
 
 /// Settings for a streaming sound.
@@ -24,6 +26,37 @@ pub struct StreamingSoundSettings {
 	pub track: TrackId,
 	/// An optional fade-in from silence.
 	pub fade_in_tween: Option<Tween>,
+	/// The maximum number of frames that will be decoded
+	/// ahead of the current playback position.
+	///
+	/// A larger buffer gives the decoder more leeway when the
+	/// file can't be read fast enough (for example, on slow
+	/// storage), at the cost of memory (16 bytes per frame).
+	/// The buffer size does not affect how long it takes for
+	/// playback to start, since playback starts as soon as the
+	/// first frames are decoded.
+	///
+	/// Values smaller than 4 will be treated as 4.
+	///
+	/// The default buffer size is 16,384 frames.
+	pub buffer_size: usize,
+	/// How long the decoding thread sleeps when the buffer is full
+	/// before checking whether there's room for more frames.
+	///
+	/// A shorter interval refills the buffer and responds to seeks
+	/// sooner, at the cost of waking the thread up more often. A
+	/// duration of zero makes the thread yield to other threads
+	/// instead of sleeping, which uses the most CPU time. If
+	/// [`StreamingSoundHandle::underrun_count`](super::StreamingSoundHandle::underrun_count)
+	/// goes up while the game is busy, try a shorter interval or a
+	/// larger [`buffer_size`](Self::buffer_size).
+	///
+	/// The operating system may sleep for longer than requested.
+	/// The priority of the decoding thread can't be changed, since
+	/// the standard library has no cross-platform way to do so.
+	///
+	/// The default interval is 1 millisecond.
+	pub decoder_sleep_duration: Duration,
 }
 
 impl StreamingSoundSettings {
@@ -38,6 +71,8 @@ impl StreamingSoundSettings {
 			loop_behavior: None,
 			track: TrackId::Main,
 			fade_in_tween: None,
+			buffer_size: 16_384,
+			decoder_sleep_duration: Duration::from_millis(1),
 		}
 	}
 
@@ -105,6 +140,24 @@ impl StreamingSoundSettings {
 			..self
 		}
 	}
+
+	/// Sets the maximum number of frames that will be decoded
+	/// ahead of the current playback position.
+	pub fn buffer_size(self, buffer_size: usize) -> Self {
+		Self {
+			buffer_size,
+			..self
+		}
+	}
+
+	/// Sets how long the decoding thread sleeps when the
+	/// buffer is full.
+	pub fn decoder_sleep_duration(self, decoder_sleep_duration: Duration) -> Self {
+		Self {
+			decoder_sleep_duration,
+			..self
+		}
+	}
 }
 
 impl Default for StreamingSoundSettings {
@@ -12,6 +12,7 @@ use crate::{
 	clock::ClockTime,
 	dsp::{interpolate_frame, Frame},
 	sound::{static_sound::PlaybackState, Sound},
+	start_time::count_down_delay,
 	track::TrackId,
 	tween::{Tween, Tweener},
 	PlaybackRate, StartTime, Volume,
@@ -25,6 +26,8 @@ use self::decode_scheduler::{DecodeScheduler, DecodeSchedulerController};
 pub(crate) struct Shared {
 	state: AtomicU8,
 	position: AtomicU64,
+	underrun_count: AtomicU64,
+	completed_seek_generation: Arc<AtomicU64>,
 }
 
 impl Shared {
@@ -42,6 +45,14 @@ impl Shared {
 	pub fn position(&self) -> f64 {
 		f64::from_bits(self.position.load(Ordering::SeqCst))
 	}
+
+	pub fn underrun_count(&self) -> u64 {
+		self.underrun_count.load(Ordering::SeqCst)
+	}
+
+	pub fn completed_seek_generation(&self) -> u64 {
+		self.completed_seek_generation.load(Ordering::SeqCst)
+	}
 }
 
 pub(crate) struct StreamingSound {
@@ -50,6 +61,9 @@ pub(crate) struct StreamingSound {
 	scheduler_controller: DecodeSchedulerController,
 	track: TrackId,
 	start_time: StartTime,
+	/// The time (in seconds) left before the sound starts
+	/// if it was started with a [`StartTime::Delay`].
+	remaining_start_delay: f64,
 	state: PlaybackState,
 	volume_fade: Tweener<Volume>,
 	current_frame: u64,
@@ -58,6 +72,7 @@ pub(crate) struct StreamingSound {
 	playback_rate: Tweener<PlaybackRate>,
 	panning: Tweener,
 	shared: Arc<Shared>,
+	waiting_for_frames: bool,
 }
 
 impl StreamingSound {
@@ -76,10 +91,11 @@ impl StreamingSound {
 			scheduler_controller,
 			track: settings.track,
 			start_time: settings.start_time,
+			remaining_start_delay: settings.start_time.delay().unwrap_or(0.0),
 			state: PlaybackState::Playing,
 			volume_fade: if let Some(tween) = settings.fade_in_tween {
-				let mut tweenable = Tweener::new(Volume::Decibels(Volume::MIN_DECIBELS));
-				tweenable.set(Volume::Decibels(0.0), tween);
+				let mut tweenable = Tweener::new(tween.fade_curve.silent_volume());
+				tweenable.set(tween.fade_curve.full_volume(), tween);
 				tweenable
 			} else {
 				Tweener::new(Volume::Decibels(0.0))
@@ -92,7 +108,12 @@ impl StreamingSound {
 			shared: Arc::new(Shared {
 				position: AtomicU64::new(start_position.to_bits()),
 				state: AtomicU8::new(PlaybackState::Playing as u8),
+				underrun_count: AtomicU64::new(0),
+				completed_seek_generation: scheduler.completed_seek_generation(),
 			}),
+			// the decoder hasn't had a chance to fill the buffer yet,
+			// so waiting at the very start isn't an underrun
+			waiting_for_frames: true,
 		}
 	}
 
@@ -132,23 +153,6 @@ impl StreamingSound {
 		(self.current_frame as f64 + self.fractional_position) / self.sample_rate as f64
 	}
 
-	fn pause(&mut self, tween: Tween) {
-		self.set_state(PlaybackState::Pausing);
-		self.volume_fade
-			.set(Volume::Decibels(Volume::MIN_DECIBELS), tween);
-	}
-
-	fn resume(&mut self, tween: Tween) {
-		self.set_state(PlaybackState::Playing);
-		self.volume_fade.set(Volume::Decibels(0.0), tween);
-	}
-
-	fn stop(&mut self, tween: Tween) {
-		self.set_state(PlaybackState::Stopping);
-		self.volume_fade
-			.set(Volume::Decibels(Volume::MIN_DECIBELS), tween);
-	}
-
 	fn seek_to_index(&mut self, index: u64) {
 		self.scheduler_controller.seek(index);
 	}
@@ -202,6 +206,12 @@ impl Sound for StreamingSound {
 		if matches!(self.start_time, StartTime::ClockTime(..)) {
 			return Frame::ZERO;
 		}
+		// if the delay ends partway through this frame, only advance
+		// the playback position by the time after the delay
+		let playback_dt = match count_down_delay(&mut self.remaining_start_delay, dt) {
+			Some(playback_dt) => playback_dt,
+			None => return Frame::ZERO,
+		};
 		if matches!(self.state, PlaybackState::Paused | PlaybackState::Stopped) {
 			return Frame::ZERO;
 		}
@@ -211,8 +221,13 @@ impl Sound for StreamingSound {
 		if self.scheduler_controller.frame_consumer_mut().len() < 2
 			&& !self.scheduler_controller.finished()
 		{
+			if !self.waiting_for_frames {
+				self.waiting_for_frames = true;
+				self.shared.underrun_count.fetch_add(1, Ordering::SeqCst);
+			}
 			return Frame::ZERO;
 		}
+		self.waiting_for_frames = false;
 		let next_frames = self.next_frames();
 		let out = interpolate_frame(
 			next_frames[0],
@@ -222,7 +237,7 @@ impl Sound for StreamingSound {
 			self.fractional_position as f32,
 		);
 		self.fractional_position +=
-			self.sample_rate as f64 * self.playback_rate.value().as_factor() * dt;
+			self.sample_rate as f64 * self.playback_rate.value().as_factor() * playback_dt;
 		while self.fractional_position >= 1.0 {
 			self.fractional_position -= 1.0;
 			self.scheduler_controller.frame_consumer_mut().pop();
@@ -249,6 +264,31 @@ impl Sound for StreamingSound {
 		}
 	}
 
+	fn pause(&mut self, tween: Tween) {
+		// a sound that's stopping can't be paused, or it
+		// would never finish
+		if matches!(self.state, PlaybackState::Stopping | PlaybackState::Stopped) {
+			return;
+		}
+		self.set_state(PlaybackState::Pausing);
+		self.volume_fade
+			.set(tween.fade_curve.silent_volume(), tween);
+	}
+
+	fn resume(&mut self, tween: Tween) {
+		if matches!(self.state, PlaybackState::Stopping | PlaybackState::Stopped) {
+			return;
+		}
+		self.set_state(PlaybackState::Playing);
+		self.volume_fade.set(tween.fade_curve.full_volume(), tween);
+	}
+
+	fn stop(&mut self, tween: Tween) {
+		self.set_state(PlaybackState::Stopping);
+		self.volume_fade
+			.set(tween.fade_curve.silent_volume(), tween);
+	}
+
 	fn finished(&self) -> bool {
 		self.state == PlaybackState::Stopped
 	}
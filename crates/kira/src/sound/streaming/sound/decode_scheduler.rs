@@ -14,9 +14,7 @@ use crate::{
 };
 use ringbuf::{Consumer, Producer, RingBuffer};
 
-const BUFFER_SIZE: usize = 16_384;
-const SEEK_DESTINATION_NONE: u64 = u64::MAX;
-const DECODER_THREAD_SLEEP_DURATION: Duration = Duration::from_millis(1);
+const MIN_BUFFER_SIZE: usize = 4;
 
 pub(crate) enum NextStep {
 	Continue,
@@ -27,6 +25,9 @@ pub(crate) enum NextStep {
 pub(crate) struct DecodeSchedulerController {
 	frame_consumer: Consumer<(u64, Frame)>,
 	seek_destination_sender: Arc<AtomicU64>,
+	/// Incremented every time a seek is requested.
+	seek_generation: u64,
+	seek_generation_sender: Arc<AtomicU64>,
 	stopped_signal_sender: Arc<AtomicBool>,
 	finished_signal_receiver: Arc<AtomicBool>,
 }
@@ -36,8 +37,14 @@ impl DecodeSchedulerController {
 		&mut self.frame_consumer
 	}
 
-	pub fn seek(&self, index: u64) {
+	pub fn seek(&mut self, index: u64) {
+		// the destination is stored before the generation, so once the
+		// decode thread sees a new generation, the destination is at
+		// least as new as the seek that generation belongs to
+		self.seek_generation += 1;
 		self.seek_destination_sender.store(index, Ordering::SeqCst);
+		self.seek_generation_sender
+			.store(self.seek_generation, Ordering::SeqCst);
 	}
 
 	pub fn finished(&self) -> bool {
@@ -57,10 +64,14 @@ pub(crate) struct DecodeScheduler<Error: Send + 'static> {
 	loop_behavior: Option<LoopBehavior>,
 	frame_producer: Producer<(u64, Frame)>,
 	seek_destination_receiver: Arc<AtomicU64>,
+	seek_generation_receiver: Arc<AtomicU64>,
+	/// The generation of the last seek the decoder performed.
+	completed_seek_generation: Arc<AtomicU64>,
 	stopped_signal_receiver: Arc<AtomicBool>,
 	finished_signal_sender: Arc<AtomicBool>,
 	decoded_frames: VecDeque<Frame>,
 	current_frame: u64,
+	sleep_duration: Duration,
 	error_producer: Producer<Error>,
 }
 
@@ -70,14 +81,17 @@ impl<Error: Send + 'static> DecodeScheduler<Error> {
 		settings: StreamingSoundSettings,
 		error_producer: Producer<Error>,
 	) -> Result<(Self, DecodeSchedulerController), Error> {
-		let (mut frame_producer, frame_consumer) = RingBuffer::new(BUFFER_SIZE).split();
+		let (mut frame_producer, frame_consumer) =
+			RingBuffer::new(settings.buffer_size.max(MIN_BUFFER_SIZE)).split();
 		// pre-seed the frame ringbuffer with a zero frame. this is the "previous" frame
 		// when the sound just started.
 		frame_producer
 			.push((0, Frame::ZERO))
 			.expect("The frame producer shouldn't be full because we just created it");
-		let seek_destination_sender = Arc::new(AtomicU64::new(SEEK_DESTINATION_NONE));
+		let seek_destination_sender = Arc::new(AtomicU64::new(0));
 		let seek_destination_receiver = seek_destination_sender.clone();
+		let seek_generation_sender = Arc::new(AtomicU64::new(0));
+		let seek_generation_receiver = seek_generation_sender.clone();
 		let stopped_signal_sender = Arc::new(AtomicBool::new(false));
 		let stopped_signal_receiver = stopped_signal_sender.clone();
 		let finished_signal_sender = Arc::new(AtomicBool::new(false));
@@ -89,16 +103,28 @@ impl<Error: Send + 'static> DecodeScheduler<Error> {
 			loop_behavior: settings.loop_behavior,
 			frame_producer,
 			seek_destination_receiver,
+			seek_generation_receiver,
+			completed_seek_generation: Arc::new(AtomicU64::new(0)),
 			stopped_signal_receiver,
 			finished_signal_sender,
 			decoded_frames: VecDeque::new(),
 			current_frame: 0,
+			sleep_duration: settings.decoder_sleep_duration,
 			error_producer,
 		};
 		scheduler.seek(settings.start_position)?;
+		// move any frames that were decoded ahead of time into the
+		// ringbuffer now so the sound can start playing right away
+		while (scheduler.decoder.has_prebuffered_frames() || !scheduler.decoded_frames.is_empty())
+			&& !scheduler.frame_producer.is_full()
+		{
+			scheduler.run()?;
+		}
 		let controller = DecodeSchedulerController {
 			frame_consumer,
 			seek_destination_sender,
+			seek_generation: 0,
+			seek_generation_sender,
 			stopped_signal_sender,
 			finished_signal_receiver,
 		};
@@ -109,12 +135,22 @@ impl<Error: Send + 'static> DecodeScheduler<Error> {
 		self.current_frame
 	}
 
+	pub fn completed_seek_generation(&self) -> Arc<AtomicU64> {
+		self.completed_seek_generation.clone()
+	}
+
 	pub fn start(mut self) {
 		std::thread::spawn(move || loop {
 			match self.run() {
 				Ok(result) => match result {
 					NextStep::Continue => {}
-					NextStep::Wait => std::thread::sleep(DECODER_THREAD_SLEEP_DURATION),
+					NextStep::Wait => {
+						if self.sleep_duration.is_zero() {
+							std::thread::yield_now();
+						} else {
+							std::thread::sleep(self.sleep_duration);
+						}
+					}
 					NextStep::End => break,
 				},
 				Err(error) => {
@@ -130,17 +166,20 @@ impl<Error: Send + 'static> DecodeScheduler<Error> {
 		if self.stopped_signal_receiver.load(Ordering::SeqCst) {
 			return Ok(NextStep::End);
 		}
+		// check for seek commands
+		let seek_generation = self.seek_generation_receiver.load(Ordering::SeqCst);
+		if seek_generation != self.completed_seek_generation.load(Ordering::SeqCst) {
+			let seek_destination = self.seek_destination_receiver.load(Ordering::SeqCst);
+			self.current_frame = self.decoder.seek(seek_destination)?;
+			// frames decoded before the seek are from the old position
+			self.decoded_frames.clear();
+			self.completed_seek_generation
+				.store(seek_generation, Ordering::SeqCst);
+		}
 		// if the frame ringbuffer is full, sleep for a bit
 		if self.frame_producer.is_full() {
 			return Ok(NextStep::Wait);
 		}
-		// check for seek commands
-		let seek_destination = self.seek_destination_receiver.load(Ordering::SeqCst);
-		if seek_destination != SEEK_DESTINATION_NONE {
-			self.current_frame = self.decoder.seek(seek_destination)?;
-			self.seek_destination_receiver
-				.store(SEEK_DESTINATION_NONE, Ordering::SeqCst);
-		}
 		// if we have leftover frames from the last decode, push
 		// those first
 		if let Some(frame) = self.decoded_frames.pop_front() {
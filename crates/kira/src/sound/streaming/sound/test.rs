@@ -131,6 +131,79 @@ fn waits_for_samples() {
 	}
 }
 
+/// Tests that a `StreamingSound` counts the number of times
+/// playback stalls waiting for the decoder.
+#[test]
+fn reports_underruns() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(
+			(1..=10).map(|i| Frame::from_mono(i as f32)).collect(),
+		)),
+		settings: StreamingSoundSettings::default(),
+	};
+	let (mut sound, handle, mut scheduler) = data.split().unwrap();
+
+	// waiting for the first frames is not an underrun
+	for _ in 0..3 {
+		sound.process(1.0);
+		sound.on_start_processing();
+	}
+	assert_eq!(handle.underrun_count(), 0);
+
+	for _ in 0..4 {
+		scheduler.run().unwrap();
+	}
+	for _ in 0..3 {
+		sound.process(1.0);
+		sound.on_start_processing();
+	}
+	assert_eq!(handle.underrun_count(), 0);
+
+	// a stall is only counted once, however long it lasts
+	for _ in 0..3 {
+		sound.process(1.0);
+		sound.on_start_processing();
+	}
+	assert_eq!(handle.underrun_count(), 1);
+
+	for _ in 0..4 {
+		scheduler.run().unwrap();
+	}
+	for _ in 0..4 {
+		sound.process(1.0);
+		sound.on_start_processing();
+	}
+	assert_eq!(handle.underrun_count(), 2);
+}
+
+/// Tests that the decode scheduler stops decoding ahead once
+/// the frame buffer is full.
+#[test]
+#[allow(clippy::float_cmp)]
+fn respects_buffer_size() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(
+			(1..=10).map(|i| Frame::from_mono(i as f32)).collect(),
+		)),
+		// buffer sizes below the minimum are clamped to 4 frames,
+		// one of which is taken by the pre-seeded "previous" frame
+		settings: StreamingSoundSettings::new().buffer_size(0),
+	};
+	let (mut sound, _handle, mut scheduler) = data.split().unwrap();
+
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	assert!(matches!(scheduler.run().unwrap(), NextStep::Wait));
+	for i in 1..=3 {
+		assert_eq!(sound.process(1.0), Frame::from_mono(i as f32).panned(0.5));
+		sound.on_start_processing();
+	}
+	assert_eq!(sound.process(1.0), Frame::ZERO.panned(0.5));
+	sound.on_start_processing();
+
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	assert_eq!(sound.process(1.0), Frame::from_mono(4.0).panned(0.5));
+}
+
 /// Tests that a `StreamingSound` correctly reports its playback state
 /// to be queried by StreamingSoundHandle::state.
 #[test]
@@ -437,6 +510,25 @@ fn waits_for_start_time() {
 	}
 }
 
+/// Tests that a `StreamingSound` with a delayed start time starts
+/// playing on the exact sample the delay ends.
+#[test]
+fn waits_for_start_delay() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(
+			(1..100).map(|i| Frame::from_mono(i as f32)).collect(),
+		)),
+		settings: StreamingSoundSettings::new().start_time(Duration::from_secs(3)),
+	};
+	let (mut sound, _, mut scheduler) = data.split().unwrap();
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+
+	let first_nonzero_frame = (0..10)
+		.map(|_| sound.process(1.0))
+		.position(|frame| frame != Frame::ZERO);
+	assert_eq!(first_nonzero_frame, Some(3));
+}
+
 /// Tests that a `StreamingSound` can be paused and resumed immediately
 /// even if playback is waiting for a clock time to start.
 #[test]
@@ -852,6 +944,51 @@ fn seek_by() {
 	expect_frame_soon(Frame::from_mono(20.0).panned(0.5), &mut sound);
 }
 
+/// Tests that a `StreamingSoundHandle` reports when a seek
+/// has been completed by the decoder.
+#[test]
+#[allow(clippy::float_cmp)]
+fn reports_seek_completion() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(
+			(0..100).map(|i| Frame::from_mono(i as f32)).collect(),
+		)),
+		settings: StreamingSoundSettings::new(),
+	};
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+	assert!(!handle.is_seeking());
+
+	handle.seek_to_duration(Duration::from_secs(15)).unwrap();
+	assert!(handle.is_seeking());
+	sound.on_start_processing();
+	assert!(handle.is_seeking());
+	scheduler.run().unwrap();
+	assert!(!handle.is_seeking());
+
+	while matches!(scheduler.run().unwrap(), NextStep::Continue) {}
+	expect_frame_soon(Frame::from_mono(15.0).panned(0.5), &mut sound);
+	sound.on_start_processing();
+	assert_eq!(handle.position(), 16.0);
+}
+
+/// Tests that a prebuffered `StreamingSound` can start playing
+/// before the decode thread has run.
+#[test]
+fn prebuffer() {
+	let data = StreamingSoundData {
+		decoder: Box::new(MockDecoder::new(
+			(1..=10).map(|i| Frame::from_mono(i as f32)).collect(),
+		)),
+		settings: StreamingSoundSettings::new().start_position(3.0),
+	}
+	.prebuffer()
+	.unwrap();
+	let (mut sound, _, _) = data.split().unwrap();
+	for i in 4..=10 {
+		assert_eq!(sound.process(1.0), Frame::from_mono(i as f32).panned(0.5));
+	}
+}
+
 fn expect_frame_soon(expected_frame: Frame, sound: &mut StreamingSound) {
 	const NUM_SAMPLES_TO_WAIT: usize = 10;
 	for _ in 0..NUM_SAMPLES_TO_WAIT {
@@ -865,3 +1002,42 @@ fn expect_frame_soon(expected_frame: Frame, sound: &mut StreamingSound) {
 		expected_frame, NUM_SAMPLES_TO_WAIT
 	);
 }
+
+/// Tests that a `StreamingSound` can play from a source that
+/// doesn't support seeking, and reports an error if a seek
+/// is requested.
+#[test]
+#[cfg(feature = "ogg")]
+fn reports_error_when_seeking_unseekable_source() {
+	use symphonia::core::{
+		errors::{Error, SeekErrorKind},
+		io::ReadOnlySource,
+	};
+
+	use crate::sound::FromFileError;
+
+	let file = std::fs::File::open(concat!(
+		env!("CARGO_MANIFEST_DIR"),
+		"/../examples/assets/blip.ogg"
+	))
+	.unwrap();
+	let data = StreamingSoundData::from_media_source(
+		ReadOnlySource::new(file),
+		StreamingSoundSettings::new(),
+	)
+	.unwrap();
+	let (mut sound, mut handle, mut scheduler) = data.split().unwrap();
+
+	for _ in 0..10 {
+		assert!(matches!(scheduler.run(), Ok(NextStep::Continue)));
+	}
+
+	handle.seek_to(0.0).unwrap();
+	sound.on_start_processing();
+	assert!(matches!(
+		scheduler.run(),
+		Err(FromFileError::SymphoniaError(Error::SeekError(
+			SeekErrorKind::Unseekable
+		)))
+	));
+}
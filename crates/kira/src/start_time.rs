@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::clock::ClockTime;
 
 /// Describes when an action should occur.
@@ -9,6 +11,41 @@ pub enum StartTime {
 	/// The action should occur when a clock reaches a
 	/// specific time.
 	ClockTime(ClockTime),
+	/// The action should occur after the given amount of time
+	/// has passed on the audio thread.
+	///
+	/// Unlike [`StartTime::Immediate`], which waits for the next
+	/// batch of audio to be processed, the delay is counted in
+	/// samples, so actions with the same delay that are sent at
+	/// the same time will start on exactly the same sample.
+	Delay(Duration),
+}
+
+impl StartTime {
+	/// Returns the delay in seconds if this is a [`StartTime::Delay`].
+	pub(crate) fn delay(&self) -> Option<f64> {
+		match self {
+			StartTime::Delay(delay) => Some(delay.as_secs_f64()),
+			_ => None,
+		}
+	}
+}
+
+/// Counts down the remaining delay before an action starts.
+///
+/// Returns `None` if the action should still wait, or the
+/// portion of `dt` that's left after the delay is over.
+pub(crate) fn count_down_delay(remaining_delay: &mut f64, dt: f64) -> Option<f64> {
+	if *remaining_delay <= 0.0 {
+		return Some(dt);
+	}
+	if *remaining_delay >= dt {
+		*remaining_delay -= dt;
+		return None;
+	}
+	let remaining_dt = dt - *remaining_delay;
+	*remaining_delay = 0.0;
+	Some(remaining_dt)
 }
 
 impl From<ClockTime> for StartTime {
@@ -17,6 +54,12 @@ impl From<ClockTime> for StartTime {
 	}
 }
 
+impl From<Duration> for StartTime {
+	fn from(v: Duration) -> Self {
+		Self::Delay(v)
+	}
+}
+
 impl Default for StartTime {
 	fn default() -> Self {
 		Self::Immediate
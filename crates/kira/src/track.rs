@@ -1,32 +1,53 @@
 //! Organizes and applies effects to audio.
 
 mod builder;
+mod correlation_meter;
+mod delay_line;
 pub mod effect;
+mod envelope_follower;
+mod group;
 mod handle;
 mod routes;
+mod template;
 
 #[cfg(test)]
 mod test;
 
 pub use builder::*;
+pub use group::*;
 pub use handle::*;
 pub use routes::*;
+pub use template::*;
+
+pub(crate) use delay_line::DelayLine;
 
 use std::sync::{
-	atomic::{AtomicBool, Ordering},
-	Arc,
+	atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+	Arc, Mutex, MutexGuard,
 };
 
 use atomic_arena::Key;
 
 use crate::{
 	clock::ClockTime,
-	dsp::Frame,
+	dsp::{Frame, Random},
 	tween::{Tween, Tweener},
 	Volume,
 };
 
-use self::effect::Effect;
+use self::{
+	correlation_meter::CorrelationMeter,
+	effect::{Effect, EffectId},
+	envelope_follower::EnvelopeFollower,
+};
+
+/// Samples quieter than this are considered silent when
+/// deciding whether a track's effects have finished ringing out.
+const SILENCE_THRESHOLD: f32 = 1.0e-6;
+
+/// The number of routes that can be added to a track
+/// after it's created.
+pub(crate) const MAX_ADDED_ROUTES: usize = 8;
 
 /// A unique identifier for a mixer sub-track.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -55,15 +76,146 @@ impl From<&TrackHandle> for TrackId {
 
 pub(crate) struct TrackShared {
 	removed: AtomicBool,
+	envelope: AtomicU64,
+	correlation: AtomicU32,
+	non_finite_frames: AtomicU64,
+	/// The last [`NonFiniteSource`] seen by the track, where `0` means
+	/// none, `1` means the input, and `n + 2` means the `n`th effect.
+	last_non_finite_source: AtomicU64,
+	/// The current volume of the track (as an amplitude).
+	volume: AtomicU64,
+	/// The destination of each route, in the same order as the
+	/// track's routes, and whether the route has been removed.
+	///
+	/// This is only used on the main thread.
+	route_ids: Mutex<Vec<(TrackId, bool)>>,
+	/// The current volume of each route (as an amplitude). There's
+	/// a slot for every route the track can have, so the audio
+	/// thread never needs to allocate new ones.
+	route_volumes: Vec<AtomicU64>,
+	/// The number of sounds that were playing on the track at
+	/// the start of the last batch of samples.
+	num_sounds: AtomicU64,
 }
 
 impl TrackShared {
-	pub fn new() -> Self {
+	pub fn new(route_ids: impl IntoIterator<Item = TrackId>) -> Self {
+		let route_ids: Vec<(TrackId, bool)> = route_ids.into_iter().map(|id| (id, true)).collect();
 		Self {
+			volume: AtomicU64::new(1.0f64.to_bits()),
+			route_volumes: (0..route_ids.len() + MAX_ADDED_ROUTES)
+				.map(|_| AtomicU64::new(0.0f64.to_bits()))
+				.collect(),
+			route_ids: Mutex::new(route_ids),
+			num_sounds: AtomicU64::new(0),
 			removed: AtomicBool::new(false),
+			envelope: AtomicU64::new(0.0f64.to_bits()),
+			correlation: AtomicU32::new(0.0f32.to_bits()),
+			non_finite_frames: AtomicU64::new(0),
+			last_non_finite_source: AtomicU64::new(0),
+		}
+	}
+
+	pub fn non_finite_frame_count(&self) -> u64 {
+		self.non_finite_frames.load(Ordering::SeqCst)
+	}
+
+	pub fn last_non_finite_source(&self) -> Option<NonFiniteSource> {
+		match self.last_non_finite_source.load(Ordering::SeqCst) {
+			0 => None,
+			1 => Some(NonFiniteSource::Input),
+			n => Some(NonFiniteSource::Effect(EffectId(n as usize - 2))),
+		}
+	}
+
+	/// Replaces a frame with silence and reports where it came from
+	/// if it contains NaN or infinite samples.
+	///
+	/// The check is skipped in release builds.
+	fn sanitize(&self, frame: Frame, source: NonFiniteSource) -> Frame {
+		if !cfg!(debug_assertions) || frame.is_finite() {
+			return frame;
+		}
+		self.non_finite_frames.fetch_add(1, Ordering::SeqCst);
+		self.last_non_finite_source.store(
+			match source {
+				NonFiniteSource::Input => 1,
+				NonFiniteSource::Effect(EffectId(index)) => index as u64 + 2,
+			},
+			Ordering::SeqCst,
+		);
+		Frame::ZERO
+	}
+
+	pub fn envelope(&self) -> f64 {
+		f64::from_bits(self.envelope.load(Ordering::SeqCst))
+	}
+
+	pub fn correlation(&self) -> f32 {
+		f32::from_bits(self.correlation.load(Ordering::SeqCst))
+	}
+
+	pub fn volume(&self) -> Volume {
+		Volume::Amplitude(f64::from_bits(self.volume.load(Ordering::SeqCst)))
+	}
+
+	/// Returns the destination and current volume of each route
+	/// that hasn't been removed.
+	pub fn route_volumes(&self) -> Vec<(TrackId, Volume)> {
+		self.route_ids()
+			.iter()
+			.zip(&self.route_volumes)
+			.filter(|((_, active), _)| *active)
+			.map(|((id, _), volume)| {
+				(
+					*id,
+					Volume::Amplitude(f64::from_bits(volume.load(Ordering::SeqCst))),
+				)
+			})
+			.collect()
+	}
+
+	pub fn has_route(&self, to: TrackId) -> bool {
+		self.route_active(to) == Some(true)
+	}
+
+	/// Returns whether the route to a track hasn't been removed,
+	/// or `None` if the track has never had a route to it.
+	pub fn route_active(&self, to: TrackId) -> Option<bool> {
+		self.route_ids()
+			.iter()
+			.find(|(id, _)| *id == to)
+			.map(|(_, active)| *active)
+	}
+
+	/// Returns `true` if there's a free slot for a new route.
+	pub fn has_room_for_route(&self) -> bool {
+		self.route_ids().len() < self.route_volumes.len()
+	}
+
+	/// Records that a route has been added or removed, adding it
+	/// to the list of routes if it's new.
+	///
+	/// This should only be called after the command to change the
+	/// route has been sent to the audio thread.
+	pub fn set_route_active(&self, to: TrackId, active: bool) {
+		let mut route_ids = self.route_ids();
+		match route_ids.iter_mut().find(|(id, _)| *id == to) {
+			Some((_, existing_active)) => *existing_active = active,
+			None => route_ids.push((to, active)),
 		}
 	}
 
+	fn route_ids(&self) -> MutexGuard<'_, Vec<(TrackId, bool)>> {
+		self.route_ids
+			.lock()
+			.expect("The route ID mutex was poisoned")
+	}
+
+	pub fn num_sounds(&self) -> usize {
+		self.num_sounds.load(Ordering::SeqCst) as usize
+	}
+
 	pub fn is_marked_for_removal(&self) -> bool {
 		self.removed.load(Ordering::SeqCst)
 	}
@@ -73,49 +225,269 @@ impl TrackShared {
 	}
 }
 
+type SubTrackEntry = (SubTrackId, Arc<TrackShared>, Vec<&'static str>);
+
+/// The sub-tracks that haven't been removed, from oldest to newest.
+///
+/// This is shared between the [`AudioManager`](crate::manager::AudioManager)
+/// and the track handles so routes can be checked before they're
+/// added. It's only used on the main thread.
+#[derive(Clone, Default)]
+pub(crate) struct SubTrackRegistry(Arc<Mutex<Vec<SubTrackEntry>>>);
+
+impl SubTrackRegistry {
+	pub fn tracks(&self) -> MutexGuard<'_, Vec<SubTrackEntry>> {
+		let mut tracks = self
+			.0
+			.lock()
+			.expect("The sub-track registry mutex was poisoned");
+		tracks.retain(|(_, shared, _)| !shared.is_marked_for_removal());
+		tracks
+	}
+
+	pub fn add(&self, id: SubTrackId, shared: Arc<TrackShared>, effect_names: Vec<&'static str>) {
+		self.tracks().push((id, shared, effect_names));
+	}
+
+	pub fn contains(&self, id: SubTrackId) -> bool {
+		self.tracks()
+			.iter()
+			.any(|(existing_id, _, _)| *existing_id == id)
+	}
+
+	/// Returns `true` if the output of the `from` track reaches the
+	/// `to` track, either directly or through other tracks.
+	///
+	/// Removed routes count, since they're still silently connected
+	/// on the audio thread.
+	pub fn reaches(&self, from: SubTrackId, to: SubTrackId) -> bool {
+		let tracks = self.tracks();
+		let mut visited = vec![];
+		let mut unvisited = vec![from];
+		while let Some(id) = unvisited.pop() {
+			if id == to {
+				return true;
+			}
+			if visited.contains(&id) {
+				continue;
+			}
+			visited.push(id);
+			if let Some((_, shared, _)) = tracks.iter().find(|(track_id, _, _)| *track_id == id) {
+				unvisited.extend(shared.route_ids().iter().filter_map(|(destination, _)| {
+					match destination {
+						TrackId::Main => None,
+						TrackId::Sub(id) => Some(*id),
+					}
+				}));
+			}
+		}
+		false
+	}
+}
+
 pub(crate) struct Track {
 	shared: Arc<TrackShared>,
 	volume: Tweener<Volume>,
-	routes: Vec<(TrackId, Tweener<Volume>)>,
+	routes: Vec<(TrackId, Tweener<Volume>, SendPoint)>,
 	effects: Vec<Box<dyn Effect>>,
+	envelope_follower: Option<EnvelopeFollower>,
+	correlation_meter: Option<CorrelationMeter>,
+	/// The output of the effects from the last call to `process`,
+	/// before the track's volume was applied.
+	pre_fader_output: Frame,
+	/// Input from sounds playing on the track.
 	input: Frame,
+	/// Input from other tracks routed to this one.
+	routed_input: Frame,
+	/// Delays the input from sounds to line up with the input
+	/// from tracks with latency that are routed to this one.
+	input_delay: DelayLine,
+	/// Delays the output sent along each route to line up with
+	/// other signals arriving at the destination track. These are
+	/// in the same order as `routes`.
+	route_delays: Vec<DelayLine>,
+	/// The latency of the slowest signal arriving at the track (in frames).
+	input_latency: usize,
+	/// The total latency of the effects (in frames).
+	latency_frames: usize,
+	receiving_input: bool,
+	/// The total of the tail lengths of the effects (in frames).
+	tail_frames: usize,
+	/// The number of frames in a row that the track has received
+	/// no input and its effects have output silence.
+	silent_frames: usize,
+	/// Whether the track is fading out its volume and should be
+	/// marked for removal once the fade finishes.
+	stopping: bool,
+	/// The number of frames of silence to run through the effects
+	/// after they're initialized.
+	warm_up_frames: usize,
+	/// The number of sounds counted on the track since the
+	/// last call to `on_start_processing`.
+	num_sounds: usize,
+	/// The number of tracks routed to this one that haven't been
+	/// reached yet while sorting the tracks for latency compensation.
+	unsorted_inputs: usize,
 }
 
 impl Track {
 	pub fn new(builder: TrackBuilder) -> Self {
-		Self {
-			shared: Arc::new(TrackShared::new()),
+		let mut routes = builder.routes.into_vec();
+		// leave room for routes added later so adding them
+		// doesn't allocate on the audio thread
+		routes.reserve(MAX_ADDED_ROUTES);
+		let mut route_delays = Vec::with_capacity(routes.len() + MAX_ADDED_ROUTES);
+		route_delays.extend(routes.iter().map(|_| DelayLine::new()));
+		let track = Self {
+			shared: Arc::new(TrackShared::new(routes.iter().map(|(id, _, _)| *id))),
 			volume: Tweener::new(builder.volume),
-			routes: builder.routes.into_vec(),
+			route_delays,
+			routes,
 			effects: builder.effects,
+			envelope_follower: builder.envelope_follower.map(
+				|(attack_duration, release_duration)| {
+					EnvelopeFollower::new(attack_duration, release_duration)
+				},
+			),
+			correlation_meter: builder.correlation_meter.map(CorrelationMeter::new),
+			pre_fader_output: Frame::ZERO,
 			input: Frame::ZERO,
-		}
+			routed_input: Frame::ZERO,
+			input_delay: DelayLine::new(),
+			input_latency: 0,
+			latency_frames: 0,
+			receiving_input: true,
+			tail_frames: 0,
+			silent_frames: 0,
+			stopping: false,
+			warm_up_frames: builder.warm_up_frames,
+			num_sounds: 0,
+			unsorted_inputs: 0,
+		};
+		track.update_shared_volumes();
+		track
 	}
 
-	pub fn init_effects(&mut self, sample_rate: u32) {
+	pub fn init_effects(&mut self, sample_rate: u32, random: &mut Random) {
 		for effect in &mut self.effects {
+			effect.set_random_seed(random.next_u64());
 			effect.init(sample_rate);
 		}
+		let dt = 1.0 / sample_rate as f64;
+		for _ in 0..self.warm_up_frames {
+			for effect in &mut self.effects {
+				effect.process(Frame::ZERO, dt);
+			}
+		}
+		self.update_tail_frames();
 	}
 
 	pub fn on_change_sample_rate(&mut self, sample_rate: u32) {
 		for effect in &mut self.effects {
 			effect.on_change_sample_rate(sample_rate);
 		}
+		self.update_tail_frames();
+	}
+
+	fn update_tail_frames(&mut self) {
+		self.tail_frames = self.effects.iter().fold(0, |total, effect| {
+			total.saturating_add(effect.tail_frames())
+		});
+		self.latency_frames = self.effects.iter().fold(0, |total, effect| {
+			total.saturating_add(effect.latency_frames())
+		});
+	}
+
+	/// Returns `true` if the track has received no input and its
+	/// effects have finished ringing out.
+	pub fn is_idle(&self) -> bool {
+		// sound that's still passing through the input delay or
+		// the effects' latency hasn't come out of the track yet
+		self.silent_frames
+			> self
+				.tail_frames
+				.saturating_add(self.latency_frames)
+				.saturating_add(self.input_delay.delay())
+	}
+
+	/// Returns the latency of the track's output relative to
+	/// sounds playing on the track, including the latency of
+	/// tracks routed to this one (in frames).
+	pub fn output_latency(&self) -> usize {
+		self.input_latency.saturating_add(self.latency_frames)
+	}
+
+	pub fn input_latency(&self) -> usize {
+		self.input_latency
+	}
+
+	/// Sets the latency of the slowest signal arriving at the
+	/// track and delays the input from sounds to match it.
+	pub fn set_input_latency(&mut self, input_latency: usize) {
+		self.input_latency = input_latency;
+		self.input_delay.set_delay(input_latency);
+	}
+
+	/// The number of tracks routed to this one that haven't been
+	/// reached yet while sorting the tracks for latency compensation.
+	pub fn unsorted_inputs_mut(&mut self) -> &mut usize {
+		&mut self.unsorted_inputs
+	}
+
+	pub fn route_delays_mut(&mut self) -> &mut Vec<DelayLine> {
+		&mut self.route_delays
+	}
+
+	/// Makes the track ignore any further input so that its
+	/// effects can ring out before it's removed.
+	pub fn stop_receiving_input(&mut self) {
+		self.receiving_input = false;
+	}
+
+	/// Lets the track's effects know that the track is being
+	/// removed from the mixer.
+	pub fn on_removed(&mut self) {
+		for effect in &mut self.effects {
+			effect.on_removed();
+		}
 	}
 
 	pub fn shared(&self) -> Arc<TrackShared> {
 		self.shared.clone()
 	}
 
-	pub fn routes_mut(&mut self) -> &mut Vec<(TrackId, Tweener<Volume>)> {
+	pub fn routes(&self) -> &[(TrackId, Tweener<Volume>, SendPoint)] {
+		&self.routes
+	}
+
+	pub fn routes_mut(&mut self) -> &mut Vec<(TrackId, Tweener<Volume>, SendPoint)> {
 		&mut self.routes
 	}
 
+	/// Returns the output of the track's effects from the last
+	/// call to `process`, before the track's volume was applied.
+	pub fn pre_fader_output(&self) -> Frame {
+		self.pre_fader_output
+	}
+
+	/// Returns `true` if the track is fading out before being
+	/// marked for removal.
+	pub fn is_stopping(&self) -> bool {
+		self.stopping
+	}
+
 	pub fn set_volume(&mut self, volume: Volume, tween: Tween) {
 		self.volume.set(volume, tween);
 	}
 
+	/// Fades out the track and marks it for removal once the
+	/// fade finishes. This should never be called on the main track.
+	pub fn stop(&mut self, fade_out_tween: Tween) {
+		self.volume
+			.set(fade_out_tween.fade_curve.silent_volume(), fade_out_tween);
+		self.stopping = true;
+	}
+
 	pub fn set_route(&mut self, to: TrackId, volume: Volume, tween: Tween) {
 		// TODO: determine if we should store the track routes in some
 		// other data structure like an IndexMap so we don't have to do
@@ -123,37 +495,148 @@ impl Track {
 		if let Some(route) =
 			self.routes
 				.iter_mut()
-				.find_map(|(id, route)| if *id == to { Some(route) } else { None })
+				.find_map(|(id, route, _)| if *id == to { Some(route) } else { None })
 		{
 			route.set(volume, tween);
 		}
 	}
 
+	/// Adds a route to another track, fading its volume in from
+	/// silence. If the route already exists, this just changes
+	/// its volume and send point.
+	///
+	/// `delay_line` should be created on the main thread. It's only
+	/// `None` if the main thread knows the route already exists.
+	pub fn add_route(
+		&mut self,
+		to: TrackId,
+		volume: Volume,
+		send_point: SendPoint,
+		tween: Tween,
+		delay_line: Option<DelayLine>,
+	) {
+		if let Some((_, route, existing_send_point)) =
+			self.routes.iter_mut().find(|(id, _, _)| *id == to)
+		{
+			route.set(volume, tween);
+			*existing_send_point = send_point;
+			return;
+		}
+		let delay_line = match delay_line {
+			Some(delay_line) => delay_line,
+			None => return,
+		};
+		let mut route = Tweener::new(Volume::Amplitude(0.0));
+		route.set(volume, tween);
+		self.routes.push((to, route, send_point));
+		self.route_delays.push(delay_line);
+	}
+
 	pub fn add_input(&mut self, input: Frame) {
-		self.input += input;
+		if self.receiving_input {
+			self.input += input;
+		}
+	}
+
+	/// Adds input from another track routed to this one. Unlike
+	/// [`add_input`](Self::add_input), this input isn't delayed,
+	/// since the mixer already delays it before sending it.
+	pub fn add_routed_input(&mut self, input: Frame) {
+		if self.receiving_input {
+			self.routed_input += input;
+		}
+	}
+
+	/// Records that a sound is playing on this track. The count is
+	/// reported to the track's shared state and reset at the start
+	/// of each batch of samples.
+	pub fn count_sound(&mut self) {
+		self.num_sounds += 1;
+	}
+
+	fn update_shared_volumes(&self) {
+		self.shared.volume.store(
+			self.volume.value().as_amplitude().to_bits(),
+			Ordering::SeqCst,
+		);
+		for ((_, route, _), shared_volume) in self.routes.iter().zip(&self.shared.route_volumes) {
+			shared_volume.store(route.value().as_amplitude().to_bits(), Ordering::SeqCst);
+		}
 	}
 
 	pub fn on_start_processing(&mut self) {
+		self.update_tail_frames();
+		self.update_shared_volumes();
+		self.shared.num_sounds.store(
+			std::mem::take(&mut self.num_sounds) as u64,
+			Ordering::SeqCst,
+		);
+		if let Some(envelope_follower) = &self.envelope_follower {
+			self.shared
+				.envelope
+				.store(envelope_follower.value().to_bits(), Ordering::SeqCst);
+		}
+		if let Some(correlation_meter) = &self.correlation_meter {
+			self.shared
+				.correlation
+				.store(correlation_meter.value().to_bits(), Ordering::SeqCst);
+		}
 		for effect in &mut self.effects {
 			effect.on_start_processing();
 		}
 	}
 
 	pub fn process(&mut self, dt: f64) -> Frame {
-		self.volume.update(dt);
-		for (_, route) in &mut self.routes {
+		if self.volume.update(dt) && self.stopping {
+			self.stopping = false;
+			self.shared.mark_for_removal();
+		}
+		for (_, route, _) in &mut self.routes {
 			route.update(dt);
 		}
-		let mut output = std::mem::replace(&mut self.input, Frame::ZERO);
-		for effect in &mut self.effects {
-			output = effect.process(output, dt);
+		let input = self
+			.input_delay
+			.process(std::mem::replace(&mut self.input, Frame::ZERO))
+			+ std::mem::replace(&mut self.routed_input, Frame::ZERO);
+		let input = self.shared.sanitize(input, NonFiniteSource::Input);
+		// if the effects have finished ringing out and there's no new
+		// input, they would only output silence, so we can skip them
+		if self.is_idle() && is_silent(input) {
+			if let Some(envelope_follower) = &mut self.envelope_follower {
+				envelope_follower.update(Frame::ZERO, dt);
+			}
+			if let Some(correlation_meter) = &mut self.correlation_meter {
+				correlation_meter.update(Frame::ZERO, dt);
+			}
+			self.pre_fader_output = Frame::ZERO;
+			return Frame::ZERO;
+		}
+		let mut output = input;
+		for (index, effect) in self.effects.iter_mut().enumerate() {
+			output = self.shared.sanitize(
+				effect.process(output, dt),
+				NonFiniteSource::Effect(EffectId(index)),
+			);
+		}
+		if is_silent(input) && is_silent(output) {
+			self.silent_frames = self.silent_frames.saturating_add(1);
+		} else {
+			self.silent_frames = 0;
+		}
+		self.pre_fader_output = output.flush_denormals();
+		let output = self.pre_fader_output * self.volume.value().as_amplitude() as f32;
+		if let Some(envelope_follower) = &mut self.envelope_follower {
+			envelope_follower.update(output, dt);
 		}
-		output * self.volume.value().as_amplitude() as f32
+		if let Some(correlation_meter) = &mut self.correlation_meter {
+			correlation_meter.update(output, dt);
+		}
+		output
 	}
 
 	pub fn on_clock_tick(&mut self, time: ClockTime) {
 		self.volume.on_clock_tick(time);
-		for (_, route) in &mut self.routes {
+		for (_, route, _) in &mut self.routes {
 			route.on_clock_tick(time);
 		}
 		for effect in &mut self.effects {
@@ -161,3 +644,18 @@ impl Track {
 		}
 	}
 }
+
+/// Where a NaN or infinite sample on a mixer track came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NonFiniteSource {
+	/// The input to the track, which comes from sounds and
+	/// other tracks routed to it.
+	Input,
+	/// The output of one of the track's effects.
+	Effect(EffectId),
+}
+
+fn is_silent(frame: Frame) -> bool {
+	frame.left.abs() < SILENCE_THRESHOLD && frame.right.abs() < SILENCE_THRESHOLD
+}
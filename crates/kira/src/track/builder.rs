@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::Volume;
 
 use super::{effect::EffectBuilder, routes::TrackRoutes, Effect};
@@ -13,6 +15,16 @@ pub struct TrackBuilder {
 	/// The effects that should be applied to the input audio
 	/// for this track.
 	pub(crate) effects: Vec<Box<dyn Effect>>,
+	/// The names of the effects, in the same order as `effects`.
+	pub(crate) effect_names: Vec<&'static str>,
+	/// The attack and release durations of the track's
+	/// envelope follower, if it has one.
+	pub(crate) envelope_follower: Option<(Duration, Duration)>,
+	/// The window of the track's correlation meter, if it has one.
+	pub(crate) correlation_meter: Option<Duration>,
+	/// The number of frames of silence to run through the effects
+	/// when the track is added to the mixer.
+	pub(crate) warm_up_frames: usize,
 }
 
 impl TrackBuilder {
@@ -22,6 +34,10 @@ impl TrackBuilder {
 			volume: Volume::Amplitude(1.0),
 			routes: TrackRoutes::new(),
 			effects: vec![],
+			effect_names: vec![],
+			envelope_follower: None,
+			correlation_meter: None,
+			warm_up_frames: 0,
 		}
 	}
 
@@ -39,8 +55,56 @@ impl TrackBuilder {
 		Self { routes, ..self }
 	}
 
+	/// Enables the envelope follower for the track, which
+	/// can be read using [`TrackHandle::envelope`](super::TrackHandle::envelope).
+	///
+	/// `attack_duration` and `release_duration` control how quickly
+	/// the envelope rises and falls to match the track's output.
+	pub fn envelope_follower(self, attack_duration: Duration, release_duration: Duration) -> Self {
+		Self {
+			envelope_follower: Some((attack_duration, release_duration)),
+			..self
+		}
+	}
+
+	/// Enables the correlation meter for the track, which can be
+	/// read using [`TrackHandle::correlation`](super::TrackHandle::correlation).
+	///
+	/// `window` controls how much of the track's recent output
+	/// the correlation is measured over. Longer windows give a
+	/// steadier reading that's slower to react to changes.
+	pub fn correlation_meter(self, window: Duration) -> Self {
+		Self {
+			correlation_meter: Some(window),
+			..self
+		}
+	}
+
+	/// Sets the number of frames of silence to run through the
+	/// track's effects when the track is added to the mixer.
+	///
+	/// The first time an effect processes audio, it may touch memory
+	/// that hasn't been used yet, which can cause a hitch on the audio
+	/// thread. Warming up the effects right after they're initialized
+	/// moves that cost to when the track is added, rather than when it
+	/// first receives audio. This is mostly useful for tracks added
+	/// while other audio is playing.
+	///
+	/// Warming up happens on the audio thread, so this should be kept
+	/// small (a block or two of audio). Effects see this as a short
+	/// stretch of silence, so tweens and LFOs will have advanced
+	/// slightly by the time the track receives audio. Defaults to 0
+	/// (no warm-up).
+	pub fn warm_up_frames(self, warm_up_frames: usize) -> Self {
+		Self {
+			warm_up_frames,
+			..self
+		}
+	}
+
 	/// Adds an effect to the track.
 	pub fn add_effect<B: EffectBuilder>(&mut self, builder: B) -> B::Handle {
+		self.effect_names.push(builder.name());
 		let (effect, handle) = builder.build();
 		self.effects.push(effect);
 		handle
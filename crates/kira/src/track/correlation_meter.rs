@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use crate::dsp::Frame;
+
+/// Signals quieter than this (in average power) are treated as
+/// silence, which has no meaningful correlation.
+const SILENCE_POWER_THRESHOLD: f64 = 1.0e-12;
+
+/// Tracks the running correlation between the left and
+/// right channels of a signal.
+pub(crate) struct CorrelationMeter {
+	window: Duration,
+	/// The smoothed product of the left and right channels.
+	left_right: f64,
+	/// The smoothed power of the left channel.
+	left_power: f64,
+	/// The smoothed power of the right channel.
+	right_power: f64,
+}
+
+impl CorrelationMeter {
+	pub fn new(window: Duration) -> Self {
+		Self {
+			window,
+			left_right: 0.0,
+			left_power: 0.0,
+			right_power: 0.0,
+		}
+	}
+
+	/// Returns the correlation from `-1.0` (the channels are
+	/// out of phase) to `1.0` (the channels are identical),
+	/// or `0.0` if the signal is silent.
+	pub fn value(&self) -> f32 {
+		let power = (self.left_power * self.right_power).sqrt();
+		if power < SILENCE_POWER_THRESHOLD {
+			return 0.0;
+		}
+		(self.left_right / power).clamp(-1.0, 1.0) as f32
+	}
+
+	pub fn update(&mut self, input: Frame, dt: f64) {
+		let left = input.left as f64;
+		let right = input.right as f64;
+		// one-pole smoothing, like the envelope follower, so older
+		// samples gradually stop affecting the result
+		let coefficient = if self.window.is_zero() {
+			0.0
+		} else {
+			(-dt / self.window.as_secs_f64()).exp()
+		};
+		self.left_right = left * right + (self.left_right - left * right) * coefficient;
+		self.left_power = left * left + (self.left_power - left * left) * coefficient;
+		self.right_power = right * right + (self.right_power - right * right) * coefficient;
+	}
+}
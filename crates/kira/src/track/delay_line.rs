@@ -0,0 +1,56 @@
+use crate::dsp::Frame;
+
+/// The longest delay (in frames) that the mixer can use to keep
+/// signals with different amounts of latency aligned.
+pub(crate) const MAX_LATENCY_COMPENSATION_FRAMES: usize = 2048;
+
+/// Delays a signal by a whole number of frames.
+///
+/// The buffer is allocated up front so the delay can be changed
+/// on the audio thread without allocating.
+pub(crate) struct DelayLine {
+	buffer: Vec<Frame>,
+	write_index: usize,
+	delay: usize,
+}
+
+impl DelayLine {
+	pub fn new() -> Self {
+		Self {
+			buffer: vec![Frame::ZERO; MAX_LATENCY_COMPENSATION_FRAMES + 1],
+			write_index: 0,
+			delay: 0,
+		}
+	}
+
+	pub fn delay(&self) -> usize {
+		self.delay
+	}
+
+	/// Sets how many frames to delay the signal by.
+	///
+	/// Delays longer than [`MAX_LATENCY_COMPENSATION_FRAMES`]
+	/// are clamped.
+	pub fn set_delay(&mut self, delay: usize) {
+		let delay = delay.min(MAX_LATENCY_COMPENSATION_FRAMES);
+		if delay == self.delay {
+			return;
+		}
+		self.delay = delay;
+		// discard the old contents of the buffer so stale audio
+		// doesn't play at the new delay time
+		for frame in &mut self.buffer {
+			*frame = Frame::ZERO;
+		}
+	}
+
+	pub fn process(&mut self, input: Frame) -> Frame {
+		if self.delay == 0 {
+			return input;
+		}
+		self.buffer[self.write_index] = input;
+		let read_index = (self.write_index + self.buffer.len() - self.delay) % self.buffer.len();
+		self.write_index = (self.write_index + 1) % self.buffer.len();
+		self.buffer[read_index]
+	}
+}
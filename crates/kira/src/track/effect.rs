@@ -1,19 +1,66 @@
 //! Modifies audio signals.
 
+pub mod channel_ops;
 pub mod delay;
 pub mod distortion;
 pub mod filter;
 pub mod panning_control;
 pub mod reverb;
+pub mod tremolo;
 pub mod volume_control;
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use crate::{clock::ClockTime, dsp::Frame};
 
+/// Identifies an effect on a mixer track by its position
+/// in the track's effect chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EffectId(pub(crate) usize);
+
+impl EffectId {
+	/// Returns the position of the effect in the track's
+	/// effect chain, starting from 0.
+	pub fn index(&self) -> usize {
+		self.0
+	}
+}
+
+/// State shared between a built-in effect and its handle.
+pub(crate) struct EffectShared {
+	removed: AtomicBool,
+}
+
+impl EffectShared {
+	pub fn new() -> Self {
+		Self {
+			removed: AtomicBool::new(false),
+		}
+	}
+
+	pub fn is_removed(&self) -> bool {
+		self.removed.load(Ordering::SeqCst)
+	}
+
+	pub fn mark_as_removed(&self) {
+		self.removed.store(true, Ordering::SeqCst);
+	}
+}
+
 /// Configures an effect.
 pub trait EffectBuilder {
 	/// Allows the user to control the effect from gameplay code.
 	type Handle;
 
+	/// Returns a human-readable name for the kind of effect
+	/// this builder creates, like "Filter" or "Reverb".
+	///
+	/// This can be retrieved later using
+	/// [`TrackHandle::effect_name`](crate::track::TrackHandle::effect_name).
+	fn name(&self) -> &'static str {
+		"Custom effect"
+	}
+
 	/// Creates the effect and a handle to the effect.
 	fn build(self) -> (Box<dyn Effect>, Self::Handle);
 }
@@ -24,6 +71,16 @@ pub trait Effect: Send + Sync {
 	/// Called when the effect is first sent to the renderer.
 	fn init(&mut self, sample_rate: u32) {}
 
+	/// Called right before [`init`](Effect::init) with a seed that the
+	/// effect can use for any randomness it needs, for example by
+	/// creating a [`Random`](crate::dsp::Random).
+	///
+	/// Seeds are derived from
+	/// [`AudioManagerSettings::random_seed`](crate::manager::AudioManagerSettings::random_seed),
+	/// so effects that use this seed will produce the same output every
+	/// time if the same random seed is used.
+	fn set_random_seed(&mut self, seed: u64) {}
+
 	/// Called when the sample rate of the renderer is changed.
 	fn on_change_sample_rate(&mut self, sample_rate: u32) {}
 
@@ -40,5 +97,59 @@ pub trait Effect: Send + Sync {
 	fn process(&mut self, input: Frame, dt: f64) -> Frame;
 
 	/// Called whenever a [clock](crate::clock) ticks.
+	///
+	/// This is called for every clock, on the audio thread, right
+	/// before the [`process`](Effect::process) call for the frame the
+	/// clock ticked on. To move in time with a clock, for example to
+	/// sweep a filter once per beat, forward the ticks to a
+	/// [`ClockSync`](crate::clock::ClockSync).
 	fn on_clock_tick(&mut self, time: ClockTime) {}
+
+	/// Called on the audio thread when the mixer track the effect
+	/// belongs to is removed.
+	///
+	/// The effect will not be processed after this is called, so
+	/// this is a good place to let the effect's handle know that
+	/// it can no longer send commands.
+	fn on_removed(&mut self) {}
+
+	/// Returns the longest stretch of silence (in frames) that the
+	/// effect can output while it still has sound left to output,
+	/// like the gap between the echoes of a delay.
+	///
+	/// Once a mixer track has received no input and output silence
+	/// for longer than this, its effects are considered to have
+	/// finished ringing out.
+	///
+	/// While a track is idle, its effects are not processed, so
+	/// [`process`](Effect::process) will not be called until the
+	/// track receives input again. Effects that can start outputting
+	/// sound on their own should return `usize::MAX` so the track is
+	/// never considered idle.
+	///
+	/// Effects that only output sound while they're receiving
+	/// input, or whose output only ever decays smoothly, can
+	/// use the default implementation, which returns `0`.
+	fn tail_frames(&self) -> usize {
+		0
+	}
+
+	/// Returns how many frames the effect delays its input by, like
+	/// the lookahead of a limiter or the block size of an FFT-based
+	/// effect.
+	///
+	/// The latency of every effect on a mixer track is added up, along
+	/// with the latency of the tracks routed to it, and reported up the
+	/// chain to the tracks it routes to. The mixer delays every other
+	/// signal arriving at a track to match the slowest one, so a signal
+	/// that goes through an effect with latency stays aligned with a dry
+	/// copy of the same signal. Delays longer than 2048 frames are not
+	/// fully compensated.
+	///
+	/// This is called once per batch of audio, so the latency can
+	/// change over time, but changing it will cause a discontinuity
+	/// in the output.
+	fn latency_frames(&self) -> usize {
+		0
+	}
 }
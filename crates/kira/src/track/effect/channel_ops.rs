@@ -0,0 +1,99 @@
+//! Swaps, sums, isolates, or inverts the channels of audio.
+
+mod builder;
+mod handle;
+
+#[cfg(test)]
+mod test;
+
+pub use builder::*;
+pub use handle::*;
+
+use std::sync::Arc;
+
+use ringbuf::Consumer;
+
+use crate::dsp::Frame;
+
+use super::{Effect, EffectShared};
+
+enum Command {
+	SetMode(ChannelOpsMode),
+}
+
+/// How a channel ops effect changes the channels of its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum ChannelOpsMode {
+	/// The input is left unchanged.
+	#[default]
+	Passthrough,
+	/// The left and right channels are swapped.
+	SwapLR,
+	/// Both channels are set to the average of the left
+	/// and right channels.
+	///
+	/// This is useful for checking how a mix sounds
+	/// on mono speakers.
+	SumToMono,
+	/// Both channels are set to the left channel.
+	LeftOnly,
+	/// Both channels are set to the right channel.
+	RightOnly,
+	/// The phase of the left channel is flipped.
+	InvertLeft,
+	/// The phase of the right channel is flipped.
+	InvertRight,
+}
+
+impl ChannelOpsMode {
+	fn apply(self, input: Frame) -> Frame {
+		match self {
+			ChannelOpsMode::Passthrough => input,
+			ChannelOpsMode::SwapLR => Frame::new(input.right, input.left),
+			ChannelOpsMode::SumToMono => Frame::from_mono((input.left + input.right) / 2.0),
+			ChannelOpsMode::LeftOnly => Frame::from_mono(input.left),
+			ChannelOpsMode::RightOnly => Frame::from_mono(input.right),
+			ChannelOpsMode::InvertLeft => Frame::new(-input.left, input.right),
+			ChannelOpsMode::InvertRight => Frame::new(input.left, -input.right),
+		}
+	}
+}
+
+struct ChannelOps {
+	command_consumer: Consumer<Command>,
+	shared: Arc<EffectShared>,
+	mode: ChannelOpsMode,
+}
+
+impl ChannelOps {
+	fn new(
+		builder: ChannelOpsBuilder,
+		command_consumer: Consumer<Command>,
+		shared: Arc<EffectShared>,
+	) -> Self {
+		Self {
+			command_consumer,
+			shared,
+			mode: builder.mode,
+		}
+	}
+}
+
+impl Effect for ChannelOps {
+	fn on_removed(&mut self) {
+		self.shared.mark_as_removed();
+	}
+
+	fn on_start_processing(&mut self) {
+		while let Some(command) = self.command_consumer.pop() {
+			match command {
+				Command::SetMode(mode) => self.mode = mode,
+			}
+		}
+	}
+
+	fn process(&mut self, input: Frame, _dt: f64) -> Frame {
+		self.mode.apply(input)
+	}
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use ringbuf::RingBuffer;
+
+use crate::track::effect::{Effect, EffectBuilder, EffectShared};
+
+use super::{ChannelOps, ChannelOpsHandle, ChannelOpsMode};
+
+const COMMAND_CAPACITY: usize = 8;
+
+/// Configures a channel ops effect.
+#[derive(Debug, Copy, Clone, Default)]
+#[non_exhaustive]
+pub struct ChannelOpsBuilder {
+	/// How the effect changes the channels of its input.
+	pub mode: ChannelOpsMode,
+}
+
+impl ChannelOpsBuilder {
+	/// Creates a new [`ChannelOpsBuilder`].
+	pub fn new(mode: ChannelOpsMode) -> Self {
+		Self { mode }
+	}
+}
+
+impl EffectBuilder for ChannelOpsBuilder {
+	type Handle = ChannelOpsHandle;
+
+	fn name(&self) -> &'static str {
+		"Channel ops"
+	}
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_CAPACITY).split();
+		let shared = Arc::new(EffectShared::new());
+		(
+			Box::new(ChannelOps::new(self, command_consumer, shared.clone())),
+			ChannelOpsHandle {
+				command_producer,
+				shared,
+			},
+		)
+	}
+}
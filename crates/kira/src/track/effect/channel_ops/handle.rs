@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use ringbuf::Producer;
+
+use crate::{track::effect::EffectShared, CommandError};
+
+use super::{ChannelOpsMode, Command};
+
+/// Controls a channel ops effect.
+pub struct ChannelOpsHandle {
+	pub(super) command_producer: Producer<Command>,
+	pub(super) shared: Arc<EffectShared>,
+}
+
+impl ChannelOpsHandle {
+	/// Sets how the effect changes the channels of its input.
+	///
+	/// The new mode takes effect immediately, so switching modes
+	/// while audio is playing may cause a click.
+	pub fn set_mode(&mut self, mode: ChannelOpsMode) -> Result<(), CommandError> {
+		self.push_command(Command::SetMode(mode))
+	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		if self.shared.is_removed() {
+			return Err(CommandError::ResourceRemoved);
+		}
+		self.command_producer
+			.push(command)
+			.map_err(|_| CommandError::CommandQueueFull)
+	}
+}
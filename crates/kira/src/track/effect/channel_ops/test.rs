@@ -0,0 +1,40 @@
+use crate::{dsp::Frame, track::effect::EffectBuilder};
+
+use super::{ChannelOpsBuilder, ChannelOpsMode};
+
+/// Tests the output of each channel ops mode.
+#[test]
+#[allow(clippy::float_cmp)]
+fn modes() {
+	let process = |mode| {
+		let (mut effect, _) = ChannelOpsBuilder::new(mode).build();
+		effect.init(1);
+		effect.process(Frame::new(1.0, 0.5), 1.0)
+	};
+	assert_eq!(process(ChannelOpsMode::Passthrough), Frame::new(1.0, 0.5));
+	assert_eq!(process(ChannelOpsMode::SwapLR), Frame::new(0.5, 1.0));
+	assert_eq!(process(ChannelOpsMode::SumToMono), Frame::new(0.75, 0.75));
+	assert_eq!(process(ChannelOpsMode::LeftOnly), Frame::new(1.0, 1.0));
+	assert_eq!(process(ChannelOpsMode::RightOnly), Frame::new(0.5, 0.5));
+	assert_eq!(process(ChannelOpsMode::InvertLeft), Frame::new(-1.0, 0.5));
+	assert_eq!(process(ChannelOpsMode::InvertRight), Frame::new(1.0, -0.5));
+}
+
+/// Tests that the mode of a channel ops effect can be
+/// changed after it's created.
+#[test]
+#[allow(clippy::float_cmp)]
+fn set_mode() {
+	let (mut effect, mut handle) = ChannelOpsBuilder::default().build();
+	effect.init(1);
+	assert_eq!(
+		effect.process(Frame::new(1.0, 0.5), 1.0),
+		Frame::new(1.0, 0.5)
+	);
+	handle.set_mode(ChannelOpsMode::SwapLR).unwrap();
+	effect.on_start_processing();
+	assert_eq!(
+		effect.process(Frame::new(1.0, 0.5), 1.0),
+		Frame::new(0.5, 1.0)
+	);
+}
@@ -3,15 +3,20 @@
 mod builder;
 mod handle;
 
+#[cfg(test)]
+mod test;
+
 pub use builder::*;
 pub use handle::*;
 
+use std::sync::Arc;
+
 use ringbuf::Consumer;
 
 use crate::{
 	clock::ClockTime,
-	dsp::{interpolate_frame, Frame},
-	track::Effect,
+	dsp::{interpolate_frame, Frame, Random},
+	track::{effect::EffectShared, Effect},
 	tween::{Tween, Tweener},
 	Volume,
 };
@@ -22,6 +27,25 @@ enum Command {
 	SetMix(f64, Tween),
 }
 
+/// How the left and right channels are handled by a delay effect.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DelayMode {
+	/// The left and right channels are mixed together,
+	/// so echoes are heard in the center.
+	Mono,
+	/// The left and right channels are delayed separately,
+	/// so echoes keep the panning of the original sound.
+	Stereo,
+	/// Each echo swaps the left and right channels, so
+	/// echoes of sounds that are panned to one side will
+	/// bounce back and forth between the left and right.
+	///
+	/// The first echo will be heard on the opposite side
+	/// of the original sound.
+	PingPong,
+}
+
 #[derive(Debug, Clone)]
 enum DelayState {
 	Uninitialized {
@@ -36,21 +60,29 @@ enum DelayState {
 
 struct Delay {
 	command_consumer: Consumer<Command>,
+	shared: Arc<EffectShared>,
 	delay_time: Tweener,
 	feedback: Tweener<Volume>,
 	mix: Tweener,
+	mode: DelayMode,
 	state: DelayState,
 	feedback_effects: Vec<Box<dyn Effect>>,
 }
 
 impl Delay {
 	/// Creates a new delay effect.
-	fn new(builder: DelayBuilder, command_consumer: Consumer<Command>) -> Self {
+	fn new(
+		builder: DelayBuilder,
+		command_consumer: Consumer<Command>,
+		shared: Arc<EffectShared>,
+	) -> Self {
 		Self {
 			command_consumer,
+			shared,
 			delay_time: Tweener::new(builder.delay_time),
 			feedback: Tweener::new(builder.feedback),
 			mix: Tweener::new(builder.mix),
+			mode: builder.mode,
 			state: DelayState::Uninitialized {
 				buffer_length: builder.buffer_length,
 			},
@@ -60,6 +92,20 @@ impl Delay {
 }
 
 impl Effect for Delay {
+	fn on_removed(&mut self) {
+		self.shared.mark_as_removed();
+		for effect in &mut self.feedback_effects {
+			effect.on_removed();
+		}
+	}
+
+	fn set_random_seed(&mut self, seed: u64) {
+		let mut random = Random::new(seed);
+		for effect in &mut self.feedback_effects {
+			effect.set_random_seed(random.next_u64());
+		}
+	}
+
 	fn init(&mut self, sample_rate: u32) {
 		if let DelayState::Uninitialized { buffer_length } = &self.state {
 			self.state = DelayState::Initialized {
@@ -143,10 +189,18 @@ impl Effect for Delay {
 				output = effect.process(output, dt);
 			}
 
-			// write output audio to the buffer
+			// write output audio to the buffer. feedback is limited
+			// to 1.0 so the echoes can never grow louder forever
 			*write_position += 1;
 			*write_position %= buffer.len();
-			buffer[*write_position] = input + output * self.feedback.value().as_amplitude() as f32;
+			let feedback = (self.feedback.value().as_amplitude() as f32).clamp(-1.0, 1.0);
+			let delay_input = input + output * feedback;
+			buffer[*write_position] = match self.mode {
+				DelayMode::Mono => Frame::from_mono((delay_input.left + delay_input.right) / 2.0),
+				DelayMode::Stereo => delay_input,
+				DelayMode::PingPong => Frame::new(delay_input.right, delay_input.left),
+			}
+			.flush_denormals();
 
 			let mix = self.mix.value() as f32;
 			output * mix.sqrt() + input * (1.0 - mix).sqrt()
@@ -163,4 +217,17 @@ impl Effect for Delay {
 			effect.on_clock_tick(time);
 		}
 	}
+
+	fn tail_frames(&self) -> usize {
+		if let DelayState::Initialized { buffer, .. } = &self.state {
+			buffer.len()
+				+ self
+					.feedback_effects
+					.iter()
+					.map(|effect| effect.tail_frames())
+					.sum::<usize>()
+		} else {
+			0
+		}
+	}
 }
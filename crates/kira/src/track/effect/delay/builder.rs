@@ -1,11 +1,13 @@
+use std::sync::Arc;
+
 use ringbuf::RingBuffer;
 
 use crate::{
-	track::effect::{Effect, EffectBuilder},
+	track::effect::{Effect, EffectBuilder, EffectShared},
 	Volume,
 };
 
-use super::{Delay, DelayHandle};
+use super::{Delay, DelayHandle, DelayMode};
 
 const COMMAND_CAPACITY: usize = 8;
 
@@ -15,6 +17,8 @@ pub struct DelayBuilder {
 	/// The delay time (in seconds).
 	pub(super) delay_time: f64,
 	/// The amount of feedback.
+	///
+	/// Feedback amplitudes greater than `1.0` are treated as `1.0`.
 	pub(super) feedback: Volume,
 	/// The amount of audio the delay can store (in seconds).
 	/// This affects the maximum delay time.
@@ -26,6 +30,8 @@ pub struct DelayBuilder {
 	/// only the dry signal will be heard. `1.0` means
 	/// only the wet signal will be heard.
 	pub(super) mix: f64,
+	/// How the left and right channels are handled.
+	pub(super) mode: DelayMode,
 }
 
 impl DelayBuilder {
@@ -47,6 +53,11 @@ impl DelayBuilder {
 		}
 	}
 
+	/// Sets how the left and right channels are handled.
+	pub fn mode(self, mode: DelayMode) -> Self {
+		Self { mode, ..self }
+	}
+
 	/// Sets the amount of audio the delay can store.
 	pub fn buffer_length(self, buffer_length: f64) -> Self {
 		Self {
@@ -79,6 +90,7 @@ impl Default for DelayBuilder {
 			buffer_length: 10.0,
 			feedback_effects: vec![],
 			mix: 0.5,
+			mode: DelayMode::Stereo,
 		}
 	}
 }
@@ -86,11 +98,19 @@ impl Default for DelayBuilder {
 impl EffectBuilder for DelayBuilder {
 	type Handle = DelayHandle;
 
+	fn name(&self) -> &'static str {
+		"Delay"
+	}
+
 	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
 		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_CAPACITY).split();
+		let shared = Arc::new(EffectShared::new());
 		(
-			Box::new(Delay::new(self, command_consumer)),
-			DelayHandle { command_producer },
+			Box::new(Delay::new(self, command_consumer, shared.clone())),
+			DelayHandle {
+				command_producer,
+				shared,
+			},
 		)
 	}
 }
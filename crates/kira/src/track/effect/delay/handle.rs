@@ -1,27 +1,26 @@
+use std::sync::Arc;
+
 use ringbuf::Producer;
 
-use crate::{tween::Tween, CommandError, Volume};
+use crate::{track::effect::EffectShared, tween::Tween, CommandError, Volume};
 
 use super::Command;
 
 /// Controls a delay effect.
 pub struct DelayHandle {
 	pub(super) command_producer: Producer<Command>,
+	pub(super) shared: Arc<EffectShared>,
 }
 
 impl DelayHandle {
 	/// Sets the delay time (in seconds).
 	pub fn set_delay_time(&mut self, delay_time: f64, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetDelayTime(delay_time, tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetDelayTime(delay_time, tween))
 	}
 
 	/// Sets the amount of feedback.
 	pub fn set_feedback(&mut self, feedback: impl Into<Volume>, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetFeedback(feedback.into(), tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetFeedback(feedback.into(), tween))
 	}
 
 	/// Sets how much dry (unprocessed) signal should be blended
@@ -29,8 +28,15 @@ impl DelayHandle {
 	/// signal will be heard. `1.0` means only the wet signal will
 	/// be heard.
 	pub fn set_mix(&mut self, mix: f64, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::SetMix(mix, tween))
+	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		if self.shared.is_removed() {
+			return Err(CommandError::ResourceRemoved);
+		}
 		self.command_producer
-			.push(Command::SetMix(mix, tween))
+			.push(command)
 			.map_err(|_| CommandError::CommandQueueFull)
 	}
 }
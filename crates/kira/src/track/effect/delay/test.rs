@@ -0,0 +1,70 @@
+use crate::{dsp::Frame, track::effect::EffectBuilder, Volume};
+
+use super::{DelayBuilder, DelayMode};
+
+const SAMPLE_RATE: u32 = 10;
+
+/// Sends a single frame of audio through a delay effect
+/// with a delay time of 5 frames and returns the output.
+///
+/// The echoes will be heard every 6 frames, since the delay
+/// reads from its buffer before writing the current frame.
+fn render_impulse(builder: DelayBuilder, impulse: Frame, num_frames: usize) -> Vec<Frame> {
+	let (mut effect, _) = builder.delay_time(0.5).buffer_length(2.0).mix(1.0).build();
+	effect.init(SAMPLE_RATE);
+	let dt = 1.0 / SAMPLE_RATE as f64;
+	(0..num_frames)
+		.map(|i| {
+			let input = if i == 0 { impulse } else { Frame::ZERO };
+			effect.process(input, dt)
+		})
+		.collect()
+}
+
+/// Tests that the echoes of a ping-pong delay alternate
+/// between the left and right channels.
+#[test]
+#[allow(clippy::float_cmp)]
+fn ping_pong_alternates_channels() {
+	let output = render_impulse(
+		DelayBuilder::new()
+			.mode(DelayMode::PingPong)
+			.feedback(Volume::Amplitude(0.5)),
+		Frame::new(1.0, 0.0),
+		13,
+	);
+	// the first echo should be on the opposite channel from the input
+	assert_eq!(output[6], Frame::new(0.0, 1.0));
+	// the second echo should come back to the original channel
+	assert_eq!(output[12], Frame::new(0.5, 0.0));
+}
+
+/// Tests that a mono delay mixes the left and right channels.
+#[test]
+#[allow(clippy::float_cmp)]
+fn mono_mixes_channels() {
+	let output = render_impulse(
+		DelayBuilder::new().mode(DelayMode::Mono),
+		Frame::new(1.0, 0.0),
+		7,
+	);
+	assert_eq!(output[6], Frame::from_mono(0.5));
+}
+
+/// Tests that feedback amounts greater than 1.0 don't cause
+/// the echoes to keep getting louder.
+#[test]
+fn limits_feedback() {
+	for mode in [DelayMode::Mono, DelayMode::Stereo, DelayMode::PingPong] {
+		let output = render_impulse(
+			DelayBuilder::new()
+				.mode(mode)
+				.feedback(Volume::Amplitude(2.0)),
+			Frame::new(1.0, 0.0),
+			1000,
+		);
+		for frame in output {
+			assert!(frame.left.abs() <= 1.0 && frame.right.abs() <= 1.0);
+		}
+	}
+}
@@ -2,20 +2,29 @@
 
 mod builder;
 mod handle;
+mod oversampler;
+
+#[cfg(test)]
+mod test;
 
 pub use builder::*;
 pub use handle::*;
+pub use oversampler::Oversampling;
+
+use std::sync::Arc;
 
 use ringbuf::Consumer;
 
 use crate::{
 	clock::ClockTime,
 	dsp::Frame,
-	track::Effect,
+	track::{effect::EffectShared, Effect},
 	tween::{Tween, Tweener},
 	Volume,
 };
 
+use self::oversampler::Oversampler;
+
 enum Command {
 	SetKind(DistortionKind),
 	SetDrive(Volume, Tween),
@@ -38,6 +47,34 @@ pub enum DistortionKind {
 	/// This creates a smoother distortion that gradually
 	/// becomes more prominent as the signal becomes louder.
 	SoftClip,
+	/// When the signal leaves the -1.0 to 1.0 range, it will
+	/// be reflected back into the range.
+	///
+	/// This creates a metallic distortion that becomes
+	/// more complex as the drive increases.
+	Foldback,
+	/// The signal will be clamped to the -1.0 to 1.0 range
+	/// and quantized to the given number of bits.
+	///
+	/// This creates a gritty, lo-fi distortion. Lower bit
+	/// depths are more distorted. The bit depth is clamped
+	/// to the 1 to 24 range, since 32-bit floats can't
+	/// represent any finer steps than 24 bits.
+	Bitcrush(u32),
+}
+
+impl DistortionKind {
+	fn distort(self, x: f32) -> f32 {
+		match self {
+			DistortionKind::HardClip => x.clamp(-1.0, 1.0),
+			DistortionKind::SoftClip => x / (1.0 + x.abs()),
+			DistortionKind::Foldback => 1.0 - ((x + 1.0).rem_euclid(4.0) - 2.0).abs(),
+			DistortionKind::Bitcrush(bits) => {
+				let levels = 2.0f32.powi(bits.clamp(1, 24) as i32 - 1);
+				(x.clamp(-1.0, 1.0) * levels).round() / levels
+			}
+		}
+	}
 }
 
 impl Default for DistortionKind {
@@ -48,12 +85,18 @@ impl Default for DistortionKind {
 
 struct Distortion {
 	command_consumer: Consumer<Command>,
+	shared: Arc<EffectShared>,
 	kind: DistortionKind,
 	drive: Tweener<Volume>,
 	mix: Tweener,
+	oversampler: Oversampler,
 }
 
 impl Effect for Distortion {
+	fn on_removed(&mut self) {
+		self.shared.mark_as_removed();
+	}
+
 	fn on_start_processing(&mut self) {
 		while let Some(command) = self.command_consumer.pop() {
 			match command {
@@ -68,21 +111,21 @@ impl Effect for Distortion {
 		self.drive.update(dt);
 		self.mix.update(dt);
 		let drive = self.drive.value().as_amplitude() as f32;
-		let mut output = input * drive;
-		output = match self.kind {
-			DistortionKind::HardClip => Frame::new(
-				output.left.max(-1.0).min(1.0),
-				output.right.max(-1.0).min(1.0),
-			),
-			DistortionKind::SoftClip => Frame::new(
-				output.left / (1.0 + output.left.abs()),
-				output.right / (1.0 + output.right.abs()),
-			),
-		};
-		output /= drive;
+		let kind = self.kind;
+		let output = self.oversampler.process(input, |input| {
+			let driven = input * drive;
+			Frame::new(kind.distort(driven.left), kind.distort(driven.right)) / drive
+		});
+		// when oversampling, the output is delayed, so the dry
+		// signal needs to be delayed by the same amount
+		let dry = self.oversampler.delayed_input();
 
 		let mix = self.mix.value() as f32;
-		output * mix.sqrt() + input * (1.0 - mix).sqrt()
+		output * mix.sqrt() + dry * (1.0 - mix).sqrt()
+	}
+
+	fn latency_frames(&self) -> usize {
+		self.oversampler.latency_frames()
 	}
 
 	fn on_clock_tick(&mut self, time: ClockTime) {
@@ -1,12 +1,16 @@
+use std::sync::Arc;
+
 use ringbuf::RingBuffer;
 
 use crate::{
-	track::effect::{Effect, EffectBuilder},
+	track::effect::{Effect, EffectBuilder, EffectShared},
 	tween::Tweener,
 	Volume,
 };
 
-use super::{handle::DistortionHandle, Distortion, DistortionKind};
+use super::{
+	handle::DistortionHandle, oversampler::Oversampler, Distortion, DistortionKind, Oversampling,
+};
 
 const COMMAND_CAPACITY: usize = 8;
 
@@ -24,6 +28,12 @@ pub struct DistortionBuilder {
 	/// only the dry signal will be heard. `1.0` means
 	/// only the wet signal will be heard.
 	pub mix: f64,
+	/// How many times the audio should be upsampled before
+	/// applying the distortion. Higher values reduce aliasing
+	/// at the cost of more CPU time.
+	///
+	/// This cannot be changed after the effect is created.
+	pub oversampling: Oversampling,
 }
 
 impl DistortionBuilder {
@@ -53,6 +63,15 @@ impl DistortionBuilder {
 	pub fn mix(self, mix: f64) -> Self {
 		Self { mix, ..self }
 	}
+
+	/// Sets how many times the audio should be upsampled before
+	/// applying the distortion.
+	pub fn oversampling(self, oversampling: Oversampling) -> Self {
+		Self {
+			oversampling,
+			..self
+		}
+	}
 }
 
 impl Default for DistortionBuilder {
@@ -61,6 +80,7 @@ impl Default for DistortionBuilder {
 			kind: Default::default(),
 			drive: Volume::Amplitude(1.0),
 			mix: 1.0,
+			oversampling: Oversampling::X1,
 		}
 	}
 }
@@ -68,16 +88,26 @@ impl Default for DistortionBuilder {
 impl EffectBuilder for DistortionBuilder {
 	type Handle = DistortionHandle;
 
+	fn name(&self) -> &'static str {
+		"Distortion"
+	}
+
 	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
 		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_CAPACITY).split();
+		let shared = Arc::new(EffectShared::new());
 		(
 			Box::new(Distortion {
 				command_consumer,
+				shared: shared.clone(),
 				kind: self.kind,
 				drive: Tweener::new(self.drive),
 				mix: Tweener::new(self.mix),
+				oversampler: Oversampler::new(self.oversampling),
 			}),
-			DistortionHandle { command_producer },
+			DistortionHandle {
+				command_producer,
+				shared,
+			},
 		)
 	}
 }
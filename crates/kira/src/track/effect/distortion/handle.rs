@@ -1,20 +1,21 @@
+use std::sync::Arc;
+
 use ringbuf::Producer;
 
-use crate::{tween::Tween, CommandError, Volume};
+use crate::{track::effect::EffectShared, tween::Tween, CommandError, Volume};
 
 use super::{Command, DistortionKind};
 
 /// Controls a distortion effect.
 pub struct DistortionHandle {
 	pub(super) command_producer: Producer<Command>,
+	pub(super) shared: Arc<EffectShared>,
 }
 
 impl DistortionHandle {
 	/// Sets the kind of distortion to use.
 	pub fn set_kind(&mut self, kind: DistortionKind) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetKind(kind))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetKind(kind))
 	}
 
 	/// Sets how much distortion should be applied.
@@ -23,9 +24,7 @@ impl DistortionHandle {
 		drive: impl Into<Volume>,
 		tween: Tween,
 	) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetDrive(drive.into(), tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetDrive(drive.into(), tween))
 	}
 
 	/// Sets how much dry (unprocessed) signal should be blended
@@ -33,8 +32,15 @@ impl DistortionHandle {
 	/// signal will be heard. `1.0` means only the wet signal will
 	/// be heard.
 	pub fn set_mix(&mut self, mix: f64, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::SetMix(mix, tween))
+	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		if self.shared.is_removed() {
+			return Err(CommandError::ResourceRemoved);
+		}
 		self.command_producer
-			.push(Command::SetMix(mix, tween))
+			.push(command)
 			.map_err(|_| CommandError::CommandQueueFull)
 	}
 }
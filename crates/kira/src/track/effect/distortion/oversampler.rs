@@ -0,0 +1,128 @@
+use std::f64::consts::PI;
+
+use crate::dsp::{interpolate_frame, Frame};
+
+/// How many times the audio should be upsampled before
+/// applying the distortion.
+///
+/// Distortion adds harmonics to the signal, and harmonics above
+/// the Nyquist frequency (half the sample rate) fold back down
+/// into the audible range as harsh, inharmonic noise (aliasing).
+/// Processing the distortion at a higher sample rate and filtering
+/// out the highest frequencies before downsampling reduces this
+/// aliasing at the cost of more CPU time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Oversampling {
+	/// The distortion will be applied at the original sample rate.
+	X1,
+	/// The distortion will be applied at twice the sample rate.
+	X2,
+	/// The distortion will be applied at four times the sample rate.
+	X4,
+}
+
+impl Oversampling {
+	fn factor(self) -> usize {
+		match self {
+			Oversampling::X1 => 1,
+			Oversampling::X2 => 2,
+			Oversampling::X4 => 4,
+		}
+	}
+}
+
+/// The cutoff of the anti-aliasing filter, as a fraction
+/// of the original sample rate.
+const CUTOFF: f64 = 0.45;
+
+/// A 2-pole lowpass state variable filter.
+struct Lowpass {
+	a1: f32,
+	a2: f32,
+	a3: f32,
+	ic1eq: Frame,
+	ic2eq: Frame,
+}
+
+impl Lowpass {
+	fn new(relative_cutoff: f64) -> Self {
+		let g = (PI * relative_cutoff).tan();
+		// a butterworth response, so the passband stays flat
+		let k = std::f64::consts::SQRT_2;
+		let a1 = 1.0 / (1.0 + (g * (g + k)));
+		let a2 = g * a1;
+		let a3 = g * a2;
+		Self {
+			a1: a1 as f32,
+			a2: a2 as f32,
+			a3: a3 as f32,
+			ic1eq: Frame::ZERO,
+			ic2eq: Frame::ZERO,
+		}
+	}
+
+	fn process(&mut self, input: Frame) -> Frame {
+		let v3 = input - self.ic2eq;
+		let v1 = (self.ic1eq * self.a1) + (v3 * self.a2);
+		let v2 = self.ic2eq + (self.ic1eq * self.a2) + (v3 * self.a3);
+		self.ic1eq = ((v1 * 2.0) - self.ic1eq).flush_denormals();
+		self.ic2eq = ((v2 * 2.0) - self.ic2eq).flush_denormals();
+		v2
+	}
+}
+
+/// Runs a nonlinear function at a multiple of the sample rate.
+///
+/// When oversampling, the output is delayed by one frame.
+pub(super) struct Oversampler {
+	factor: usize,
+	input_history: [Frame; 4],
+	lowpasses: [Lowpass; 2],
+}
+
+impl Oversampler {
+	pub fn new(oversampling: Oversampling) -> Self {
+		let factor = oversampling.factor();
+		let relative_cutoff = CUTOFF / factor as f64;
+		Self {
+			factor,
+			input_history: [Frame::ZERO; 4],
+			lowpasses: [Lowpass::new(relative_cutoff), Lowpass::new(relative_cutoff)],
+		}
+	}
+
+	/// How many frames the output is delayed by.
+	pub fn latency_frames(&self) -> usize {
+		if self.factor == 1 {
+			0
+		} else {
+			1
+		}
+	}
+
+	/// Returns the input frame that lines up with the most
+	/// recent output of [`Oversampler::process`].
+	pub fn delayed_input(&self) -> Frame {
+		self.input_history[3 - self.latency_frames()]
+	}
+
+	pub fn process(&mut self, input: Frame, mut f: impl FnMut(Frame) -> Frame) -> Frame {
+		self.input_history.rotate_left(1);
+		self.input_history[3] = input;
+		if self.factor == 1 {
+			return f(input);
+		}
+		let [previous, current, next_1, next_2] = self.input_history;
+		let mut output = Frame::ZERO;
+		for i in 1..=self.factor {
+			let fraction = i as f32 / self.factor as f32;
+			let upsampled = interpolate_frame(previous, current, next_1, next_2, fraction);
+			output = f(upsampled);
+			for lowpass in &mut self.lowpasses {
+				output = lowpass.process(output);
+			}
+		}
+		output
+	}
+}
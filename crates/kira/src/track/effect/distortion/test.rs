@@ -0,0 +1,148 @@
+use crate::{dsp::Frame, track::effect::EffectBuilder, Volume};
+
+use super::{DistortionBuilder, DistortionKind, Oversampling};
+
+/// Feeds a ramp from -4.0 to 4.0 through a distortion effect
+/// with no drive and returns the output for the left channel.
+fn ramp_through(kind: DistortionKind) -> Vec<f32> {
+	let (mut effect, _) = DistortionBuilder::new().kind(kind).mix(1.0).build();
+	effect.init(1);
+	(0..=800)
+		.map(|i| {
+			let x = i as f32 / 100.0 - 4.0;
+			effect.process(Frame::from_mono(x), 1.0).left
+		})
+		.collect()
+}
+
+fn is_monotonic(output: &[f32]) -> bool {
+	output.windows(2).all(|pair| pair[1] >= pair[0])
+}
+
+fn is_in_range(output: &[f32]) -> bool {
+	output.iter().all(|sample| (-1.0..=1.0).contains(sample))
+}
+
+#[test]
+fn hard_clip() {
+	let output = ramp_through(DistortionKind::HardClip);
+	assert!(is_monotonic(&output));
+	assert!(is_in_range(&output));
+	assert_eq!(output[0], -1.0);
+	assert_eq!(output[800], 1.0);
+	// signals in range should not be changed
+	assert!((output[450] - 0.5).abs() < 0.0001);
+}
+
+#[test]
+fn soft_clip() {
+	let output = ramp_through(DistortionKind::SoftClip);
+	assert!(is_monotonic(&output));
+	assert!(is_in_range(&output));
+	// the signal should approach, but never reach, -1.0 and 1.0
+	assert!(output[0] > -1.0 && output[0] < -0.75);
+	assert!(output[800] < 1.0 && output[800] > 0.75);
+}
+
+#[test]
+fn foldback() {
+	let output = ramp_through(DistortionKind::Foldback);
+	assert!(is_in_range(&output));
+	// signals in range should not be changed
+	assert!(is_monotonic(&output[300..=500]));
+	assert!((output[450] - 0.5).abs() < 0.0001);
+	// signals out of range should be reflected back into the range
+	assert!(!is_monotonic(&output));
+	assert!((output[550] - 0.5).abs() < 0.0001);
+	assert!((output[250] + 0.5).abs() < 0.0001);
+}
+
+#[test]
+fn bitcrush() {
+	let output = ramp_through(DistortionKind::Bitcrush(2));
+	assert!(is_monotonic(&output));
+	assert!(is_in_range(&output));
+	// 2 bits should leave only 5 possible output values
+	for sample in &output {
+		assert!([-1.0, -0.5, 0.0, 0.5, 1.0].contains(sample));
+	} // very large bit depths are clamped instead of
+   // overflowing to infinity
+	for bits in [129, u32::MAX] {
+		let output = ramp_through(DistortionKind::Bitcrush(bits));
+		assert!(output.iter().all(|sample| sample.is_finite()));
+		assert!(is_monotonic(&output));
+		assert!(is_in_range(&output));
+	}
+}
+
+/// Returns the magnitude of a frequency in a signal.
+fn magnitude(signal: &[f32], frequency: f64, sample_rate: f64) -> f64 {
+	let (mut real, mut imaginary) = (0.0, 0.0);
+	for (i, sample) in signal.iter().enumerate() {
+		let phase = std::f64::consts::TAU * frequency * i as f64 / sample_rate;
+		real += *sample as f64 * phase.cos();
+		imaginary += *sample as f64 * phase.sin();
+	}
+	(real * real + imaginary * imaginary).sqrt() / signal.len() as f64
+}
+
+/// Tests that oversampling reduces the energy of harmonics that
+/// fold back below the Nyquist frequency.
+#[test]
+fn oversampling_reduces_aliasing() {
+	const SAMPLE_RATE: u32 = 48_000;
+	const FREQUENCY: f64 = 7_000.0;
+	const NUM_FRAMES: usize = 4_800;
+
+	fn render(oversampling: Oversampling) -> Vec<f32> {
+		let (mut effect, _) = DistortionBuilder::new()
+			.kind(DistortionKind::HardClip)
+			.drive(Volume::Decibels(24.0))
+			.mix(1.0)
+			.oversampling(oversampling)
+			.build();
+		effect.init(SAMPLE_RATE);
+		let dt = 1.0 / SAMPLE_RATE as f64;
+		(0..NUM_FRAMES * 2)
+			.map(|i| {
+				let x = (std::f64::consts::TAU * FREQUENCY * i as f64 * dt).sin() as f32;
+				effect.process(Frame::from_mono(x), dt).left
+			})
+			// skip the beginning while the filters settle
+			.skip(NUM_FRAMES)
+			.collect()
+	}
+
+	// hard clipping a 7 kHz sine produces odd harmonics at 21 kHz,
+	// 35 kHz, 49 kHz, 63 kHz, etc. all but the first are above
+	// the Nyquist frequency and alias down to other frequencies
+	let aliased_frequencies = [13_000.0, 1_000.0, 15_000.0, 9_000.0, 17_000.0];
+	let aliasing = |output: &[f32]| -> f64 {
+		aliased_frequencies
+			.iter()
+			.map(|frequency| magnitude(output, *frequency, SAMPLE_RATE as f64))
+			.sum()
+	};
+	let fundamental = |output: &[f32]| magnitude(output, FREQUENCY, SAMPLE_RATE as f64);
+	let x1 = render(Oversampling::X1);
+	let x2 = render(Oversampling::X2);
+	let x4 = render(Oversampling::X4);
+	assert!(aliasing(&x2) < aliasing(&x1) * 0.5);
+	assert!(aliasing(&x4) < aliasing(&x1) * 0.25);
+	// the lowpass filter shouldn't affect the fundamental frequency
+	assert!((fundamental(&x4) / fundamental(&x1) - 1.0).abs() < 0.05);
+}
+
+/// Tests that the distortion effect reports the delay
+/// introduced by oversampling.
+#[test]
+fn reports_latency_when_oversampling() {
+	for (oversampling, expected_latency) in [
+		(Oversampling::X1, 0),
+		(Oversampling::X2, 1),
+		(Oversampling::X4, 1),
+	] {
+		let (effect, _) = DistortionBuilder::new().oversampling(oversampling).build();
+		assert_eq!(effect.latency_frames(), expected_latency);
+	}
+}
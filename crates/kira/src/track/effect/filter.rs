@@ -7,18 +7,20 @@ pub use builder::*;
 pub use handle::*;
 use ringbuf::Consumer;
 
-use std::f64::consts::PI;
+use std::{f64::consts::PI, sync::Arc};
 
 use crate::{
 	clock::ClockTime,
 	dsp::Frame,
-	track::Effect,
+	modulator::{ModulatorId, ModulatorMapping},
+	track::{effect::EffectShared, Effect},
 	tween::{Tween, Tweener},
 };
 
 enum Command {
 	SetMode(FilterMode),
 	SetCutoff(f64, Tween),
+	SetCutoffModulator(ModulatorId, ModulatorMapping),
 	SetResonance(f64, Tween),
 	SetMix(f64, Tween),
 }
@@ -41,8 +43,10 @@ pub enum FilterMode {
 
 struct Filter {
 	command_consumer: Consumer<Command>,
+	shared: Arc<EffectShared>,
 	mode: FilterMode,
 	cutoff: Tweener,
+	cutoff_modulator: Option<(ModulatorId, ModulatorMapping)>,
 	resonance: Tweener,
 	mix: Tweener,
 	ic1eq: Frame,
@@ -51,11 +55,17 @@ struct Filter {
 
 impl Filter {
 	/// Creates a new filter.
-	fn new(builder: FilterBuilder, command_consumer: Consumer<Command>) -> Self {
+	fn new(
+		builder: FilterBuilder,
+		command_consumer: Consumer<Command>,
+		shared: Arc<EffectShared>,
+	) -> Self {
 		Self {
 			command_consumer,
+			shared,
 			mode: builder.mode,
 			cutoff: Tweener::new(builder.cutoff),
+			cutoff_modulator: None,
 			resonance: Tweener::new(builder.resonance),
 			mix: Tweener::new(builder.mix),
 			ic1eq: Frame::ZERO,
@@ -65,11 +75,21 @@ impl Filter {
 }
 
 impl Effect for Filter {
+	fn on_removed(&mut self) {
+		self.shared.mark_as_removed();
+	}
+
 	fn on_start_processing(&mut self) {
 		while let Some(command) = self.command_consumer.pop() {
 			match command {
 				Command::SetMode(mode) => self.mode = mode,
-				Command::SetCutoff(cutoff, tween) => self.cutoff.set(cutoff, tween),
+				Command::SetCutoff(cutoff, tween) => {
+					self.cutoff.set(cutoff, tween);
+					self.cutoff_modulator = None;
+				}
+				Command::SetCutoffModulator(id, mapping) => {
+					self.cutoff_modulator = Some((id, mapping))
+				}
 				Command::SetResonance(resonance, tween) => self.resonance.set(resonance, tween),
 				Command::SetMix(mix, tween) => self.mix.set(mix, tween),
 			}
@@ -81,7 +101,11 @@ impl Effect for Filter {
 		self.resonance.update(dt);
 		self.mix.update(dt);
 		let sample_rate = 1.0 / dt;
-		let g = (PI * (self.cutoff.value() / sample_rate)).tan();
+		let cutoff = match &self.cutoff_modulator {
+			Some((id, mapping)) => mapping.map(id.value()),
+			None => self.cutoff.value(),
+		};
+		let g = (PI * (cutoff / sample_rate)).tan();
 		let k = 2.0 - (1.9 * self.resonance.value().min(1.0).max(0.0));
 		let a1 = 1.0 / (1.0 + (g * (g + k)));
 		let a2 = g * a1;
@@ -89,8 +113,8 @@ impl Effect for Filter {
 		let v3 = input - self.ic2eq;
 		let v1 = (self.ic1eq * (a1 as f32)) + (v3 * (a2 as f32));
 		let v2 = self.ic2eq + (self.ic1eq * (a2 as f32)) + (v3 * (a3 as f32));
-		self.ic1eq = (v1 * 2.0) - self.ic1eq;
-		self.ic2eq = (v2 * 2.0) - self.ic2eq;
+		self.ic1eq = ((v1 * 2.0) - self.ic1eq).flush_denormals();
+		self.ic2eq = ((v2 * 2.0) - self.ic2eq).flush_denormals();
 		let output = match self.mode {
 			FilterMode::LowPass => v2,
 			FilterMode::BandPass => v1,
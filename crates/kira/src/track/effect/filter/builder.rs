@@ -1,6 +1,8 @@
+use std::sync::Arc;
+
 use ringbuf::RingBuffer;
 
-use crate::track::effect::{Effect, EffectBuilder};
+use crate::track::effect::{Effect, EffectBuilder, EffectShared};
 
 use super::{Filter, FilterHandle, FilterMode};
 
@@ -70,11 +72,19 @@ impl Default for FilterBuilder {
 impl EffectBuilder for FilterBuilder {
 	type Handle = FilterHandle;
 
+	fn name(&self) -> &'static str {
+		"Filter"
+	}
+
 	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
 		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_CAPACITY).split();
+		let shared = Arc::new(EffectShared::new());
 		(
-			Box::new(Filter::new(self, command_consumer)),
-			FilterHandle { command_producer },
+			Box::new(Filter::new(self, command_consumer, shared.clone())),
+			FilterHandle {
+				command_producer,
+				shared,
+			},
 		)
 	}
 }
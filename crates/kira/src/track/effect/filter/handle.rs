@@ -1,34 +1,49 @@
+use std::sync::Arc;
+
 use ringbuf::Producer;
 
-use crate::{tween::Tween, CommandError};
+use crate::{
+	modulator::{ModulatorId, ModulatorMapping},
+	track::effect::EffectShared,
+	tween::Tween,
+	CommandError,
+};
 
 use super::{Command, FilterMode};
 
 /// Controls a filter effect.
 pub struct FilterHandle {
 	pub(super) command_producer: Producer<Command>,
+	pub(super) shared: Arc<EffectShared>,
 }
 
 impl FilterHandle {
 	/// Sets the frequencies that the filter will remove.
 	pub fn set_mode(&mut self, mode: FilterMode) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetMode(mode))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetMode(mode))
 	}
 
 	/// Sets the cutoff frequency of the filter (in hertz).
 	pub fn set_cutoff(&mut self, cutoff: f64, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetCutoff(cutoff, tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetCutoff(cutoff, tween))
+	}
+
+	/// Links the cutoff frequency of the filter (in hertz) to the
+	/// output of a modulator.
+	///
+	/// The link is removed the next time [`set_cutoff`](Self::set_cutoff)
+	/// is called.
+	pub fn set_cutoff_modulator(
+		&mut self,
+		modulator: impl Into<ModulatorId>,
+		mapping: ModulatorMapping,
+	) -> Result<(), CommandError> {
+		self.push_command(Command::SetCutoffModulator(modulator.into(), mapping))
 	}
 
 	/// Sets the resonance of the filter.
 	pub fn set_resonance(&mut self, resonance: f64, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetResonance(resonance, tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetResonance(resonance, tween))
 	}
 
 	/// Sets how much dry (unprocessed) signal should be blended
@@ -36,8 +51,15 @@ impl FilterHandle {
 	/// signal will be heard. `1.0` means only the wet signal will
 	/// be heard.
 	pub fn set_mix(&mut self, mix: f64, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::SetMix(mix, tween))
+	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		if self.shared.is_removed() {
+			return Err(CommandError::ResourceRemoved);
+		}
 		self.command_producer
-			.push(Command::SetMix(mix, tween))
+			.push(command)
 			.map_err(|_| CommandError::CommandQueueFull)
 	}
 }
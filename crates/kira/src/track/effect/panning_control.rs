@@ -3,49 +3,79 @@
 mod builder;
 mod handle;
 
+#[cfg(test)]
+mod test;
+
 pub use builder::*;
 pub use handle::*;
 
+use std::sync::Arc;
+
 use ringbuf::Consumer;
 
 use crate::{
 	clock::ClockTime,
-	dsp::Frame,
+	dsp::{Frame, Lfo, LfoSettings, PanningLaw},
 	tween::{Tween, Tweener},
 };
 
-use super::Effect;
+use super::{Effect, EffectShared};
 
 enum Command {
 	SetPanning(f64, Tween),
+	SetLfo(LfoSettings),
+	RemoveLfo,
 }
 
 struct PanningControl {
 	command_consumer: Consumer<Command>,
+	shared: Arc<EffectShared>,
 	panning: Tweener,
+	panning_law: PanningLaw,
+	lfo: Lfo,
+	lfo_settings: Option<LfoSettings>,
 }
 
 impl PanningControl {
-	fn new(builder: PanningControlBuilder, command_consumer: Consumer<Command>) -> Self {
+	fn new(
+		builder: PanningControlBuilder,
+		command_consumer: Consumer<Command>,
+		shared: Arc<EffectShared>,
+	) -> Self {
 		Self {
 			command_consumer,
-			panning: Tweener::new(builder.0),
+			shared,
+			panning: Tweener::new(builder.panning),
+			panning_law: builder.panning_law,
+			lfo: Lfo::new(),
+			lfo_settings: None,
 		}
 	}
 }
 
 impl Effect for PanningControl {
+	fn on_removed(&mut self) {
+		self.shared.mark_as_removed();
+	}
+
 	fn on_start_processing(&mut self) {
 		while let Some(command) = self.command_consumer.pop() {
 			match command {
 				Command::SetPanning(panning, tween) => self.panning.set(panning, tween),
+				Command::SetLfo(lfo_settings) => self.lfo_settings = Some(lfo_settings),
+				Command::RemoveLfo => self.lfo_settings = None,
 			}
 		}
 	}
 
 	fn process(&mut self, input: Frame, dt: f64) -> Frame {
 		self.panning.update(dt);
-		input.panned(self.panning.value() as f32)
+		let mut panning = self.panning.value();
+		if let Some(lfo_settings) = &self.lfo_settings {
+			self.lfo.update(lfo_settings.rate, dt);
+			panning = (panning + lfo_settings.offset(&self.lfo)).clamp(0.0, 1.0);
+		}
+		input.panned_with_law(panning as f32, self.panning_law)
 	}
 
 	fn on_clock_tick(&mut self, time: ClockTime) {
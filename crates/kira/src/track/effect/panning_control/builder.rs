@@ -1,6 +1,11 @@
+use std::sync::Arc;
+
 use ringbuf::RingBuffer;
 
-use crate::track::effect::EffectBuilder;
+use crate::{
+	dsp::PanningLaw,
+	track::effect::{EffectBuilder, EffectShared},
+};
 
 use super::{PanningControl, PanningControlHandle};
 
@@ -8,22 +13,60 @@ const COMMAND_CAPACITY: usize = 8;
 
 /// Configures a panning control effect.
 #[derive(Debug, Copy, Clone)]
-pub struct PanningControlBuilder(pub f64);
+#[non_exhaustive]
+pub struct PanningControlBuilder {
+	/// The panning to apply, where `0.0` is hard left,
+	/// `0.5` is center, and `1.0` is hard right.
+	pub panning: f64,
+	/// How the volume of the left and right channels
+	/// changes as the audio is panned.
+	pub panning_law: PanningLaw,
+}
+
+impl PanningControlBuilder {
+	/// Creates a new [`PanningControlBuilder`].
+	pub fn new(panning: f64) -> Self {
+		Self {
+			panning,
+			..Default::default()
+		}
+	}
+
+	/// Sets how the volume of the left and right channels
+	/// changes as the audio is panned.
+	pub fn panning_law(self, panning_law: PanningLaw) -> Self {
+		Self {
+			panning_law,
+			..self
+		}
+	}
+}
 
 impl Default for PanningControlBuilder {
 	fn default() -> Self {
-		Self(0.5)
+		Self {
+			panning: 0.5,
+			panning_law: PanningLaw::ConstantPower,
+		}
 	}
 }
 
 impl EffectBuilder for PanningControlBuilder {
 	type Handle = PanningControlHandle;
 
+	fn name(&self) -> &'static str {
+		"Panning control"
+	}
+
 	fn build(self) -> (Box<dyn crate::track::effect::Effect>, Self::Handle) {
 		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_CAPACITY).split();
+		let shared = Arc::new(EffectShared::new());
 		(
-			Box::new(PanningControl::new(self, command_consumer)),
-			PanningControlHandle { command_producer },
+			Box::new(PanningControl::new(self, command_consumer, shared.clone())),
+			PanningControlHandle {
+				command_producer,
+				shared,
+			},
 		)
 	}
 }
@@ -1,19 +1,49 @@
+use std::sync::Arc;
+
 use ringbuf::Producer;
 
-use crate::{tween::Tween, CommandError};
+use crate::{
+	dsp::{LfoSettings, LfoShape},
+	track::effect::EffectShared,
+	tween::Tween,
+	CommandError,
+};
 
 use super::Command;
 
 /// Controls a panning control effect.
 pub struct PanningControlHandle {
 	pub(super) command_producer: Producer<Command>,
+	pub(super) shared: Arc<EffectShared>,
 }
 
 impl PanningControlHandle {
 	/// Sets the panning adjustment to apply to input audio.
 	pub fn set_panning(&mut self, panning: f64, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::SetPanning(panning, tween))
+	}
+
+	/// Starts modulating the panning with a low frequency oscillator.
+	///
+	/// The oscillator's output (from `-depth` to `depth`) is added to
+	/// the panning set by [`set_panning`](Self::set_panning), and the
+	/// result is kept in the `0.0` to `1.0` range. `rate` is the
+	/// frequency of the oscillator (in hertz).
+	pub fn set_lfo(&mut self, rate: f64, depth: f64, shape: LfoShape) -> Result<(), CommandError> {
+		self.push_command(Command::SetLfo(LfoSettings { rate, depth, shape }))
+	}
+
+	/// Stops modulating the panning with a low frequency oscillator.
+	pub fn remove_lfo(&mut self) -> Result<(), CommandError> {
+		self.push_command(Command::RemoveLfo)
+	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		if self.shared.is_removed() {
+			return Err(CommandError::ResourceRemoved);
+		}
 		self.command_producer
-			.push(Command::SetPanning(panning, tween))
+			.push(command)
 			.map_err(|_| CommandError::CommandQueueFull)
 	}
 }
@@ -0,0 +1,55 @@
+use crate::{
+	dsp::{Frame, LfoShape, PanningLaw},
+	track::effect::EffectBuilder,
+};
+
+use super::PanningControlBuilder;
+
+/// Tests that an LFO is added to the base panning of
+/// a panning control effect, and that the result is kept
+/// in the 0.0 to 1.0 range.
+#[test]
+#[allow(clippy::float_cmp)]
+fn lfo_modulates_panning() {
+	let (mut effect, mut handle) = PanningControlBuilder::new(0.5).build();
+	effect.init(4);
+	handle.set_lfo(1.0, 1.0, LfoShape::Square).unwrap();
+	effect.on_start_processing();
+	assert_eq!(
+		effect.process(Frame::from_mono(1.0), 0.25),
+		Frame::from_mono(1.0).panned(1.0)
+	);
+	assert_eq!(
+		effect.process(Frame::from_mono(1.0), 0.25),
+		Frame::from_mono(1.0).panned(0.0)
+	);
+}
+
+/// Tests that each panning law leaves centered audio unchanged,
+/// and compares how loud centered audio is to audio panned
+/// hard to one side.
+#[test]
+fn panning_laws() {
+	fn power(frame: Frame) -> f32 {
+		frame.left * frame.left + frame.right * frame.right
+	}
+
+	fn center_to_side_power_ratio(panning_law: PanningLaw) -> f32 {
+		let process = |panning| {
+			let (mut effect, _) = PanningControlBuilder::new(panning)
+				.panning_law(panning_law)
+				.build();
+			effect.init(1);
+			effect.process(Frame::from_mono(1.0), 1.0)
+		};
+		assert_eq!(process(0.5), Frame::from_mono(1.0));
+		power(process(0.5)) / power(process(0.0))
+	}
+
+	// constant power panning has no dip in the center
+	assert!((center_to_side_power_ratio(PanningLaw::ConstantPower) - 1.0).abs() < 1.0e-6);
+	// linear panning is 3dB quieter in the center
+	assert!((center_to_side_power_ratio(PanningLaw::Linear) - 0.5).abs() < 1.0e-6);
+	// the compromise panning law is 1.5dB quieter in the center
+	assert!((center_to_side_power_ratio(PanningLaw::Compromise) - 0.5f32.powf(0.5)).abs() < 1.0e-6);
+}
@@ -6,12 +6,14 @@ mod handle;
 pub use builder::*;
 pub use handle::*;
 
+use std::sync::Arc;
+
 use ringbuf::Consumer;
 
 use crate::{
 	clock::ClockTime,
 	dsp::Frame,
-	track::Effect,
+	track::{effect::EffectShared, Effect},
 	tween::{Tween, Tweener},
 };
 use all_pass::AllPassFilter;
@@ -45,6 +47,7 @@ enum ReverbState {
 // http://blog.bjornroche.com/2012/06/freeverb-original-public-domain-code-by.html
 struct Reverb {
 	command_consumer: Consumer<Command>,
+	shared: Arc<EffectShared>,
 	feedback: Tweener,
 	damping: Tweener,
 	stereo_width: Tweener,
@@ -54,9 +57,14 @@ struct Reverb {
 
 impl Reverb {
 	/// Creates a new `Reverb` effect.
-	fn new(settings: ReverbBuilder, command_consumer: Consumer<Command>) -> Self {
+	fn new(
+		settings: ReverbBuilder,
+		command_consumer: Consumer<Command>,
+		shared: Arc<EffectShared>,
+	) -> Self {
 		Self {
 			command_consumer,
+			shared,
 			feedback: Tweener::new(settings.feedback),
 			damping: Tweener::new(settings.damping),
 			stereo_width: Tweener::new(settings.stereo_width),
@@ -131,6 +139,10 @@ impl Reverb {
 }
 
 impl Effect for Reverb {
+	fn on_removed(&mut self) {
+		self.shared.mark_as_removed();
+	}
+
 	fn init(&mut self, sample_rate: u32) {
 		self.init_filters(sample_rate);
 	}
@@ -198,4 +210,28 @@ impl Effect for Reverb {
 		self.stereo_width.on_clock_tick(time);
 		self.mix.on_clock_tick(time);
 	}
+
+	fn tail_frames(&self) -> usize {
+		if let ReverbState::Initialized {
+			comb_filters,
+			all_pass_filters,
+		} = &self.state
+		{
+			// the comb filters run in parallel, so the longest gap
+			// is the longest comb filter, which can be stretched
+			// by each all-pass filter in series
+			let longest_comb_filter = comb_filters
+				.iter()
+				.map(|(left, right)| left.buffer_len().max(right.buffer_len()))
+				.max()
+				.unwrap_or(0);
+			let all_pass_filters_length: usize = all_pass_filters
+				.iter()
+				.map(|(left, right)| left.buffer_len().max(right.buffer_len()))
+				.sum();
+			longest_comb_filter + all_pass_filters_length
+		} else {
+			0
+		}
+	}
 }
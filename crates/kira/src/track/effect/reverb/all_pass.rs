@@ -1,3 +1,5 @@
+use crate::dsp::flush_denormal;
+
 const FEEDBACK: f32 = 0.5;
 
 #[derive(Debug)]
@@ -14,10 +16,14 @@ impl AllPassFilter {
 		}
 	}
 
+	pub fn buffer_len(&self) -> usize {
+		self.buffer.len()
+	}
+
 	pub fn process(&mut self, input: f32) -> f32 {
 		let buffer_output = self.buffer[self.current_index];
 		let output = -input + buffer_output;
-		self.buffer[self.current_index] = input + buffer_output * FEEDBACK;
+		self.buffer[self.current_index] = flush_denormal(input + buffer_output * FEEDBACK);
 		self.current_index += 1;
 		self.current_index %= self.buffer.len();
 		output
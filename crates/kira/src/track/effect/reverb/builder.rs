@@ -1,6 +1,8 @@
+use std::sync::Arc;
+
 use ringbuf::RingBuffer;
 
-use crate::track::effect::{Effect, EffectBuilder};
+use crate::track::effect::{Effect, EffectBuilder, EffectShared};
 
 use super::{Reverb, ReverbHandle};
 
@@ -76,11 +78,19 @@ impl Default for ReverbBuilder {
 impl EffectBuilder for ReverbBuilder {
 	type Handle = ReverbHandle;
 
+	fn name(&self) -> &'static str {
+		"Reverb"
+	}
+
 	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
 		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_CAPACITY).split();
+		let shared = Arc::new(EffectShared::new());
 		(
-			Box::new(Reverb::new(self, command_consumer)),
-			ReverbHandle { command_producer },
+			Box::new(Reverb::new(self, command_consumer, shared.clone())),
+			ReverbHandle {
+				command_producer,
+				shared,
+			},
 		)
 	}
 }
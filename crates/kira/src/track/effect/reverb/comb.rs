@@ -1,3 +1,5 @@
+use crate::dsp::flush_denormal;
+
 #[derive(Debug)]
 pub struct CombFilter {
 	filter_store: f32,
@@ -14,10 +16,14 @@ impl CombFilter {
 		}
 	}
 
+	pub fn buffer_len(&self) -> usize {
+		self.buffer.len()
+	}
+
 	pub fn process(&mut self, input: f32, feedback: f32, damp: f32) -> f32 {
 		let output = self.buffer[self.current_index];
-		self.filter_store = output * (1.0 - damp) + self.filter_store * damp;
-		self.buffer[self.current_index] = input + self.filter_store * feedback;
+		self.filter_store = flush_denormal(output * (1.0 - damp) + self.filter_store * damp);
+		self.buffer[self.current_index] = flush_denormal(input + self.filter_store * feedback);
 		self.current_index += 1;
 		self.current_index %= self.buffer.len();
 		output
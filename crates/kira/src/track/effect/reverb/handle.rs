@@ -1,12 +1,15 @@
+use std::sync::Arc;
+
 use ringbuf::Producer;
 
-use crate::{tween::Tween, CommandError};
+use crate::{track::effect::EffectShared, tween::Tween, CommandError};
 
 use super::Command;
 
 /// Controls a reverb effect.
 pub struct ReverbHandle {
 	pub(super) command_producer: Producer<Command>,
+	pub(super) shared: Arc<EffectShared>,
 }
 
 impl ReverbHandle {
@@ -14,16 +17,12 @@ impl ReverbHandle {
 	/// result in a bigger sounding room. 1.0 gives an infinitely
 	/// reverberating room.
 	pub fn set_feedback(&mut self, feedback: f64, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetFeedback(feedback, tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetFeedback(feedback, tween))
 	}
 
 	/// Sets how quickly high frequencies disappear from the reverberation.
 	pub fn set_damping(&mut self, damping: f64, tween: Tween) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetDamping(damping, tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetDamping(damping, tween))
 	}
 
 	/// Sets the stereo width of the reverb effect (0.0 being fully mono,
@@ -33,9 +32,7 @@ impl ReverbHandle {
 		stereo_width: f64,
 		tween: Tween,
 	) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::SetStereoWidth(stereo_width, tween))
-			.map_err(|_| CommandError::CommandQueueFull)
+		self.push_command(Command::SetStereoWidth(stereo_width, tween))
 	}
 
 	/// Sets how much dry (unprocessed) signal should be blended
@@ -43,8 +40,15 @@ impl ReverbHandle {
 	/// signal will be heard. `1.0` means only the wet signal will
 	/// be heard.
 	pub fn set_mix(&mut self, mix: f64, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::SetMix(mix, tween))
+	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		if self.shared.is_removed() {
+			return Err(CommandError::ResourceRemoved);
+		}
 		self.command_producer
-			.push(Command::SetMix(mix, tween))
+			.push(command)
 			.map_err(|_| CommandError::CommandQueueFull)
 	}
 }
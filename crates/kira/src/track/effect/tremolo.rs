@@ -0,0 +1,125 @@
+//! Periodically modulates the volume or panning of audio.
+
+mod builder;
+mod handle;
+
+#[cfg(test)]
+mod test;
+
+pub use builder::*;
+pub use handle::*;
+
+use std::sync::Arc;
+
+use ringbuf::Consumer;
+
+use crate::{
+	clock::{ClockSync, ClockTime},
+	dsp::{Frame, Lfo, LfoShape},
+	track::{effect::EffectShared, Effect},
+	tween::{Tween, Tweener},
+};
+
+enum Command {
+	SetRate(f64, Tween),
+	SetDepth(f64, Tween),
+	SetShape(LfoShape),
+	SetMode(TremoloMode),
+	SetSync(Option<ClockSync>),
+}
+
+/// Which property of the audio the tremolo effect modulates.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TremoloMode {
+	/// The volume of the audio will rise and fall.
+	Tremolo,
+	/// The audio will pan back and forth between the left
+	/// and right channels.
+	AutoPan,
+}
+
+struct Tremolo {
+	command_consumer: Consumer<Command>,
+	shared: Arc<EffectShared>,
+	mode: TremoloMode,
+	shape: LfoShape,
+	rate: Tweener,
+	depth: Tweener,
+	sync: Option<ClockSync>,
+	lfo: Lfo,
+}
+
+impl Tremolo {
+	fn new(
+		builder: TremoloBuilder,
+		command_consumer: Consumer<Command>,
+		shared: Arc<EffectShared>,
+	) -> Self {
+		Self {
+			command_consumer,
+			shared,
+			mode: builder.mode,
+			shape: builder.shape,
+			rate: Tweener::new(builder.rate),
+			depth: Tweener::new(builder.depth),
+			sync: builder.sync,
+			lfo: Lfo::new(),
+		}
+	}
+}
+
+impl Effect for Tremolo {
+	fn on_removed(&mut self) {
+		self.shared.mark_as_removed();
+	}
+
+	fn on_change_sample_rate(&mut self, _sample_rate: u32) {
+		self.lfo.reset();
+	}
+
+	fn on_start_processing(&mut self) {
+		while let Some(command) = self.command_consumer.pop() {
+			match command {
+				Command::SetRate(rate, tween) => self.rate.set(rate, tween),
+				Command::SetDepth(depth, tween) => self.depth.set(depth, tween),
+				Command::SetShape(shape) => self.shape = shape,
+				Command::SetMode(mode) => self.mode = mode,
+				Command::SetSync(sync) => self.sync = sync,
+			}
+		}
+	}
+
+	fn process(&mut self, input: Frame, dt: f64) -> Frame {
+		self.rate.update(dt);
+		self.depth.update(dt);
+		let depth = self.depth.value().clamp(0.0, 1.0);
+		let lfo_value = match &mut self.sync {
+			Some(sync) => {
+				self.lfo.set_phase(sync.phase());
+				sync.update(dt);
+				self.lfo.value(self.shape)
+			}
+			None => {
+				let lfo_value = self.lfo.value(self.shape);
+				self.lfo.update(self.rate.value(), dt);
+				lfo_value
+			}
+		};
+		match self.mode {
+			TremoloMode::Tremolo => {
+				let amplitude = 1.0 - depth * (1.0 - lfo_value) / 2.0;
+				input * amplitude as f32
+			}
+			TremoloMode::AutoPan => input.panned((0.5 + depth * lfo_value / 2.0) as f32),
+		}
+	}
+
+	fn on_clock_tick(&mut self, time: ClockTime) {
+		self.rate.on_clock_tick(time);
+		self.depth.on_clock_tick(time);
+		if let Some(sync) = &mut self.sync {
+			sync.on_clock_tick(time);
+		}
+	}
+}
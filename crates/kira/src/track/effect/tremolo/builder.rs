@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use ringbuf::RingBuffer;
+
+use crate::{
+	clock::ClockSync,
+	dsp::LfoShape,
+	track::effect::{Effect, EffectBuilder, EffectShared},
+};
+
+use super::{Tremolo, TremoloHandle, TremoloMode};
+
+const COMMAND_CAPACITY: usize = 8;
+
+/// Configures a tremolo effect.
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub struct TremoloBuilder {
+	/// Which property of the audio should be modulated.
+	pub mode: TremoloMode,
+	/// The shape of the modulation.
+	pub shape: LfoShape,
+	/// How many times per second the modulation repeats (in hertz).
+	pub rate: f64,
+	/// If set, the modulation repeats once per cycle of a clock
+	/// instead of at the [`rate`](Self::rate).
+	pub sync: Option<ClockSync>,
+	/// How strong the modulation is. `0.0` means the audio will
+	/// not be affected. `1.0` means the volume will dip all the
+	/// way to silence (or the audio will pan all the way to the
+	/// left and right).
+	pub depth: f64,
+}
+
+impl TremoloBuilder {
+	/// Creates a new [`TremoloBuilder`] with the default settings.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets which property of the audio should be modulated.
+	pub fn mode(self, mode: TremoloMode) -> Self {
+		Self { mode, ..self }
+	}
+
+	/// Sets the shape of the modulation.
+	pub fn shape(self, shape: LfoShape) -> Self {
+		Self { shape, ..self }
+	}
+
+	/// Sets how many times per second the modulation repeats (in hertz).
+	pub fn rate(self, rate: f64) -> Self {
+		Self { rate, ..self }
+	}
+
+	/// Sets a clock to repeat the modulation in time with.
+	pub fn sync(self, sync: impl Into<Option<ClockSync>>) -> Self {
+		Self {
+			sync: sync.into(),
+			..self
+		}
+	}
+
+	/// Sets how strong the modulation is.
+	pub fn depth(self, depth: f64) -> Self {
+		Self { depth, ..self }
+	}
+}
+
+impl Default for TremoloBuilder {
+	fn default() -> Self {
+		Self {
+			mode: TremoloMode::Tremolo,
+			shape: LfoShape::Sine,
+			rate: 5.0,
+			sync: None,
+			depth: 0.5,
+		}
+	}
+}
+
+impl EffectBuilder for TremoloBuilder {
+	type Handle = TremoloHandle;
+
+	fn name(&self) -> &'static str {
+		"Tremolo"
+	}
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_CAPACITY).split();
+		let shared = Arc::new(EffectShared::new());
+		(
+			Box::new(Tremolo::new(self, command_consumer, shared.clone())),
+			TremoloHandle {
+				command_producer,
+				shared,
+			},
+		)
+	}
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use ringbuf::Producer;
+
+use crate::{
+	clock::ClockSync, dsp::LfoShape, track::effect::EffectShared, tween::Tween, CommandError,
+};
+
+use super::{Command, TremoloMode};
+
+/// Controls a tremolo effect.
+pub struct TremoloHandle {
+	pub(super) command_producer: Producer<Command>,
+	pub(super) shared: Arc<EffectShared>,
+}
+
+impl TremoloHandle {
+	/// Sets how many times per second the modulation repeats (in hertz).
+	pub fn set_rate(&mut self, rate: f64, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::SetRate(rate, tween))
+	}
+
+	/// Sets a clock to repeat the modulation in time with, or
+	/// goes back to using the rate if `sync` is `None`.
+	pub fn set_sync(&mut self, sync: impl Into<Option<ClockSync>>) -> Result<(), CommandError> {
+		self.push_command(Command::SetSync(sync.into()))
+	}
+
+	/// Sets how strong the modulation is.
+	pub fn set_depth(&mut self, depth: f64, tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::SetDepth(depth, tween))
+	}
+
+	/// Sets the shape of the modulation.
+	pub fn set_shape(&mut self, shape: LfoShape) -> Result<(), CommandError> {
+		self.push_command(Command::SetShape(shape))
+	}
+
+	/// Sets which property of the audio should be modulated.
+	pub fn set_mode(&mut self, mode: TremoloMode) -> Result<(), CommandError> {
+		self.push_command(Command::SetMode(mode))
+	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		if self.shared.is_removed() {
+			return Err(CommandError::ResourceRemoved);
+		}
+		self.command_producer
+			.push(command)
+			.map_err(|_| CommandError::CommandQueueFull)
+	}
+}
@@ -0,0 +1,103 @@
+use atomic_arena::Arena;
+
+use crate::{
+	clock::{ClockId, ClockSync, ClockTime},
+	dsp::{Frame, LfoShape},
+	track::effect::{Effect, EffectBuilder},
+};
+
+use super::{TremoloBuilder, TremoloMode};
+
+const SAMPLE_RATE: u32 = 100;
+
+fn render(effect: &mut Box<dyn Effect>, num_frames: usize) -> Vec<Frame> {
+	effect.init(SAMPLE_RATE);
+	(0..num_frames)
+		.map(|_| effect.process(Frame::from_mono(1.0), 1.0 / SAMPLE_RATE as f64))
+		.collect()
+}
+
+/// Tests that the volume envelope of the tremolo effect
+/// repeats at the configured rate.
+#[test]
+fn tremolo_oscillates_at_rate() {
+	let (mut effect, _) = TremoloBuilder::new()
+		.rate(1.0)
+		.depth(1.0)
+		.shape(LfoShape::Triangle)
+		.build();
+	let output = render(&mut effect, SAMPLE_RATE as usize * 3);
+	// a rate of 1 Hz means the envelope repeats every 100 frames
+	let period = SAMPLE_RATE as usize;
+	for i in 0..output.len() - period {
+		assert!((output[i].left - output[i + period].left).abs() < 0.0001);
+	}
+	// the triangle wave starts at the middle of its range, peaks
+	// after a quarter of a cycle, and hits its minimum after
+	// three quarters of a cycle
+	assert!((output[0].left - 0.5).abs() < 0.0001);
+	assert!((output[period / 4].left - 1.0).abs() < 0.0001);
+	assert!(output[period * 3 / 4].left.abs() < 0.0001);
+}
+
+/// Tests that the auto-pan mode moves the audio between
+/// the left and right channels.
+#[test]
+fn auto_pan() {
+	let (mut effect, _) = TremoloBuilder::new()
+		.mode(TremoloMode::AutoPan)
+		.rate(1.0)
+		.depth(1.0)
+		.shape(LfoShape::Square)
+		.build();
+	let output = render(&mut effect, SAMPLE_RATE as usize);
+	// the first half of the cycle should be panned hard right,
+	// and the second half should be panned hard left
+	for frame in &output[..SAMPLE_RATE as usize / 2] {
+		assert_eq!(*frame, Frame::from_mono(1.0).panned(1.0));
+	}
+	for frame in &output[SAMPLE_RATE as usize / 2..] {
+		assert_eq!(*frame, Frame::from_mono(1.0).panned(0.0));
+	}
+}
+
+/// Tests that a depth of zero leaves the audio unchanged.
+#[test]
+fn zero_depth() {
+	let (mut effect, _) = TremoloBuilder::new().depth(0.0).build();
+	for frame in render(&mut effect, SAMPLE_RATE as usize) {
+		assert_eq!(frame, Frame::from_mono(1.0));
+	}
+}
+
+/// Tests that a tremolo synced to a clock restarts its
+/// modulation on each tick.
+#[test]
+fn syncs_to_clock() {
+	let mut dummy_arena = Arena::new(1);
+	let clock = ClockId(dummy_arena.insert(()).unwrap());
+	let (mut effect, _) = TremoloBuilder::new()
+		.rate(1.0)
+		.depth(1.0)
+		.shape(LfoShape::Square)
+		.sync(ClockSync::new(clock))
+		.build();
+	effect.init(SAMPLE_RATE);
+	// the clock ticks every 10 frames, so the rate is ignored
+	// and the volume dips halfway between each tick
+	for ticks in 0..5 {
+		effect.on_clock_tick(ClockTime { clock, ticks });
+		let output: Vec<f32> = (0..10)
+			.map(|_| {
+				effect
+					.process(Frame::from_mono(1.0), 1.0 / SAMPLE_RATE as f64)
+					.left
+			})
+			.collect();
+		// the time between ticks is only known after the second tick
+		if ticks >= 1 {
+			assert!(output[..5].iter().all(|value| *value == 1.0));
+			assert!(output[6..].iter().all(|value| *value == 0.0));
+		}
+	}
+}
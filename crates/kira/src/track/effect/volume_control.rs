@@ -3,50 +3,91 @@
 mod builder;
 mod handle;
 
+#[cfg(test)]
+mod test;
+
 pub use builder::*;
 pub use handle::*;
 
+use std::sync::Arc;
+
 use ringbuf::Consumer;
 
 use crate::{
 	clock::ClockTime,
-	dsp::Frame,
+	dsp::{Frame, Lfo, LfoSettings},
+	modulator::{ModulatorId, ModulatorMapping},
 	tween::{Tween, Tweener},
 	Volume,
 };
 
-use super::Effect;
+use super::{Effect, EffectShared};
 
 enum Command {
 	SetVolume(Volume, Tween),
+	SetVolumeModulator(ModulatorId, ModulatorMapping),
+	SetLfo(LfoSettings),
+	RemoveLfo,
 }
 
 struct VolumeControl {
 	command_consumer: Consumer<Command>,
+	shared: Arc<EffectShared>,
 	volume: Tweener<Volume>,
+	volume_modulator: Option<(ModulatorId, ModulatorMapping)>,
+	lfo: Lfo,
+	lfo_settings: Option<LfoSettings>,
 }
 
 impl VolumeControl {
-	fn new(builder: VolumeControlBuilder, command_consumer: Consumer<Command>) -> Self {
+	fn new(
+		builder: VolumeControlBuilder,
+		command_consumer: Consumer<Command>,
+		shared: Arc<EffectShared>,
+	) -> Self {
 		Self {
 			command_consumer,
+			shared,
 			volume: Tweener::new(builder.0),
+			volume_modulator: None,
+			lfo: Lfo::new(),
+			lfo_settings: None,
 		}
 	}
 }
 
 impl Effect for VolumeControl {
+	fn on_removed(&mut self) {
+		self.shared.mark_as_removed();
+	}
+
 	fn on_start_processing(&mut self) {
 		while let Some(command) = self.command_consumer.pop() {
 			match command {
-				Command::SetVolume(volume, tween) => self.volume.set(volume, tween),
+				Command::SetVolume(volume, tween) => {
+					self.volume.set(volume, tween);
+					self.volume_modulator = None;
+				}
+				Command::SetVolumeModulator(id, mapping) => {
+					self.volume_modulator = Some((id, mapping))
+				}
+				Command::SetLfo(lfo_settings) => self.lfo_settings = Some(lfo_settings),
+				Command::RemoveLfo => self.lfo_settings = None,
 			}
 		}
 	}
 
 	fn process(&mut self, input: Frame, dt: f64) -> Frame {
 		self.volume.update(dt);
-		input * self.volume.value().as_amplitude() as f32
+		let mut amplitude = match &self.volume_modulator {
+			Some((id, mapping)) => mapping.map(id.value()),
+			None => self.volume.value().as_amplitude(),
+		};
+		if let Some(lfo_settings) = &self.lfo_settings {
+			self.lfo.update(lfo_settings.rate, dt);
+			amplitude = (amplitude + lfo_settings.offset(&self.lfo)).max(0.0);
+		}
+		input * amplitude as f32
 	}
 
 	fn on_clock_tick(&mut self, time: ClockTime) {
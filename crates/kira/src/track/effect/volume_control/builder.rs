@@ -1,6 +1,11 @@
+use std::sync::Arc;
+
 use ringbuf::RingBuffer;
 
-use crate::{track::effect::EffectBuilder, Volume};
+use crate::{
+	track::effect::{EffectBuilder, EffectShared},
+	Volume,
+};
 
 use super::{VolumeControl, VolumeControlHandle};
 
@@ -26,11 +31,19 @@ impl Default for VolumeControlBuilder {
 impl EffectBuilder for VolumeControlBuilder {
 	type Handle = VolumeControlHandle;
 
+	fn name(&self) -> &'static str {
+		"Volume control"
+	}
+
 	fn build(self) -> (Box<dyn crate::track::effect::Effect>, Self::Handle) {
 		let (command_producer, command_consumer) = RingBuffer::new(COMMAND_CAPACITY).split();
+		let shared = Arc::new(EffectShared::new());
 		(
-			Box::new(VolumeControl::new(self, command_consumer)),
-			VolumeControlHandle { command_producer },
+			Box::new(VolumeControl::new(self, command_consumer, shared.clone())),
+			VolumeControlHandle {
+				command_producer,
+				shared,
+			},
 		)
 	}
 }
@@ -1,12 +1,21 @@
+use std::sync::Arc;
+
 use ringbuf::Producer;
 
-use crate::{tween::Tween, CommandError, Volume};
+use crate::{
+	dsp::{LfoSettings, LfoShape},
+	modulator::{ModulatorId, ModulatorMapping},
+	track::effect::EffectShared,
+	tween::Tween,
+	CommandError, Volume,
+};
 
 use super::Command;
 
 /// Controls a volume control effect.
 pub struct VolumeControlHandle {
 	pub(super) command_producer: Producer<Command>,
+	pub(super) shared: Arc<EffectShared>,
 }
 
 impl VolumeControlHandle {
@@ -16,8 +25,44 @@ impl VolumeControlHandle {
 		volume: impl Into<Volume>,
 		tween: Tween,
 	) -> Result<(), CommandError> {
+		self.push_command(Command::SetVolume(volume.into(), tween))
+	}
+
+	/// Links the volume adjustment (as an amplitude) to the
+	/// output of a modulator.
+	///
+	/// The link is removed the next time [`set_volume`](Self::set_volume)
+	/// is called.
+	pub fn set_volume_modulator(
+		&mut self,
+		modulator: impl Into<ModulatorId>,
+		mapping: ModulatorMapping,
+	) -> Result<(), CommandError> {
+		self.push_command(Command::SetVolumeModulator(modulator.into(), mapping))
+	}
+
+	/// Starts modulating the volume with a low frequency oscillator.
+	///
+	/// The oscillator's output (from `-depth` to `depth`) is added to
+	/// the amplitude of the volume set by [`set_volume`](Self::set_volume)
+	/// or [`set_volume_modulator`](Self::set_volume_modulator).
+	/// Amplitudes below `0.0` are treated as `0.0`. `rate` is the
+	/// frequency of the oscillator (in hertz).
+	pub fn set_lfo(&mut self, rate: f64, depth: f64, shape: LfoShape) -> Result<(), CommandError> {
+		self.push_command(Command::SetLfo(LfoSettings { rate, depth, shape }))
+	}
+
+	/// Stops modulating the volume with a low frequency oscillator.
+	pub fn remove_lfo(&mut self) -> Result<(), CommandError> {
+		self.push_command(Command::RemoveLfo)
+	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		if self.shared.is_removed() {
+			return Err(CommandError::ResourceRemoved);
+		}
 		self.command_producer
-			.push(Command::SetVolume(volume.into(), tween))
+			.push(command)
 			.map_err(|_| CommandError::CommandQueueFull)
 	}
 }
@@ -0,0 +1,60 @@
+use crate::{
+	dsp::{Frame, LfoShape},
+	manager::{backend::mock::MockBackend, AudioManager},
+	modulator::{lfo::LfoBuilder, ModulatorMapping},
+	track::effect::EffectBuilder,
+	Volume,
+};
+
+use super::VolumeControlBuilder;
+
+/// Tests that an LFO is added to the base volume of
+/// a volume control effect.
+#[test]
+#[allow(clippy::float_cmp)]
+fn lfo_modulates_volume() {
+	let (mut effect, mut handle) = VolumeControlBuilder::new(Volume::Amplitude(0.5)).build();
+	effect.init(4);
+	handle.set_lfo(1.0, 0.25, LfoShape::Square).unwrap();
+	effect.on_start_processing();
+	let output = (0..4)
+		.map(|_| effect.process(Frame::from_mono(1.0), 0.25).left)
+		.collect::<Vec<_>>();
+	assert_eq!(output, vec![0.75, 0.25, 0.25, 0.75]);
+
+	// removing the LFO should go back to the base volume
+	handle.remove_lfo().unwrap();
+	effect.on_start_processing();
+	assert_eq!(effect.process(Frame::from_mono(1.0), 0.25).left, 0.5);
+}
+
+/// Tests that the volume of a volume control effect can
+/// follow a modulator.
+#[test]
+#[allow(clippy::float_cmp)]
+fn follows_modulator() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let modulator = manager
+		.add_modulator(LfoBuilder::new().shape(LfoShape::Square))
+		.unwrap();
+	let (mut effect, mut handle) = VolumeControlBuilder::new(Volume::Amplitude(0.5)).build();
+	effect.init(1);
+	handle
+		.set_volume_modulator(&modulator, ModulatorMapping::new((-1.0, 1.0), (0.0, 1.0)))
+		.unwrap();
+	effect.on_start_processing();
+	manager.backend_mut().on_start_processing();
+	manager.backend_mut().process();
+	let expected = ModulatorMapping::new((-1.0, 1.0), (0.0, 1.0)).map(modulator.id().value());
+	assert_eq!(
+		effect.process(Frame::from_mono(1.0), 1.0).left,
+		expected as f32
+	);
+
+	// setting the volume should unlink the modulator
+	handle
+		.set_volume(Volume::Amplitude(0.25), Default::default())
+		.unwrap();
+	effect.on_start_processing();
+	assert_eq!(effect.process(Frame::from_mono(1.0), 1.0).left, 0.25);
+}
@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use crate::dsp::Frame;
+
+/// Tracks the smoothed amplitude of a signal.
+pub(crate) struct EnvelopeFollower {
+	attack_duration: Duration,
+	release_duration: Duration,
+	value: f64,
+}
+
+impl EnvelopeFollower {
+	pub fn new(attack_duration: Duration, release_duration: Duration) -> Self {
+		Self {
+			attack_duration,
+			release_duration,
+			value: 0.0,
+		}
+	}
+
+	pub fn value(&self) -> f64 {
+		self.value
+	}
+
+	pub fn update(&mut self, input: Frame, dt: f64) {
+		let level = input.left.abs().max(input.right.abs()) as f64;
+		let duration = if level > self.value {
+			self.attack_duration
+		} else {
+			self.release_duration
+		};
+		// one-pole smoothing where the envelope covers ~63% of the
+		// distance to the input level after the given duration
+		let coefficient = if duration.is_zero() {
+			0.0
+		} else {
+			(-dt / duration.as_secs_f64()).exp()
+		};
+		self.value = level + (self.value - level) * coefficient;
+	}
+}
@@ -0,0 +1,75 @@
+use crate::{error::CommandError, tween::Tween, Volume};
+
+use super::{SubTrackId, TrackHandle, TrackId};
+
+/// A unique identifier for a sound group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GroupId(pub(crate) SubTrackId);
+
+impl From<GroupId> for TrackId {
+	fn from(id: GroupId) -> Self {
+		Self::Sub(id.0)
+	}
+}
+
+impl From<&GroupHandle> for TrackId {
+	fn from(handle: &GroupHandle) -> Self {
+		handle.id().into()
+	}
+}
+
+/// Controls a category of sounds, like music, sound effects,
+/// or dialogue.
+///
+/// Sounds can be added to a group by setting their track to the
+/// group, for example with
+/// [`StaticSoundSettings::track`](crate::sound::static_sound::StaticSoundSettings::track).
+/// The group's volume is applied on top of the volume of each sound
+/// in the group.
+///
+/// Groups are mixer sub-tracks with no effects, so they count
+/// towards the sub-track capacity, and dropping a [`GroupHandle`]
+/// removes the group.
+pub struct GroupHandle {
+	pub(crate) track: TrackHandle,
+}
+
+impl GroupHandle {
+	/// Returns the unique identifier for the group.
+	pub fn id(&self) -> GroupId {
+		match self.track.id() {
+			TrackId::Sub(id) => GroupId(id),
+			TrackId::Main => unreachable!("groups are always sub-tracks"),
+		}
+	}
+
+	/// Fades out and pauses all the sounds in the group.
+	///
+	/// See [`TrackHandle::pause_sounds`] for details.
+	pub fn pause(&mut self, fade_out_tween: Tween) -> Result<(), CommandError> {
+		self.track.pause_sounds(fade_out_tween)
+	}
+
+	/// Resumes and fades in all the sounds in the group.
+	///
+	/// See [`TrackHandle::resume_sounds`] for details.
+	pub fn resume(&mut self, fade_in_tween: Tween) -> Result<(), CommandError> {
+		self.track.resume_sounds(fade_in_tween)
+	}
+
+	/// Fades out and stops all the sounds in the group.
+	///
+	/// See [`TrackHandle::stop_sounds`] for details.
+	pub fn stop(&mut self, fade_out_tween: Tween) -> Result<(), CommandError> {
+		self.track.stop_sounds(fade_out_tween)
+	}
+
+	/// Sets the volume of all the sounds in the group.
+	pub fn set_volume(
+		&mut self,
+		volume: impl Into<Volume>,
+		tween: Tween,
+	) -> Result<(), CommandError> {
+		self.track.set_volume(volume, tween)
+	}
+}
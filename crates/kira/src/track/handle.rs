@@ -1,13 +1,16 @@
-use std::{collections::HashSet, error::Error, fmt::Display, sync::Arc};
+use std::{error::Error, fmt::Display, sync::Arc};
 
 use crate::{
 	error::CommandError,
-	manager::command::{producer::CommandProducer, Command, MixerCommand},
+	manager::command::{producer::CommandProducer, Command, MixerCommand, SoundCommand},
 	tween::Tween,
 	Volume,
 };
 
-use super::{TrackId, TrackShared};
+use super::{
+	effect::EffectId, DelayLine, NonFiniteSource, SendPoint, SubTrackRegistry, TrackId,
+	TrackShared, MAX_ADDED_ROUTES,
+};
 
 /// Errors that can occur when changing the volume of a track route.
 #[derive(Debug)]
@@ -38,15 +41,67 @@ impl Error for SetRouteError {
 	}
 }
 
+/// Errors that can occur when adding a track route.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AddRouteError {
+	/// The main track's output can't be routed to other tracks.
+	MainTrack,
+	/// A track can't be routed to itself.
+	RouteToSelf,
+	/// The maximum number of routes has already been added to the track.
+	RouteLimitReached,
+	/// The destination track doesn't exist or has been removed.
+	NonexistentTrack,
+	/// The destination track's output already reaches this track,
+	/// so the route would make the audio feed back into itself.
+	RoutingCycle,
+	/// An error occurred when sending a command to the audio renderer.
+	CommandError(CommandError),
+}
+
+impl Display for AddRouteError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			AddRouteError::MainTrack => {
+				f.write_str("The main track's output can't be routed to other tracks")
+			}
+			AddRouteError::RouteToSelf => f.write_str("A track can't be routed to itself"),
+			AddRouteError::RouteLimitReached => f.write_str(&format!(
+				"Cannot add more than {} routes to a track after it's created",
+				MAX_ADDED_ROUTES
+			)),
+			AddRouteError::NonexistentTrack => {
+				f.write_str("The destination track doesn't exist or has been removed")
+			}
+			AddRouteError::RoutingCycle => {
+				f.write_str("The route would make a track's output feed back into itself")
+			}
+			AddRouteError::CommandError(error) => error.fmt(f),
+		}
+	}
+}
+
+impl Error for AddRouteError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			AddRouteError::CommandError(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
 /// Controls a mixer track.
 ///
 /// When a [`TrackHandle`] is dropped, the corresponding mixer
-/// track will be removed.
+/// track will stop receiving input and will be removed once its
+/// effects have finished ringing out.
 pub struct TrackHandle {
 	pub(crate) id: TrackId,
-	pub(crate) shared: Option<Arc<TrackShared>>,
+	pub(crate) shared: Arc<TrackShared>,
 	pub(crate) command_producer: CommandProducer,
-	pub(crate) existing_routes: HashSet<TrackId>,
+	pub(crate) effect_names: Vec<&'static str>,
+	pub(crate) sub_tracks: SubTrackRegistry,
 }
 
 impl TrackHandle {
@@ -55,24 +110,157 @@ impl TrackHandle {
 		self.id
 	}
 
+	/// Returns `false` if the mixer track has been stopped using
+	/// [`stop`](Self::stop) and will be removed once its effects
+	/// finish ringing out.
+	///
+	/// This is only advisory: the track may be marked for removal
+	/// right after this returns `true`.
+	pub fn is_alive(&self) -> bool {
+		!self.shared.is_marked_for_removal()
+	}
+
+	/// Returns the smoothed amplitude of the mixer track's output.
+	///
+	/// This is always `0.0` unless the envelope follower was enabled using
+	/// [`TrackBuilder::envelope_follower`](super::TrackBuilder::envelope_follower).
+	/// The value is updated once per batch of audio processed
+	/// by the renderer.
+	pub fn envelope(&self) -> f64 {
+		self.shared.envelope()
+	}
+
+	/// Returns the number of frames in which the mixer track found a
+	/// NaN or infinite sample in its input or in the output of one of
+	/// its effects.
+	///
+	/// Non-finite samples are replaced with silence so they don't
+	/// spread through the rest of the mix. Use
+	/// [`last_non_finite_source`](Self::last_non_finite_source) to find
+	/// out where they came from.
+	///
+	/// This check only runs in debug builds. In release builds, this
+	/// always returns `0`.
+	pub fn non_finite_frame_count(&self) -> u64 {
+		self.shared.non_finite_frame_count()
+	}
+
+	/// Returns where the most recent NaN or infinite sample on the
+	/// mixer track came from, or `None` if there haven't been any.
+	///
+	/// This check only runs in debug builds. In release builds, this
+	/// always returns `None`.
+	pub fn last_non_finite_source(&self) -> Option<NonFiniteSource> {
+		self.shared.last_non_finite_source()
+	}
+
+	/// Returns the correlation between the left and right channels
+	/// of the mixer track's output, from `-1.0` to `1.0`.
+	///
+	/// `1.0` means the channels are identical (mono), `0.0` means
+	/// they're unrelated (or the track is silent), and `-1.0` means
+	/// they're out of phase and will cancel out when mixed down to
+	/// mono.
+	///
+	/// This is always `0.0` unless the correlation meter was enabled using
+	/// [`TrackBuilder::correlation_meter`](super::TrackBuilder::correlation_meter).
+	/// The value is updated once per batch of audio processed
+	/// by the renderer.
+	pub fn correlation(&self) -> f32 {
+		self.shared.correlation()
+	}
+
+	/// Returns the identifiers of the effects on the mixer track,
+	/// in the order they're applied.
+	pub fn effect_ids(&self) -> Vec<EffectId> {
+		(0..self.effect_names.len()).map(EffectId).collect()
+	}
+
+	/// Returns the name of the kind of effect with the given
+	/// identifier, or `None` if the track has no such effect.
+	///
+	/// Names are provided by [`EffectBuilder::name`](super::effect::EffectBuilder::name).
+	pub fn effect_name(&self, id: EffectId) -> Option<&'static str> {
+		self.effect_names.get(id.0).copied()
+	}
+
 	/// Sets the (post-effects) volume of the mixer track.
+	///
+	/// Routes are post-fader by default, so this also changes how
+	/// much signal is sent along them. Routes with a send point of
+	/// [`SendPoint::PreFader`] are not affected.
 	pub fn set_volume(
 		&mut self,
 		volume: impl Into<Volume>,
 		tween: Tween,
 	) -> Result<(), CommandError> {
-		self.command_producer
-			.push(Command::Mixer(MixerCommand::SetTrackVolume(
-				self.id,
-				volume.into(),
-				tween,
-			)))
+		self.push_command(Command::Mixer(MixerCommand::SetTrackVolume(
+			self.id,
+			volume.into(),
+			tween,
+		)))
+	}
+
+	/// Fades out the volume of the mixer track and then removes it.
+	///
+	/// Every sound playing on the track is stopped using the same
+	/// fade (see [`stop_sounds`](Self::stop_sounds)). Once the fade
+	/// finishes, the track stops receiving input and is removed after
+	/// its effects have finished ringing out, just like when the
+	/// [`TrackHandle`] is dropped.
+	///
+	/// Pre-fader routes keep sending the track's signal during the
+	/// fade, but they go silent once the track is removed.
+	///
+	/// The main track can't be removed, so stopping it just fades it
+	/// out and stops its sounds.
+	pub fn stop(&mut self, fade_out_tween: Tween) -> Result<(), CommandError> {
+		self.stop_sounds(fade_out_tween)?;
+		self.push_command(Command::Mixer(MixerCommand::StopTrack(
+			self.id,
+			fade_out_tween,
+		)))
+	}
+
+	/// Fades out and pauses every sound playing on this track.
+	///
+	/// Sounds that start playing on this track afterward will start
+	/// out paused until [`resume_sounds`](Self::resume_sounds) is called.
+	/// Only sounds whose output goes directly to this track are affected,
+	/// not sounds on other tracks that route to this one.
+	pub fn pause_sounds(&mut self, fade_out_tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::Sound(SoundCommand::PauseTrack(
+			self.id,
+			fade_out_tween,
+		)))
+	}
+
+	/// Resumes and fades in every paused sound on this track.
+	///
+	/// This also resumes sounds on this track that were paused
+	/// individually using their own handles.
+	pub fn resume_sounds(&mut self, fade_in_tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::Sound(SoundCommand::ResumeTrack(
+			self.id,
+			fade_in_tween,
+		)))
+	}
+
+	/// Fades out and stops every sound playing on this track.
+	///
+	/// Sounds that start playing on this track afterward are
+	/// not affected.
+	pub fn stop_sounds(&mut self, fade_out_tween: Tween) -> Result<(), CommandError> {
+		self.push_command(Command::Sound(SoundCommand::StopTrack(
+			self.id,
+			fade_out_tween,
+		)))
 	}
 
 	/// Sets the volume of this track's route to another track.
 	///
-	/// This can only be used to change the volume of existing routes,
-	/// not to add new routes.
+	/// This can only be used to change the volume of existing routes.
+	/// Use [`add_route`](Self::add_route) to add new routes.
 	pub fn set_route(
 		&mut self,
 		to: impl Into<TrackId>,
@@ -80,24 +268,147 @@ impl TrackHandle {
 		tween: Tween,
 	) -> Result<(), SetRouteError> {
 		let to = to.into();
-		if !self.existing_routes.contains(&to) {
+		if self.id == TrackId::Main || !self.shared.has_route(to) {
 			return Err(SetRouteError::NonexistentRoute);
 		}
-		self.command_producer
-			.push(Command::Mixer(MixerCommand::SetTrackRoutes {
+		self.push_command(Command::Mixer(MixerCommand::SetTrackRoutes {
+			from: self.id,
+			to,
+			volume: volume.into(),
+			tween,
+		}))
+		.map_err(SetRouteError::CommandError)
+	}
+
+	/// Sends this track's output to another track, fading the
+	/// route's volume in from silence.
+	///
+	/// A track can have any number of routes, each with its own
+	/// volume, and up to 8 routes can be added after the track is
+	/// created. If the route already exists, this just changes its
+	/// volume.
+	///
+	/// Routes can't be added to tracks that have been removed or
+	/// to tracks whose output already reaches this track. Removed
+	/// routes still count toward both this check and the route limit.
+	///
+	/// New routes are post-fader. Use [`add_route_at`](Self::add_route_at)
+	/// to choose the send point.
+	pub fn add_route(
+		&mut self,
+		to: impl Into<TrackId>,
+		volume: impl Into<Volume>,
+		tween: Tween,
+	) -> Result<(), AddRouteError> {
+		self.add_route_with_send_point(to.into(), volume.into(), None, tween)
+	}
+
+	/// Sends this track's output to another track, fading the
+	/// route's volume in from silence, and sets whether the signal is
+	/// taken before or after this track's volume is applied.
+	///
+	/// If the route already exists, this changes its volume and
+	/// send point.
+	pub fn add_route_at(
+		&mut self,
+		to: impl Into<TrackId>,
+		volume: impl Into<Volume>,
+		send_point: SendPoint,
+		tween: Tween,
+	) -> Result<(), AddRouteError> {
+		self.add_route_with_send_point(to.into(), volume.into(), Some(send_point), tween)
+	}
+
+	fn add_route_with_send_point(
+		&mut self,
+		to: TrackId,
+		volume: Volume,
+		send_point: Option<SendPoint>,
+		tween: Tween,
+	) -> Result<(), AddRouteError> {
+		if self.id == TrackId::Main {
+			return Err(AddRouteError::MainTrack);
+		}
+		if to == self.id {
+			return Err(AddRouteError::RouteToSelf);
+		}
+		let is_new = self.shared.route_active(to).is_none();
+		if is_new {
+			if !self.shared.has_room_for_route() {
+				return Err(AddRouteError::RouteLimitReached);
+			}
+			if let (TrackId::Sub(from), TrackId::Sub(to)) = (self.id, to) {
+				if !self.sub_tracks.contains(to) {
+					return Err(AddRouteError::NonexistentTrack);
+				}
+				if self.sub_tracks.reaches(to, from) {
+					return Err(AddRouteError::RoutingCycle);
+				}
+			}
+		}
+		let command = match (is_new, send_point) {
+			// keep the send point of an existing route
+			(false, None) => Command::Mixer(MixerCommand::SetTrackRoutes {
+				from: self.id,
+				to,
+				volume,
+				tween,
+			}),
+			_ => Command::Mixer(MixerCommand::AddTrackRoute {
 				from: self.id,
 				to,
-				volume: volume.into(),
+				volume,
+				send_point: send_point.unwrap_or_default(),
 				tween,
-			}))
-			.map_err(SetRouteError::CommandError)
+				delay_line: if is_new { Some(DelayLine::new()) } else { None },
+			}),
+		};
+		// only record the route once the audio thread is
+		// guaranteed to receive it
+		self.push_command(command)
+			.map_err(AddRouteError::CommandError)?;
+		self.shared.set_route_active(to, true);
+		Ok(())
+	}
+
+	/// Fades out this track's route to another track and removes it.
+	///
+	/// The route can be added again later using
+	/// [`add_route`](Self::add_route).
+	pub fn remove_route(
+		&mut self,
+		to: impl Into<TrackId>,
+		fade_out_tween: Tween,
+	) -> Result<(), SetRouteError> {
+		let to = to.into();
+		if self.id == TrackId::Main || !self.shared.has_route(to) {
+			return Err(SetRouteError::NonexistentRoute);
+		}
+		self.push_command(Command::Mixer(MixerCommand::SetTrackRoutes {
+			from: self.id,
+			to,
+			volume: Volume::Amplitude(0.0),
+			tween: fade_out_tween,
+		}))
+		.map_err(SetRouteError::CommandError)?;
+		self.shared.set_route_active(to, false);
+		Ok(())
+	}
+
+	fn push_command(&mut self, command: Command) -> Result<(), CommandError> {
+		// the main track is never removed
+		if self.id != TrackId::Main && !self.is_alive() {
+			return Err(CommandError::ResourceRemoved);
+		}
+		self.command_producer.push(command)
 	}
 }
 
 impl Drop for TrackHandle {
 	fn drop(&mut self) {
-		if let Some(shared) = &self.shared {
-			shared.mark_for_removal();
+		// the main track can't be removed
+		if let TrackId::Sub(_) = self.id {
+			self.shared.mark_for_removal();
 		}
 	}
 }
@@ -4,9 +4,32 @@ use crate::{tween::Tweener, Volume};
 
 use super::TrackId;
 
+/// Where in a track's signal chain a route takes its signal from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum SendPoint {
+	/// The route receives the output of the track's effects
+	/// before the track's volume is applied, so changing the
+	/// track's volume doesn't affect how much signal is sent.
+	///
+	/// This includes the fade when the track is stopped, but
+	/// once the track is removed at the end of the fade,
+	/// nothing is sent along its routes anymore.
+	PreFader,
+	/// The route receives the output of the track after its
+	/// volume is applied.
+	#[default]
+	PostFader,
+}
+
 /// Defines how the output of a mixer sub-track will be
 /// fed into the input of other mixer tracks.
-pub struct TrackRoutes(pub(crate) HashMap<TrackId, Volume>);
+///
+/// Routes are post-fader by default, meaning the track's volume
+/// affects the signal sent along each route. Use
+/// [`with_route_at`](Self::with_route_at) to create a pre-fader route.
+#[derive(Debug, Clone)]
+pub struct TrackRoutes(pub(crate) HashMap<TrackId, (Volume, SendPoint)>);
 
 impl TrackRoutes {
 	/// Creates a new [`TrackRoutes`] with the default settings.
@@ -31,15 +54,29 @@ impl TrackRoutes {
 	pub fn parent(track: impl Into<TrackId>) -> Self {
 		Self({
 			let mut routes = HashMap::new();
-			routes.insert(track.into(), Volume::Amplitude(1.0));
+			routes.insert(track.into(), (Volume::Amplitude(1.0), SendPoint::PostFader));
 			routes
 		})
 	}
 
 	/// Sets how much of the current track's signal will be sent
 	/// to the specified destination track.
-	pub fn with_route(mut self, track: impl Into<TrackId>, volume: impl Into<Volume>) -> Self {
-		self.0.insert(track.into(), volume.into());
+	///
+	/// The route is post-fader.
+	pub fn with_route(self, track: impl Into<TrackId>, volume: impl Into<Volume>) -> Self {
+		self.with_route_at(track, volume, SendPoint::PostFader)
+	}
+
+	/// Sets how much of the current track's signal will be sent
+	/// to the specified destination track, and whether the signal
+	/// is taken before or after the track's volume is applied.
+	pub fn with_route_at(
+		mut self,
+		track: impl Into<TrackId>,
+		volume: impl Into<Volume>,
+		send_point: SendPoint,
+	) -> Self {
+		self.0.insert(track.into(), (volume.into(), send_point));
 		self
 	}
 
@@ -49,10 +86,10 @@ impl TrackRoutes {
 		self
 	}
 
-	pub(crate) fn into_vec(self) -> Vec<(TrackId, Tweener<Volume>)> {
+	pub(crate) fn into_vec(self) -> Vec<(TrackId, Tweener<Volume>, SendPoint)> {
 		self.0
 			.iter()
-			.map(|(id, value)| (*id, Tweener::new(*value)))
+			.map(|(id, (value, send_point))| (*id, Tweener::new(*value), *send_point))
 			.collect()
 	}
 }
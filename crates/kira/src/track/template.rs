@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use crate::Volume;
+
+use super::{effect::EffectBuilder, routes::TrackRoutes, TrackBuilder};
+
+/// Adds a new effect to a track builder.
+type AddEffect = Box<dyn Fn(&mut TrackBuilder)>;
+
+/// A reusable description of a mixer track that can create any
+/// number of identical [`TrackBuilder`]s.
+///
+/// Effects can't be copied once they're built, so a template keeps
+/// the effect builders instead and builds a new set of effects for
+/// each track. The handles to those effects are discarded, so use a
+/// [`TrackBuilder`] directly for effects you need to control.
+pub struct TrackTemplate {
+	volume: Volume,
+	routes: TrackRoutes,
+	envelope_follower: Option<(Duration, Duration)>,
+	correlation_meter: Option<Duration>,
+	warm_up_frames: usize,
+	effects: Vec<AddEffect>,
+}
+
+impl TrackTemplate {
+	/// Creates a new [`TrackTemplate`] with the default settings.
+	pub fn new() -> Self {
+		Self {
+			volume: Volume::Amplitude(1.0),
+			routes: TrackRoutes::new(),
+			envelope_follower: None,
+			correlation_meter: None,
+			warm_up_frames: 0,
+			effects: vec![],
+		}
+	}
+
+	/// Sets the volume of the tracks.
+	pub fn volume(self, volume: impl Into<Volume>) -> Self {
+		Self {
+			volume: volume.into(),
+			..self
+		}
+	}
+
+	/// Sets how the output of the tracks should be routed
+	/// to other mixer tracks.
+	pub fn routes(self, routes: TrackRoutes) -> Self {
+		Self { routes, ..self }
+	}
+
+	/// Enables the envelope follower for the tracks.
+	///
+	/// See [`TrackBuilder::envelope_follower`].
+	pub fn envelope_follower(self, attack_duration: Duration, release_duration: Duration) -> Self {
+		Self {
+			envelope_follower: Some((attack_duration, release_duration)),
+			..self
+		}
+	}
+
+	/// Enables the correlation meter for the tracks.
+	///
+	/// See [`TrackBuilder::correlation_meter`].
+	pub fn correlation_meter(self, window: Duration) -> Self {
+		Self {
+			correlation_meter: Some(window),
+			..self
+		}
+	}
+
+	/// Sets the number of frames of silence to run through the
+	/// tracks' effects when they're added to the mixer.
+	///
+	/// See [`TrackBuilder::warm_up_frames`].
+	pub fn warm_up_frames(self, warm_up_frames: usize) -> Self {
+		Self {
+			warm_up_frames,
+			..self
+		}
+	}
+
+	/// Adds an effect to the tracks. Each track gets its own
+	/// copy of the effect.
+	pub fn add_effect<B: EffectBuilder + Clone + 'static>(&mut self, builder: B) {
+		self.add_effect_with(move || builder.clone());
+	}
+
+	/// Adds an effect to the tracks, calling `create_builder` to
+	/// get a new effect builder for each track.
+	///
+	/// This is useful for effect builders that can't be cloned,
+	/// like [`DelayBuilder`](super::effect::delay::DelayBuilder).
+	pub fn add_effect_with<B: EffectBuilder>(&mut self, create_builder: impl Fn() -> B + 'static) {
+		self.effects.push(Box::new(move |track_builder| {
+			track_builder.add_effect(create_builder());
+		}));
+	}
+
+	/// Creates a [`TrackBuilder`] with the template's settings
+	/// and a new set of effects.
+	pub fn build(&self) -> TrackBuilder {
+		let mut builder = TrackBuilder {
+			volume: self.volume,
+			routes: self.routes.clone(),
+			envelope_follower: self.envelope_follower,
+			correlation_meter: self.correlation_meter,
+			warm_up_frames: self.warm_up_frames,
+			..TrackBuilder::new()
+		};
+		for add_effect in &self.effects {
+			add_effect(&mut builder);
+		}
+		builder
+	}
+}
+
+impl Default for TrackTemplate {
+	fn default() -> Self {
+		Self::new()
+	}
+}
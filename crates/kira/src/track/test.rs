@@ -1,10 +1,21 @@
-use std::time::Duration;
+use std::{
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
 
-use crate::{dsp::Frame, tween::Tween, Volume};
+use crate::{
+	dsp::{Frame, Random},
+	manager::{backend::mock::MockBackend, AudioManager},
+	tween::Tween,
+	Volume,
+};
 
 use super::{
-	effect::{Effect, EffectBuilder},
-	Track, TrackBuilder,
+	effect::{filter::FilterBuilder, reverb::ReverbBuilder, Effect, EffectBuilder},
+	Track, TrackBuilder, TrackTemplate,
 };
 
 /// Tests that the output volume of a track can be set.
@@ -44,6 +55,68 @@ fn effects() {
 	assert_eq!(track.process(1.0), Frame::from_mono(0.75));
 }
 
+/// Tests that subnormal samples are flushed to zero
+/// in the output of a track.
+#[test]
+fn flushes_denormals() {
+	let mut track = Track::new(TrackBuilder::new());
+	track.add_input(Frame::new(f32::MIN_POSITIVE / 2.0, 0.5));
+	assert_eq!(track.process(1.0), Frame::new(0.0, 0.5));
+}
+
+/// Tests that NaN and infinite samples are replaced with silence
+/// and reported in debug builds.
+#[test]
+#[cfg(debug_assertions)]
+fn sanitizes_non_finite_samples() {
+	use super::{effect::EffectId, NonFiniteSource};
+
+	let mut track = Track::new({
+		let mut builder = TrackBuilder::new();
+		builder.add_effect(MockEffect::Mul(1.0));
+		builder.add_effect(MockEffect::Add(Frame::from_mono(f32::NAN)));
+		builder.add_effect(MockEffect::Add(Frame::from_mono(0.5)));
+		builder
+	});
+	let shared = track.shared();
+	assert_eq!(shared.last_non_finite_source(), None);
+
+	// the nan from the second effect is replaced with silence
+	// before it reaches the third effect
+	track.add_input(Frame::from_mono(1.0));
+	assert_eq!(track.process(1.0), Frame::from_mono(0.5));
+	assert_eq!(shared.non_finite_frame_count(), 1);
+	assert_eq!(
+		shared.last_non_finite_source(),
+		Some(NonFiniteSource::Effect(EffectId(1)))
+	);
+
+	let mut track = Track::new(TrackBuilder::new());
+	let shared = track.shared();
+	track.add_input(Frame::new(f32::INFINITY, 0.0));
+	assert_eq!(track.process(1.0), Frame::ZERO);
+	assert_eq!(
+		shared.last_non_finite_source(),
+		Some(NonFiniteSource::Input)
+	);
+}
+
+/// Tests that a track template creates identical tracks
+/// with their own effects.
+#[test]
+fn template() {
+	let mut template = TrackTemplate::new().volume(0.5);
+	template.add_effect(MockEffect::Add(Frame::from_mono(0.5)));
+	template.add_effect_with(|| MockEffect::Mul(0.5));
+	let mut tracks = [Track::new(template.build()), Track::new(template.build())];
+	for track in &mut tracks {
+		assert_eq!(track.effects.len(), 2);
+		track.add_input(Frame::from_mono(1.0));
+		assert_eq!(track.process(1.0), Frame::from_mono(0.375));
+	}
+}
+
+#[derive(Clone, Copy)]
 enum MockEffect {
 	Add(Frame),
 	Mul(f32),
@@ -65,3 +138,169 @@ impl Effect for MockEffect {
 		}
 	}
 }
+
+/// Tests that the envelope follower of a track rises and
+/// falls with the track's output.
+#[test]
+#[allow(clippy::float_cmp)]
+fn envelope_follower() {
+	let mut track =
+		Track::new(TrackBuilder::new().envelope_follower(Duration::ZERO, Duration::from_secs(1)));
+	let shared = track.shared();
+	track.add_input(Frame::new(0.25, -0.5));
+	track.process(1.0);
+	track.on_start_processing();
+	assert_eq!(shared.envelope(), 0.5);
+	track.process(1.0);
+	track.on_start_processing();
+	assert!((shared.envelope() - 0.5 * (-1.0f64).exp()).abs() < 1.0e-9);
+}
+
+/// Tests that the envelope of a track without an envelope
+/// follower is always zero.
+#[test]
+#[allow(clippy::float_cmp)]
+fn envelope_follower_disabled() {
+	let mut track = Track::new(TrackBuilder::new());
+	let shared = track.shared();
+	track.add_input(Frame::from_mono(1.0));
+	track.process(1.0);
+	track.on_start_processing();
+	assert_eq!(shared.envelope(), 0.0);
+}
+
+/// Tests that the correlation meter reports `1.0` for in-phase
+/// audio and `-1.0` for out-of-phase audio.
+#[test]
+fn correlation_meter() {
+	let correlation = |right_sign: f32| {
+		let mut track =
+			Track::new(TrackBuilder::new().correlation_meter(Duration::from_millis(100)));
+		let shared = track.shared();
+		for i in 0..1000 {
+			let sample = (i as f32 * 0.05).sin();
+			track.add_input(Frame::new(sample, sample * right_sign));
+			track.process(0.001);
+		}
+		track.on_start_processing();
+		shared.correlation()
+	};
+	assert!((correlation(1.0) - 1.0).abs() < 1.0e-3);
+	assert!((correlation(-1.0) + 1.0).abs() < 1.0e-3);
+}
+
+/// Tests that the correlation of a track without a correlation
+/// meter is always zero.
+#[test]
+#[allow(clippy::float_cmp)]
+fn correlation_meter_disabled() {
+	let mut track = Track::new(TrackBuilder::new());
+	let shared = track.shared();
+	track.add_input(Frame::from_mono(1.0));
+	track.process(1.0);
+	track.on_start_processing();
+	assert_eq!(shared.correlation(), 0.0);
+}
+
+/// Tests that a track skips processing its effects while it's
+/// idle, and that its volume tweens keep advancing.
+#[test]
+fn skips_effects_while_idle() {
+	let num_process_calls = Arc::new(AtomicUsize::new(0));
+	let mut track = Track::new({
+		let mut builder = TrackBuilder::new();
+		builder.add_effect(CountingEffect(num_process_calls.clone()));
+		builder
+	});
+	track.init_effects(1, &mut Random::new(0));
+	track.process(1.0);
+	track.process(1.0);
+	assert_eq!(num_process_calls.load(Ordering::SeqCst), 1);
+
+	// tweens should keep advancing while the track is idle
+	track.set_volume(
+		Volume::Amplitude(0.5),
+		Tween {
+			duration: Duration::from_secs(2),
+			..Default::default()
+		},
+	);
+	track.process(1.0);
+	track.process(1.0);
+	assert_eq!(num_process_calls.load(Ordering::SeqCst), 1);
+
+	// the track should resume as soon as it receives input
+	track.add_input(Frame::from_mono(1.0));
+	assert_eq!(track.process(1.0), Frame::from_mono(0.5));
+	assert_eq!(num_process_calls.load(Ordering::SeqCst), 2);
+}
+
+/// Tests that a track's effects process silence when the track
+/// is initialized if warm-up is enabled.
+#[test]
+fn warms_up_effects() {
+	let num_process_calls = Arc::new(AtomicUsize::new(0));
+	let mut track = Track::new({
+		let mut builder = TrackBuilder::new().warm_up_frames(4);
+		builder.add_effect(CountingEffect(num_process_calls.clone()));
+		builder
+	});
+	track.init_effects(1, &mut Random::new(0));
+	assert_eq!(num_process_calls.load(Ordering::SeqCst), 4);
+}
+
+/// Tests that a warmed-up reverb produces the same tail as a
+/// reverb that wasn't warmed up.
+#[test]
+fn warmed_up_reverb_tail() {
+	let render = |warm_up_frames: usize| {
+		let mut track = Track::new({
+			let mut builder = TrackBuilder::new().warm_up_frames(warm_up_frames);
+			builder.add_effect(ReverbBuilder::new());
+			builder
+		});
+		track.init_effects(48_000, &mut Random::new(0));
+		track.add_input(Frame::from_mono(1.0));
+		(0..4800)
+			.map(|_| track.process(1.0 / 48_000.0))
+			.collect::<Vec<_>>()
+	};
+	let cold = render(0);
+	assert!(cold[1000..].iter().any(|frame| frame.left.abs() > 0.0));
+	assert_eq!(render(512), cold);
+}
+
+/// Tests that a track handle lists the effects on the track.
+#[test]
+fn effect_ids_and_names() {
+	let mut manager = AudioManager::<MockBackend>::new(Default::default()).unwrap();
+	let mut builder = TrackBuilder::new();
+	builder.add_effect(FilterBuilder::new());
+	builder.add_effect(CountingEffect(Arc::new(AtomicUsize::new(0))));
+	builder.add_effect(ReverbBuilder::new());
+	let track = manager.add_sub_track(builder).unwrap();
+	let ids = track.effect_ids();
+	assert_eq!(ids.len(), 3);
+	assert_eq!(ids[1].index(), 1);
+	assert_eq!(track.effect_name(ids[0]), Some("Filter"));
+	assert_eq!(track.effect_name(ids[1]), Some("Custom effect"));
+	assert_eq!(track.effect_name(ids[2]), Some("Reverb"));
+	assert_eq!(manager.main_track().effect_ids(), vec![]);
+}
+
+struct CountingEffect(Arc<AtomicUsize>);
+
+impl EffectBuilder for CountingEffect {
+	type Handle = ();
+
+	fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+		(Box::new(self), ())
+	}
+}
+
+impl Effect for CountingEffect {
+	fn process(&mut self, input: Frame, _dt: f64) -> Frame {
+		self.0.fetch_add(1, Ordering::SeqCst);
+		input
+	}
+}
@@ -1,14 +1,16 @@
 //! Smooth interpolation between values.
 
+mod envelope;
 mod tweenable;
 mod tweener;
 
+pub use envelope::*;
 pub use tweenable::*;
 pub use tweener::*;
 
 use std::time::Duration;
 
-use crate::start_time::StartTime;
+use crate::{start_time::StartTime, Volume};
 
 /// Curves the motion of a [`Tween`].
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -86,6 +88,42 @@ impl Default for Easing {
 	}
 }
 
+/// How the volume changes over the course of a fade in or fade out.
+///
+/// This is used when a [`Tween`] is passed to a method that fades
+/// a sound or the audio manager to or from silence, like
+/// `pause`, `resume`, and `stop`. The [`Easing`] of the tween
+/// is applied on top of the curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum FadeCurve {
+	/// Changes the volume linearly in decibels, which sounds
+	/// like a steady change in loudness.
+	#[default]
+	Decibels,
+	/// Changes the amplitude linearly. Most of the perceived change
+	/// in loudness happens near the silent end of the fade.
+	Amplitude,
+}
+
+impl FadeCurve {
+	/// Returns the volume at the silent end of the fade.
+	pub(crate) fn silent_volume(self) -> Volume {
+		match self {
+			FadeCurve::Decibels => Volume::Decibels(Volume::MIN_DECIBELS),
+			FadeCurve::Amplitude => Volume::Amplitude(0.0),
+		}
+	}
+
+	/// Returns the volume at the audible end of the fade.
+	pub(crate) fn full_volume(self) -> Volume {
+		match self {
+			FadeCurve::Decibels => Volume::Decibels(0.0),
+			FadeCurve::Amplitude => Volume::Amplitude(1.0),
+		}
+	}
+}
+
 /// Describes a smooth transition between values.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Tween {
@@ -95,6 +133,9 @@ pub struct Tween {
 	pub duration: Duration,
 	/// The curve of the motion.
 	pub easing: Easing,
+	/// How the volume changes if this tween is used to fade
+	/// to or from silence. This has no effect on other tweens.
+	pub fade_curve: FadeCurve,
 }
 
 impl Tween {
@@ -109,6 +150,7 @@ impl Default for Tween {
 			start_time: StartTime::default(),
 			duration: Duration::from_millis(10),
 			easing: Easing::Linear,
+			fade_curve: FadeCurve::Decibels,
 		}
 	}
 }
@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+	Idle,
+	Attack,
+	Hold,
+	Decay,
+	Sustain,
+	Release,
+}
+
+/// An attack-hold-decay-sustain-release envelope.
+///
+/// When [triggered](Envelope::trigger), the envelope rises to `1.0`
+/// over the attack duration, stays at `1.0` for the hold duration,
+/// falls to the sustain level over the decay duration, and then
+/// stays at the sustain level until it's [released](Envelope::release).
+/// Once released, it falls to `0.0` over the release duration.
+///
+/// Like [`Tweener`](super::Tweener), an envelope is advanced by
+/// calling [`update`](Envelope::update) with the amount of time that
+/// has passed, so it can be used from the audio thread without
+/// allocating.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Envelope {
+	attack: Duration,
+	hold: Duration,
+	decay: Duration,
+	sustain: f64,
+	release: Duration,
+	stage: Stage,
+	/// The time since the current stage started (in seconds).
+	time: f64,
+	/// The value the current stage started from. Attacks and
+	/// releases start from wherever the envelope was when
+	/// they were triggered to avoid discontinuities.
+	start_value: f64,
+	value: f64,
+}
+
+impl Envelope {
+	/// Creates a new [`Envelope`] with no attack, hold, decay,
+	/// or release and a sustain level of `1.0`.
+	pub fn new() -> Self {
+		Self {
+			attack: Duration::ZERO,
+			hold: Duration::ZERO,
+			decay: Duration::ZERO,
+			sustain: 1.0,
+			release: Duration::ZERO,
+			stage: Stage::Idle,
+			time: 0.0,
+			start_value: 0.0,
+			value: 0.0,
+		}
+	}
+
+	/// Sets how long it takes for the envelope to rise to `1.0`.
+	pub fn attack_duration(self, attack: Duration) -> Self {
+		Self { attack, ..self }
+	}
+
+	/// Sets how long the envelope stays at `1.0` after the attack.
+	pub fn hold_duration(self, hold: Duration) -> Self {
+		Self { hold, ..self }
+	}
+
+	/// Sets how long it takes for the envelope to fall from `1.0`
+	/// to the sustain level.
+	pub fn decay_duration(self, decay: Duration) -> Self {
+		Self { decay, ..self }
+	}
+
+	/// Sets the value the envelope stays at until it's released.
+	pub fn sustain_level(self, sustain: f64) -> Self {
+		Self { sustain, ..self }
+	}
+
+	/// Sets how long it takes for the envelope to fall to `0.0`
+	/// after it's released.
+	pub fn release_duration(self, release: Duration) -> Self {
+		Self { release, ..self }
+	}
+
+	/// Returns the current value of the envelope.
+	pub fn value(&self) -> f64 {
+		self.value
+	}
+
+	/// Returns `true` if the envelope has finished releasing
+	/// (or was never triggered).
+	pub fn is_idle(&self) -> bool {
+		self.stage == Stage::Idle
+	}
+
+	/// Starts the attack stage of the envelope.
+	///
+	/// If the envelope is already active, the attack starts
+	/// from the current value.
+	pub fn trigger(&mut self) {
+		self.start_stage(Stage::Attack);
+	}
+
+	/// Starts the release stage of the envelope.
+	///
+	/// The release starts from the current value, so releasing
+	/// the envelope partway through the attack or decay
+	/// won't cause a jump.
+	pub fn release(&mut self) {
+		if self.stage != Stage::Idle {
+			self.start_stage(Stage::Release);
+		}
+	}
+
+	/// Advances the envelope by `dt` seconds.
+	pub fn update(&mut self, dt: f64) {
+		self.time += dt;
+		// a single update can pass through multiple stages,
+		// so keep going until we find the stage we end up in
+		loop {
+			match self.stage {
+				Stage::Idle => {
+					self.time = 0.0;
+					return;
+				}
+				Stage::Attack => {
+					let duration = self.attack.as_secs_f64();
+					if self.time < duration {
+						self.value =
+							self.start_value + (1.0 - self.start_value) * self.time / duration;
+						return;
+					}
+					self.time -= duration;
+					self.value = 1.0;
+					self.stage = Stage::Hold;
+				}
+				Stage::Hold => {
+					let duration = self.hold.as_secs_f64();
+					if self.time < duration {
+						return;
+					}
+					self.time -= duration;
+					self.stage = Stage::Decay;
+				}
+				Stage::Decay => {
+					let duration = self.decay.as_secs_f64();
+					if self.time < duration {
+						self.value = 1.0 + (self.sustain - 1.0) * self.time / duration;
+						return;
+					}
+					self.time -= duration;
+					self.value = self.sustain;
+					self.stage = Stage::Sustain;
+				}
+				Stage::Sustain => {
+					self.time = 0.0;
+					return;
+				}
+				Stage::Release => {
+					let duration = self.release.as_secs_f64();
+					if self.time < duration {
+						self.value = self.start_value * (1.0 - self.time / duration);
+						return;
+					}
+					self.value = 0.0;
+					self.stage = Stage::Idle;
+				}
+			}
+		}
+	}
+
+	fn start_stage(&mut self, stage: Stage) {
+		self.stage = stage;
+		self.time = 0.0;
+		self.start_value = self.value;
+	}
+}
+
+impl Default for Envelope {
+	fn default() -> Self {
+		Self::new()
+	}
+}
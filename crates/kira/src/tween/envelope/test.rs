@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use super::Envelope;
+
+fn test_envelope() -> Envelope {
+	Envelope::new()
+		.attack_duration(Duration::from_secs(2))
+		.hold_duration(Duration::from_secs(1))
+		.decay_duration(Duration::from_secs(2))
+		.sustain_level(0.5)
+		.release_duration(Duration::from_secs(2))
+}
+
+/// Tests that an envelope goes through each of its stages
+/// and holds the sustain level until it's released.
+#[test]
+#[allow(clippy::float_cmp)]
+fn stages() {
+	let mut envelope = test_envelope();
+	// the envelope shouldn't do anything until it's triggered
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 0.0);
+	assert!(envelope.is_idle());
+
+	envelope.trigger();
+	// attack
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 0.5);
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 1.0);
+	// hold
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 1.0);
+	// decay
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 0.75);
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 0.5);
+	// sustain
+	for _ in 0..10 {
+		envelope.update(1.0);
+		assert_eq!(envelope.value(), 0.5);
+	}
+	assert!(!envelope.is_idle());
+
+	envelope.release();
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 0.25);
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 0.0);
+	assert!(envelope.is_idle());
+}
+
+/// Tests that releasing an envelope partway through the attack
+/// releases from the current value.
+#[test]
+#[allow(clippy::float_cmp)]
+fn release_during_attack() {
+	let mut envelope = test_envelope();
+	envelope.trigger();
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 0.5);
+	envelope.release();
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 0.25);
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 0.0);
+	assert!(envelope.is_idle());
+}
+
+/// Tests that retriggering an envelope restarts the attack
+/// from the current value.
+#[test]
+#[allow(clippy::float_cmp)]
+fn retrigger() {
+	let mut envelope = test_envelope();
+	envelope.trigger();
+	for _ in 0..5 {
+		envelope.update(1.0);
+	}
+	assert_eq!(envelope.value(), 0.5);
+	envelope.trigger();
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 0.75);
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 1.0);
+}
+
+/// Tests that stages with no duration are skipped.
+#[test]
+#[allow(clippy::float_cmp)]
+fn zero_length_stages() {
+	let mut envelope = Envelope::new().sustain_level(0.5);
+	envelope.trigger();
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 0.5);
+	envelope.release();
+	envelope.update(1.0);
+	assert_eq!(envelope.value(), 0.0);
+	assert!(envelope.is_idle());
+}
@@ -45,7 +45,9 @@ impl<T: Tweenable> Tweener<T> {
 	pub fn set(&mut self, target: T, tween: Tween) {
 		self.state = State::Tweening {
 			values: (self.value, target),
-			time: 0.0,
+			// a delayed tween starts at a negative time so it
+			// won't start changing the value until the delay is over
+			time: -tween.start_time.delay().unwrap_or(0.0),
 			tween,
 			waiting_to_start: matches!(tween.start_time, StartTime::ClockTime(..)),
 		}
@@ -65,6 +67,9 @@ impl<T: Tweenable> Tweener<T> {
 				return false;
 			}
 			*time += dt;
+			if *time < 0.0 {
+				return false;
+			}
 			if *time >= tween.duration.as_secs_f64() {
 				self.value = values.1;
 				self.state = State::Idle;
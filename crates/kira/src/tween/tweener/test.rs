@@ -101,3 +101,61 @@ fn waits_for_start_time() {
 	assert!(tweener.update(1.0));
 	assert_eq!(tweener.value(), 1.0);
 }
+
+/// Tests that a Tweener with a delay set as the start
+/// time waits for the delay before it begins tweening.
+#[test]
+#[allow(clippy::float_cmp)]
+fn waits_for_start_delay() {
+	let mut tweener = Tweener::new(0.0);
+	tweener.set(
+		1.0,
+		Tween {
+			start_time: StartTime::Delay(Duration::from_secs(2)),
+			duration: Duration::from_secs(2),
+			..Default::default()
+		},
+	);
+
+	// value should not be changing yet
+	for _ in 0..2 {
+		assert!(!tweener.update(1.0));
+		assert_eq!(tweener.value(), 0.0);
+	}
+
+	assert!(!tweener.update(1.0));
+	assert_eq!(tweener.value(), 0.5);
+	assert!(tweener.update(1.0));
+	assert_eq!(tweener.value(), 1.0);
+}
+
+/// Tests that a tween that's waiting for its start delay
+/// can be replaced by an immediate tween.
+#[test]
+#[allow(clippy::float_cmp)]
+fn delayed_tween_can_be_replaced() {
+	let mut tweener = Tweener::new(0.0);
+	tweener.set(
+		1.0,
+		Tween {
+			start_time: StartTime::Delay(Duration::from_secs(5)),
+			duration: Duration::from_secs(1),
+			..Default::default()
+		},
+	);
+	assert!(!tweener.update(1.0));
+	assert_eq!(tweener.value(), 0.0);
+	tweener.set(
+		2.0,
+		Tween {
+			duration: Duration::from_secs(1),
+			..Default::default()
+		},
+	);
+	assert!(tweener.update(1.0));
+	assert_eq!(tweener.value(), 2.0);
+	for _ in 0..5 {
+		assert!(!tweener.update(1.0));
+		assert_eq!(tweener.value(), 2.0);
+	}
+}
@@ -1,3 +1,5 @@
+use std::ops::{Mul, MulAssign};
+
 use crate::tween::Tweenable;
 
 /// A change in volume of a sound.
@@ -45,6 +47,39 @@ impl Volume {
 			Volume::Decibels(db) => *db,
 		}
 	}
+
+	/// Returns the same volume expressed as a [`Volume::Amplitude`].
+	pub fn to_amplitude(&self) -> Self {
+		Volume::Amplitude(self.as_amplitude())
+	}
+
+	/// Returns the same volume expressed as a [`Volume::Decibels`].
+	pub fn to_decibels(&self) -> Self {
+		Volume::Decibels(self.as_decibels())
+	}
+}
+
+/// Combines two volume adjustments, as if one was applied
+/// after the other.
+///
+/// Amplitudes are multiplied, which is the same as adding
+/// decibels. The result uses the same representation as
+/// the left-hand side.
+impl Mul for Volume {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		match self {
+			Volume::Amplitude(amplitude) => Volume::Amplitude(amplitude * rhs.as_amplitude()),
+			Volume::Decibels(db) => Volume::Decibels(db + rhs.as_decibels()),
+		}
+	}
+}
+
+impl MulAssign for Volume {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs;
+	}
 }
 
 impl From<f64> for Volume {
@@ -90,4 +125,29 @@ fn test() {
 		0.0
 	);
 	assert_eq!(Volume::Amplitude(-1.0).as_decibels(), Volume::MIN_DECIBELS);
+
+	// test conversions between representations
+	assert_eq!(Volume::Decibels(0.0).to_amplitude(), Volume::Amplitude(1.0));
+	assert_eq!(Volume::Amplitude(1.0).to_decibels(), Volume::Decibels(0.0));
+
+	// test combining volumes
+	assert_eq!(
+		Volume::Decibels(-6.0) * Volume::Decibels(-6.0),
+		Volume::Decibels(-12.0)
+	);
+	assert_eq!(
+		Volume::Amplitude(0.5) * Volume::Amplitude(0.5),
+		Volume::Amplitude(0.25)
+	);
+	let mut volume = Volume::Amplitude(0.5);
+	volume *= Volume::Decibels(-6.0);
+	assert_eq!(
+		volume,
+		Volume::Amplitude(0.5 * Volume::Decibels(-6.0).as_amplitude())
+	);
+	assert!(
+		((Volume::Decibels(-6.0) * Volume::Amplitude(0.5)).as_decibels()
+			- (-6.0 + Volume::Amplitude(0.5).as_decibels()))
+		.abs() < 0.00001
+	);
 }
@@ -34,6 +34,12 @@ impl Effect for TestEffect {
 		self.dt_producer.push(dt).unwrap();
 		Frame::ZERO
 	}
+
+	// this effect never receives input, but it still needs
+	// to be processed so it can report `dt`
+	fn tail_frames(&self) -> usize {
+		usize::MAX
+	}
 }
 
 struct TestEffectHandle {
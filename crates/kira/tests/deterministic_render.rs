@@ -0,0 +1,58 @@
+use std::{sync::Arc, time::Duration};
+
+use kira::{
+	dsp::Frame,
+	manager::{
+		backend::mock::{MockBackend, MockBackendSettings},
+		AudioManager, AudioManagerSettings,
+	},
+	sound::static_sound::{StaticSoundData, StaticSoundSettings},
+};
+
+const BLOCK_SIZE: usize = 4;
+
+fn render_mix() -> Vec<Frame> {
+	let mut manager = AudioManager::<MockBackend>::new(AudioManagerSettings {
+		backend_settings: MockBackendSettings { sample_rate: 1 },
+		random_seed: Some(0),
+		start_stop_fade_duration: Duration::ZERO,
+		..Default::default()
+	})
+	.unwrap();
+	manager
+		.play(StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new(
+				[1.0, 0.5, 0.25, 0.0]
+					.iter()
+					.copied()
+					.map(Frame::from_mono)
+					.collect(),
+			),
+			settings: StaticSoundSettings::new(),
+		})
+		.unwrap();
+	manager
+		.play(StaticSoundData {
+			sample_rate: 1,
+			frames: Arc::new(vec![Frame::from_mono(0.5); 4]),
+			settings: StaticSoundSettings::new().volume(0.5),
+		})
+		.unwrap();
+	let mut output = manager.backend_mut().process_block(BLOCK_SIZE);
+	output.extend(manager.backend_mut().process_block(BLOCK_SIZE));
+	output
+}
+
+/// Tests that rendering a mix with the mock backend produces
+/// exactly the expected frames, and the same frames every time.
+#[test]
+fn deterministic_render() {
+	let expected: Vec<Frame> = [1.0, 0.5, 0.25, 0.0]
+		.iter()
+		.map(|&sample| Frame::from_mono(sample).panned(0.5) + Frame::from_mono(0.25).panned(0.5))
+		.chain(vec![Frame::ZERO; BLOCK_SIZE])
+		.collect();
+	assert_eq!(render_mix(), expected);
+	assert_eq!(render_mix(), render_mix());
+}